@@ -5,12 +5,12 @@ use itertools::Itertools;
 use serde_json::{json, Value};
 use stellar_xdr::curr::{
     AccountId, BytesM, ContractExecutable, Error as XdrError, Hash, Int128Parts, Int256Parts,
-    PublicKey, ScAddress, ScBytes, ScContractInstance, ScMap, ScMapEntry, ScNonceKey, ScSpecEntry,
-    ScSpecFunctionV0, ScSpecTypeDef as ScType, ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeResult,
-    ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec, ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0,
-    ScSpecUdtErrorEnumV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseTupleV0, ScSpecUdtUnionCaseV0,
-    ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0, ScString, ScSymbol, ScVal, ScVec, StringM,
-    UInt128Parts, UInt256Parts, Uint256, VecM,
+    PublicKey, ScAddress, ScBytes, ScContractInstance, ScError, ScErrorCode, ScMap, ScMapEntry,
+    ScNonceKey, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef as ScType, ScSpecTypeMap,
+    ScSpecTypeOption, ScSpecTypeResult, ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec,
+    ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionCaseTupleV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionCaseVoidV0, ScSpecUdtUnionV0,
+    ScString, ScSymbol, ScVal, ScVec, StringM, UInt128Parts, UInt256Parts, Uint256, VecM,
 };
 
 pub mod contract;
@@ -59,6 +59,8 @@ pub enum Error {
     Infallible(#[from] std::convert::Infallible),
     #[error("Missing Error case {0}")]
     MissingErrorCase(u32),
+    #[error("{0} has more than {1} decimal places")]
+    TooManyDecimals(String, u32),
     #[error(transparent)]
     Spec(#[from] soroban_spec::read::FromWasmError),
     #[error(transparent)]
@@ -226,13 +228,29 @@ impl Spec {
     /// Might return errors
     #[allow(clippy::wrong_self_convention)]
     pub fn from_string(&self, s: &str, t: &ScType) -> Result<ScVal, Error> {
+        self.from_string_with_decimals(s, t, None)
+    }
+
+    /// Like [`Spec::from_string`], scaling `i128` and `u128` values by `decimals` places, e.g. with
+    /// 7 decimals `"1.5"` is parsed as `15000000` and `"2"` as `20000000`.
+    ///
+    /// # Errors
+    ///
+    /// Might return errors
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_string_with_decimals(
+        &self,
+        s: &str,
+        t: &ScType,
+        decimals: Option<u32>,
+    ) -> Result<ScVal, Error> {
         if let ScType::Option(b) = t {
             if s == "null" {
                 return Ok(ScVal::Void);
             }
             let ScSpecTypeOption { value_type } = b.as_ref().clone();
             let v = value_type.as_ref().clone();
-            return self.from_string(s, &v);
+            return self.from_string_with_decimals(s, &v, decimals);
         }
         // Parse as string and for special types assume Value::String
         serde_json::from_str(s)
@@ -250,7 +268,9 @@ impl Spec {
                     ScType::Udt(ScSpecTypeUdt { name })
                         if matches!(
                             self.find(&name.to_utf8_string_lossy())?,
-                            ScSpecEntry::UdtUnionV0(_) | ScSpecEntry::UdtStructV0(_)
+                            ScSpecEntry::UdtUnionV0(_)
+                                | ScSpecEntry::UdtStructV0(_)
+                                | ScSpecEntry::UdtEnumV0(_)
                         ) =>
                     {
                         Ok(Value::String(s.to_owned()))
@@ -264,7 +284,7 @@ impl Spec {
                     _ => Ok(val),
                 },
             )
-            .and_then(|raw| self.from_json(&raw, t))
+            .and_then(|raw| self.from_json_with_decimals(&raw, t, decimals))
     }
 
     /// # Errors
@@ -272,7 +292,32 @@ impl Spec {
     /// Might return errors
     #[allow(clippy::wrong_self_convention)]
     pub fn from_json(&self, v: &Value, t: &ScType) -> Result<ScVal, Error> {
+        self.from_json_with_decimals(v, t, None)
+    }
+
+    /// Like [`Spec::from_json`], scaling `i128` and `u128` strings and numbers by `decimals`
+    /// places, e.g. with 7 decimals `"1.5"` is parsed as `15000000` and `2` as `20000000`.
+    ///
+    /// # Errors
+    ///
+    /// Might return errors
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_json_with_decimals(
+        &self,
+        v: &Value,
+        t: &ScType,
+        decimals: Option<u32>,
+    ) -> Result<ScVal, Error> {
+        if let (Some(decimals), ScType::I128 | ScType::U128, Value::String(_) | Value::Number(_)) =
+            (decimals, t, v)
+        {
+            let s = v.as_str().map_or_else(|| v.to_string(), str::to_string);
+            return from_json_primitives(&Value::String(scale_decimal(&s, decimals, t)?), t);
+        }
         let val: ScVal = match (t, v) {
+            (ScType::I128 | ScType::U128, Value::String(s)) if s.contains('.') => {
+                return Err(Error::InvalidValue(Some(t.clone())));
+            }
             (
                 ScType::Bool
                 | ScType::U128
@@ -295,7 +340,7 @@ impl Spec {
             (ScType::Vec(elem), Value::Array(raw)) => {
                 let converted: ScVec = raw
                     .iter()
-                    .map(|item| self.from_json(item, &elem.element_type))
+                    .map(|item| self.from_json_with_decimals(item, &elem.element_type, decimals))
                     .collect::<Result<Vec<ScVal>, Error>>()?
                     .try_into()
                     .map_err(Error::Xdr)?;
@@ -303,17 +348,42 @@ impl Spec {
             }
 
             // Map parsing
-            (ScType::Map(map), Value::Object(raw)) => self.parse_map(map, raw)?,
+            (ScType::Map(map), Value::Object(raw)) => self.parse_map(map, raw, decimals)?,
 
             // Option parsing
             (ScType::Option(_), Value::Null) => ScVal::Void,
-            (ScType::Option(elem), v) => self.from_json(v, &elem.value_type)?,
+            (ScType::Option(elem), v) => {
+                self.from_json_with_decimals(v, &elem.value_type, decimals)?
+            }
 
             // Tuple parsing
-            (ScType::Tuple(elem), Value::Array(raw)) => self.parse_tuple(t, elem, raw)?,
+            (ScType::Tuple(elem), Value::Array(raw)) => self.parse_tuple(t, elem, raw, decimals)?,
 
             // User defined types parsing
-            (ScType::Udt(ScSpecTypeUdt { name }), _) => self.parse_udt(name, v)?,
+            (ScType::Udt(ScSpecTypeUdt { name }), _) => self.parse_udt(name, v, decimals)?,
+
+            // Error parsing, in the same form as they are output
+            (ScType::Error, Value::Object(map)) if map.contains_key("type") => {
+                ScVal::Error(sc_error_from_json(map)?)
+            }
+
+            // Result parsing, as `{"ok": value}` or `{"err": error}`. Any other value is the ok
+            // value on its own
+            (ScType::Result(result), v) => {
+                match v
+                    .as_object()
+                    .filter(|map| map.len() == 1)
+                    .and_then(|map| map.iter().next())
+                {
+                    Some((key, v)) if key == "ok" => {
+                        self.from_json_with_decimals(v, &result.ok_type, decimals)?
+                    }
+                    Some((key, v)) if key == "err" => {
+                        self.from_json_with_decimals(v, &result.error_type, decimals)?
+                    }
+                    _ => self.from_json_with_decimals(v, &result.ok_type, decimals)?,
+                }
+            }
 
             // TODO: Implement the rest of these
             (_, raw) => serde_json::from_value(raw.clone()).map_err(Error::Serde)?,
@@ -321,7 +391,12 @@ impl Spec {
         Ok(val)
     }
 
-    fn parse_udt(&self, name: &StringM<60>, value: &Value) -> Result<ScVal, Error> {
+    fn parse_udt(
+        &self,
+        name: &StringM<60>,
+        value: &Value,
+        decimals: Option<u32>,
+    ) -> Result<ScVal, Error> {
         let name = &name.to_utf8_string_lossy();
         match (self.find(name)?, value) {
             (ScSpecEntry::UdtStructV0(strukt), Value::Object(map)) => {
@@ -335,19 +410,39 @@ impl Spec {
                         &(0..map.len())
                             .map(|i| map.get(&i.to_string()).unwrap().clone())
                             .collect::<Vec<_>>(),
+                        decimals,
                     )
                 } else {
-                    self.parse_strukt(strukt, map)
+                    self.parse_strukt(strukt, map, decimals)
                 }
             }
             (ScSpecEntry::UdtStructV0(strukt), Value::Array(arr)) => {
-                self.parse_tuple_strukt(strukt, arr)
+                self.parse_tuple_strukt(strukt, arr, decimals)
             }
             (
                 ScSpecEntry::UdtUnionV0(union),
                 val @ (Value::Array(_) | Value::String(_) | Value::Object(_)),
-            ) => self.parse_union(union, val),
+            ) => self.parse_union(union, val, decimals),
             (ScSpecEntry::UdtEnumV0(enum_), Value::Number(num)) => parse_const_enum(num, enum_),
+            (ScSpecEntry::UdtEnumV0(enum_), Value::String(case_name)) => enum_
+                .cases
+                .iter()
+                .find(|c| c.name.to_utf8_string_lossy() == *case_name)
+                .map(|c| ScVal::U32(c.value))
+                .ok_or_else(|| Error::FailedToFindEnumCase(case_name.clone())),
+            (ScSpecEntry::UdtErrorEnumV0(_), Value::Number(num)) => {
+                let num = num
+                    .as_u64()
+                    .and_then(|n| u32::try_from(n).ok())
+                    .ok_or_else(|| Error::FailedNumConversion(num.clone()))?;
+                Ok(ScVal::Error(ScError::Contract(num)))
+            }
+            (ScSpecEntry::UdtErrorEnumV0(enum_), Value::String(case_name)) => enum_
+                .cases
+                .iter()
+                .find(|c| c.name.to_utf8_string_lossy() == *case_name)
+                .map(|c| ScVal::Error(ScError::Contract(c.value)))
+                .ok_or_else(|| Error::FailedToFindEnumCase(case_name.clone())),
             (s, v) => todo!("Not implemented for {s:#?} {v:#?}"),
         }
     }
@@ -356,6 +451,7 @@ impl Spec {
         &self,
         strukt: &ScSpecUdtStructV0,
         array: &[Value],
+        decimals: Option<u32>,
     ) -> Result<ScVal, Error> {
         let items = strukt
             .fields
@@ -363,7 +459,7 @@ impl Spec {
             .iter()
             .zip(array.iter())
             .map(|(f, v)| {
-                let val = self.from_json(v, &f.type_)?;
+                let val = self.from_json_with_decimals(v, &f.type_, decimals)?;
                 Ok(val)
             })
             .collect::<Result<Vec<_>, Error>>()?;
@@ -374,6 +470,7 @@ impl Spec {
         &self,
         strukt: &ScSpecUdtStructV0,
         map: &serde_json::Map<String, Value>,
+        decimals: Option<u32>,
     ) -> Result<ScVal, Error> {
         let items = strukt
             .fields
@@ -384,7 +481,7 @@ impl Spec {
                 let v = map
                     .get(name)
                     .ok_or_else(|| Error::MissingKey(name.clone()))?;
-                let val = self.from_json(v, &f.type_)?;
+                let val = self.from_json_with_decimals(v, &f.type_, decimals)?;
                 let key = StringM::from_str(name).unwrap();
                 Ok(ScMapEntry {
                     key: ScVal::Symbol(key.into()),
@@ -396,7 +493,12 @@ impl Spec {
         Ok(ScVal::Map(Some(map)))
     }
 
-    fn parse_union(&self, union: &ScSpecUdtUnionV0, value: &Value) -> Result<ScVal, Error> {
+    fn parse_union(
+        &self,
+        union: &ScSpecUdtUnionV0,
+        value: &Value,
+        decimals: Option<u32>,
+    ) -> Result<ScVal, Error> {
         let (enum_case, rest) = match value {
             Value::String(s) => (s, None),
             Value::Object(o) if o.len() == 1 => {
@@ -438,7 +540,7 @@ impl Spec {
             (ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 { type_, .. }), Some(arr))
                 if type_.len() == 1 =>
             {
-                res.push(self.from_json(&arr, &type_[0])?);
+                res.push(self.from_json_with_decimals(&arr, &type_[0], decimals)?);
             }
             (
                 ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 { type_, .. }),
@@ -447,7 +549,7 @@ impl Spec {
                 res.extend(
                     arr.iter()
                         .zip(type_.iter())
-                        .map(|(elem, ty)| self.from_json(elem, ty))
+                        .map(|(elem, ty)| self.from_json_with_decimals(elem, ty, decimals))
                         .collect::<Result<Vec<_>, _>>()?,
                 );
             }
@@ -461,6 +563,7 @@ impl Spec {
         t: &ScType,
         tuple: &ScSpecTypeTuple,
         items: &[Value],
+        decimals: Option<u32>,
     ) -> Result<ScVal, Error> {
         let ScSpecTypeTuple { value_types } = tuple;
         if items.len() != value_types.len() {
@@ -469,7 +572,7 @@ impl Spec {
         let parsed: Result<Vec<ScVal>, Error> = items
             .iter()
             .zip(value_types.iter())
-            .map(|(item, t)| self.from_json(item, t))
+            .map(|(item, t)| self.from_json_with_decimals(item, t, decimals))
             .collect();
         let converted: ScVec = parsed?.try_into().map_err(Error::Xdr)?;
         Ok(ScVal::Vec(Some(converted)))
@@ -479,6 +582,7 @@ impl Spec {
         &self,
         map: &ScSpecTypeMap,
         value_map: &serde_json::Map<String, Value>,
+        decimals: Option<u32>,
     ) -> Result<ScVal, Error> {
         let ScSpecTypeMap {
             key_type,
@@ -488,8 +592,8 @@ impl Spec {
         let parsed: Result<Vec<ScMapEntry>, Error> = value_map
             .iter()
             .map(|(k, v)| -> Result<ScMapEntry, Error> {
-                let key = self.from_string(k, key_type)?;
-                let val = self.from_json(v, value_type)?;
+                let key = self.from_string_with_decimals(k, key_type, decimals)?;
+                let val = self.from_json_with_decimals(v, value_type, decimals)?;
                 Ok(ScMapEntry { key, val })
             })
             .collect();
@@ -502,11 +606,7 @@ impl Spec {
 impl Spec {
     /// # Errors
     ///
-    /// Might return `Error::InvalidValue`
-    ///
-    /// # Panics
-    ///
-    /// May panic
+    /// Might return `Error::InvalidValue`, or `Error::InvalidPair` if `val` doesn't match `output`
     pub fn xdr_to_json(&self, val: &ScVal, output: &ScType) -> Result<Value, Error> {
         Ok(match (val, output) {
             (ScVal::Void, ScType::Val | ScType::Option(_) | ScType::Tuple(_))
@@ -531,18 +631,26 @@ impl Spec {
                 | ScVal::LedgerKeyNonce(_),
                 _,
             )
-            | (ScVal::Address(_), ScType::Address)
-            | (ScVal::Bytes(_), ScType::Bytes | ScType::BytesN(_)) => to_json(val)?,
+            | (ScVal::Address(_), ScType::Address) => to_json(val)?,
+            (ScVal::Bytes(bytes), ScType::Bytes | ScType::BytesN(_) | ScType::Address) => {
+                bytes_to_json(bytes.as_slice(), output)
+            }
 
-            (val, ScType::Result(inner)) => self.xdr_to_json(val, &inner.ok_type)?,
+            (ScVal::Error(_), ScType::Result(inner)) => {
+                json!({ "err": self.xdr_to_json(val, &inner.error_type)? })
+            }
+            (val, ScType::Result(inner)) => json!({ "ok": self.xdr_to_json(val, &inner.ok_type)? }),
 
             (val, ScType::Option(inner)) => self.xdr_to_json(val, &inner.value_type)?,
             (ScVal::Map(Some(_)) | ScVal::Vec(Some(_)) | ScVal::U32(_), type_) => {
                 self.sc_object_to_json(val, type_)?
             }
 
-            (ScVal::Error(_), ScType::Error) => todo!(),
-            (v, typed) => todo!("{v:#?} doesn't have a matching {typed:#?}"),
+            (ScVal::Error(_), ScType::Error) => to_json(val)?,
+            (ScVal::Error(_), ScType::Udt(ScSpecTypeUdt { name })) => {
+                self.udt_to_json(name, val)?
+            }
+            (v, typed) => return Err(Error::InvalidPair(v.clone(), typed.clone())),
         })
     }
 
@@ -580,14 +688,9 @@ impl Spec {
 
     /// # Errors
     ///
-    /// Might return an error
-    ///
-    /// # Panics
-    ///
-    /// May panic
+    /// Might return an error, e.g. `Error::InvalidPair` if `sc_obj` doesn't match the type
     pub fn udt_to_json(&self, name: &StringM<60>, sc_obj: &ScVal) -> Result<Value, Error> {
-        let name = &name.to_utf8_string_lossy();
-        let udt = self.find(name)?;
+        let udt = self.find(&name.to_utf8_string_lossy())?;
         Ok(match (sc_obj, udt) {
             (ScVal::Map(Some(map)), ScSpecEntry::UdtStructV0(strukt)) => serde_json::Value::Object(
                 strukt
@@ -611,10 +714,10 @@ impl Spec {
             (ScVal::Vec(Some(vec_)), ScSpecEntry::UdtUnionV0(union)) => {
                 let v = vec_.to_vec();
                 // let val = &v[0];
-                let (first, rest) = match v.split_at(1) {
-                    ([first], []) => (first, None),
-                    ([first], rest) => (first, Some(rest)),
-                    _ => return Err(Error::IllFormedEnum(union.name.to_utf8_string_lossy())),
+                let (first, rest) = match v.split_first() {
+                    Some((first, [])) => (first, None),
+                    Some((first, rest)) => (first, Some(rest)),
+                    None => return Err(Error::IllFormedEnum(union.name.to_utf8_string_lossy())),
                 };
 
                 let ScVal::Symbol(case_name) = first else {
@@ -658,20 +761,30 @@ impl Spec {
                     ScSpecUdtUnionCaseV0::VoidV0(_) => Value::String(case_name),
                 }
             }
-            (ScVal::U32(v), ScSpecEntry::UdtEnumV0(_enum_)) => {
-                Value::Number(serde_json::Number::from(*v))
+            (ScVal::U32(v), ScSpecEntry::UdtEnumV0(enum_)) => {
+                enum_.cases.iter().find(|c| c.value == *v).map_or_else(
+                    || Value::Number(serde_json::Number::from(*v)),
+                    |c| Value::String(c.name.to_utf8_string_lossy()),
+                )
+            }
+            (ScVal::Error(ScError::Contract(v)), ScSpecEntry::UdtErrorEnumV0(enum_)) => {
+                enum_.cases.iter().find(|c| c.value == *v).map_or_else(
+                    || Value::Number(serde_json::Number::from(*v)),
+                    |c| Value::String(c.name.to_utf8_string_lossy()),
+                )
+            }
+            (v, _) => {
+                return Err(Error::InvalidPair(
+                    v.clone(),
+                    ScType::Udt(ScSpecTypeUdt { name: name.clone() }),
+                ))
             }
-            (s, v) => todo!("Not implemented for {s:#?} {v:#?}"),
         })
     }
 
     /// # Errors
     ///
-    /// Might return an error
-    ///
-    /// # Panics
-    ///
-    /// Some types are not yet supported and will cause a panic if supplied
+    /// Might return an error, e.g. `Error::InvalidPair` for types that aren't supported yet
     pub fn sc_object_to_json(&self, val: &ScVal, spec_type: &ScType) -> Result<Value, Error> {
         Ok(match (val, spec_type) {
             (ScVal::Vec(Some(ScVec(vec_m))), ScType::Vec(type_)) => {
@@ -712,20 +825,15 @@ impl Spec {
                 Value::String(v.to_string())
             }
 
-            (ScVal::Bytes(v), ScType::Bytes | ScType::BytesN(_)) => {
-                Value::String(to_lower_hex(v.as_slice()))
+            (ScVal::Bytes(v), ScType::Bytes | ScType::BytesN(_) | ScType::Address) => {
+                bytes_to_json(v.as_slice(), spec_type)
             }
 
-            (ScVal::Bytes(_), ScType::Udt(_)) => todo!(),
-
-            (ScVal::ContractInstance(_), _) => todo!(),
+            (ScVal::ContractInstance(_), _) => to_json(val)?,
 
             (ScVal::Address(v), ScType::Address) => sc_address_to_json(v),
 
-            (ok_val, ScType::Result(result_type)) => {
-                let ScSpecTypeResult { ok_type, .. } = result_type.as_ref();
-                self.xdr_to_json(ok_val, ok_type)?
-            }
+            (val, ScType::Result(_)) => self.xdr_to_json(val, spec_type)?,
 
             (x, y) => return Err(Error::InvalidPair(x.clone(), y.clone())),
         })
@@ -752,6 +860,20 @@ fn parse_const_enum(num: &serde_json::Number, enum_: &ScSpecUdtEnumV0) -> Result
         .map(|c| ScVal::U32(c.value))
 }
 
+/// Turn a decimal string such as `-1.5` into the string of an integer scaled by `10^decimals`,
+/// refusing to drop any fractional digits.
+fn scale_decimal(s: &str, decimals: u32, t: &ScType) -> Result<String, Error> {
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    if !frac.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::InvalidValue(Some(t.clone())));
+    }
+    let width = decimals as usize;
+    if frac.len() > width {
+        return Err(Error::TooManyDecimals(s.to_string(), decimals));
+    }
+    Ok(format!("{int}{frac:0<width$}"))
+}
+
 /// # Errors
 ///
 /// Might return an error
@@ -913,6 +1035,8 @@ pub fn to_string(v: &ScVal) -> Result<String, Error> {
     })
 }
 
+/// Maps are output with their entries in the order of the `ScMap`, which is sorted by key.
+///
 /// # Errors
 ///
 /// Might return an error
@@ -953,6 +1077,7 @@ pub fn to_json(v: &ScVal) -> Result<Value, Error> {
         ScVal::Map(None) => Value::Object(serde_json::Map::with_capacity(0)),
         ScVal::Map(Some(v)) => {
             // TODO: What do we do if the key is not a string?
+            // Relies on serde_json's preserve_order feature to keep the ScMap's order
             let mut m = serde_json::Map::<String, Value>::with_capacity(v.len());
             for ScMapEntry { key, val } in v.iter() {
                 let k: String = to_string(key)?;
@@ -1034,7 +1159,7 @@ pub fn to_json(v: &ScVal) -> Result<Value, Error> {
         ScVal::LedgerKeyNonce(ScNonceKey { nonce }) => {
             Value::Number(serde_json::Number::from(*nonce))
         }
-        ScVal::Error(e) => serde_json::to_value(e)?,
+        ScVal::Error(e) => sc_error_to_json(e),
     };
     Ok(val)
 }
@@ -1048,6 +1173,55 @@ fn sc_address_to_json(v: &ScAddress) -> Value {
     }
 }
 
+/// Output an error as its type and code, e.g. `{"type":"Contract","code":1}` or
+/// `{"type":"Budget","code":"ExceededLimit"}`.
+fn sc_error_to_json(e: &ScError) -> Value {
+    match e {
+        ScError::Contract(code) => json!({ "type": e.name(), "code": code }),
+        ScError::WasmVm(code)
+        | ScError::Context(code)
+        | ScError::Storage(code)
+        | ScError::Object(code)
+        | ScError::Crypto(code)
+        | ScError::Events(code)
+        | ScError::Budget(code)
+        | ScError::Value(code)
+        | ScError::Auth(code) => json!({ "type": e.name(), "code": code.name() }),
+    }
+}
+
+fn sc_error_from_json(map: &serde_json::Map<String, Value>) -> Result<ScError, Error> {
+    let invalid = || Error::InvalidValue(Some(ScType::Error));
+    let type_ = map
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or_else(invalid)?;
+    let code = map.get("code").ok_or_else(invalid)?;
+    if type_ == "Contract" {
+        let code = code
+            .as_u64()
+            .and_then(|c| u32::try_from(c).ok())
+            .ok_or_else(invalid)?;
+        return Ok(ScError::Contract(code));
+    }
+    let code = *ScErrorCode::VARIANTS
+        .iter()
+        .find(|c| Some(c.name()) == code.as_str())
+        .ok_or_else(invalid)?;
+    Ok(match type_ {
+        "WasmVm" => ScError::WasmVm(code),
+        "Context" => ScError::Context(code),
+        "Storage" => ScError::Storage(code),
+        "Object" => ScError::Object(code),
+        "Crypto" => ScError::Crypto(code),
+        "Events" => ScError::Events(code),
+        "Budget" => ScError::Budget(code),
+        "Value" => ScError::Value(code),
+        "Auth" => ScError::Auth(code),
+        _ => return Err(invalid()),
+    })
+}
+
 fn sc_address_from_json(s: &str) -> Result<ScVal, Error> {
     stellar_strkey::Strkey::from_string(s)
         .map_err(|_| Error::InvalidValue(Some(ScType::Address)))
@@ -1063,6 +1237,16 @@ fn sc_address_from_json(s: &str) -> Result<ScVal, Error> {
         .ok_or(Error::InvalidValue(Some(ScType::Address)))
 }
 
+/// Output bytes as hex, unless the spec types them as an address. 32 bytes typed as an address
+/// are a contract id, which earlier SDKs represented as `BytesN<32>`, and are output as a
+/// contract strkey (`C...`).
+fn bytes_to_json(bytes: &[u8], type_: &ScType) -> Value {
+    match (type_, <[u8; 32]>::try_from(bytes)) {
+        (ScType::Address, Ok(id)) => Value::String(stellar_strkey::Contract(id).to_string()),
+        _ => Value::String(to_lower_hex(bytes)),
+    }
+}
+
 fn to_lower_hex(bytes: &[u8]) -> String {
     let mut res = String::with_capacity(bytes.len());
     for b in bytes {
@@ -1361,7 +1545,39 @@ impl Spec {
 mod tests {
     use super::*;
 
-    use stellar_xdr::curr::ScSpecTypeBytesN;
+    use stellar_xdr::curr::{ScSpecTypeBytesN, ScSpecUdtEnumCaseV0};
+
+    #[test]
+    fn xdr_to_json_errors_on_mismatched_values() {
+        let spec = Spec::new(vec![ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "Kind".try_into().unwrap(),
+            cases: VecM::default(),
+        })]);
+        assert!(matches!(
+            spec.xdr_to_json(&ScVal::Bool(true), &ScType::U32),
+            Err(Error::InvalidPair(..))
+        ));
+        assert!(matches!(
+            spec.xdr_to_json(
+                &ScVal::Bytes(ScBytes::default()),
+                &ScType::Udt(ScSpecTypeUdt {
+                    name: "Kind".try_into().unwrap()
+                })
+            ),
+            Err(Error::InvalidPair(..))
+        ));
+        assert!(matches!(
+            spec.xdr_to_json(
+                &ScVal::Vec(Some(ScVec::default())),
+                &ScType::Udt(ScSpecTypeUdt {
+                    name: "Kind".try_into().unwrap()
+                })
+            ),
+            Err(Error::IllFormedEnum(_))
+        ));
+    }
 
     #[test]
     fn from_json_primitives_bytesn() {
@@ -1410,6 +1626,129 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_json_decimals() {
+        let spec = Spec::default();
+        let parse = |s: &str, t: &ScType| {
+            spec.from_json_with_decimals(&Value::String(s.to_string()), t, Some(7))
+        };
+        assert_eq!(
+            parse("1.5", &ScType::I128).unwrap(),
+            ScVal::from(15_000_000i128)
+        );
+        assert_eq!(
+            parse("-1.5", &ScType::I128).unwrap(),
+            ScVal::from(-15_000_000i128)
+        );
+        assert_eq!(
+            parse("0.0000001", &ScType::U128).unwrap(),
+            ScVal::from(1u128)
+        );
+        // Integers are scaled too, whether given as strings or as JSON numbers
+        assert_eq!(
+            parse("15", &ScType::I128).unwrap(),
+            ScVal::from(150_000_000i128)
+        );
+        let number = |v: Value| spec.from_json_with_decimals(&v, &ScType::U128, Some(7));
+        assert_eq!(
+            number(serde_json::json!(2)).unwrap(),
+            ScVal::from(20_000_000u128)
+        );
+        assert_eq!(
+            number(serde_json::json!(1.5)).unwrap(),
+            ScVal::from(15_000_000u128)
+        );
+        assert!(matches!(
+            parse("1.5.0", &ScType::I128),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            parse("-1.5", &ScType::U128),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            parse("1.00000001", &ScType::I128),
+            Err(Error::TooManyDecimals(_, 7))
+        ));
+    }
+
+    #[test]
+    fn from_json_decimals_requires_scale() {
+        assert!(matches!(
+            Spec::default().from_json(&Value::String("1.5".to_string()), &ScType::I128),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn const_enum_to_json_uses_case_name() {
+        let spec = Spec::new(vec![ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "Card".try_into().unwrap(),
+            cases: vec![ScSpecUdtEnumCaseV0 {
+                doc: StringM::default(),
+                name: "Jack".try_into().unwrap(),
+                value: 11,
+            }]
+            .try_into()
+            .unwrap(),
+        })]);
+        let card = ScType::Udt(ScSpecTypeUdt {
+            name: "Card".try_into().unwrap(),
+        });
+        assert_eq!(
+            spec.xdr_to_json(&ScVal::U32(11), &card).unwrap(),
+            json!("Jack")
+        );
+        // Values without a matching case are still shown
+        assert_eq!(spec.xdr_to_json(&ScVal::U32(12), &card).unwrap(), json!(12));
+        // Names are accepted back as input
+        assert_eq!(
+            spec.from_json(&json!("Jack"), &card).unwrap(),
+            ScVal::U32(11)
+        );
+        assert_eq!(spec.from_json(&json!(11), &card).unwrap(), ScVal::U32(11));
+    }
+
+    #[test]
+    fn result_round_trip() {
+        let spec = Spec::new(vec![ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "Error".try_into().unwrap(),
+            cases: vec![ScSpecUdtErrorEnumCaseV0 {
+                doc: StringM::default(),
+                name: "NumberMustBeOdd".try_into().unwrap(),
+                value: 1,
+            }]
+            .try_into()
+            .unwrap(),
+        })]);
+        let result = ScType::Result(Box::new(ScSpecTypeResult {
+            ok_type: Box::new(ScType::U32),
+            error_type: Box::new(ScType::Udt(ScSpecTypeUdt {
+                name: "Error".try_into().unwrap(),
+            })),
+        }));
+        let ok = ScVal::U32(3);
+        let err = ScVal::Error(ScError::Contract(1));
+        assert_eq!(spec.xdr_to_json(&ok, &result).unwrap(), json!({ "ok": 3 }));
+        assert_eq!(
+            spec.xdr_to_json(&err, &result).unwrap(),
+            json!({ "err": "NumberMustBeOdd" })
+        );
+        assert_eq!(spec.from_json(&json!({ "ok": 3 }), &result).unwrap(), ok);
+        assert_eq!(
+            spec.from_json(&json!({ "err": "NumberMustBeOdd" }), &result)
+                .unwrap(),
+            err
+        );
+        assert_eq!(spec.from_json(&json!({ "err": 1 }), &result).unwrap(), err);
+        // A plain value is the ok value
+        assert_eq!(spec.from_json(&json!(3), &result).unwrap(), ok);
+    }
+
     #[test]
     fn test_sc_address_from_json_strkey() {
         // All zero contract address
@@ -1463,4 +1802,92 @@ mod tests {
             Err(e) => panic!("Unexpected error: {e}"),
         }
     }
+
+    #[test]
+    fn error_round_trip() {
+        let spec = Spec::default();
+        for (error, json) in [
+            (
+                ScError::Contract(3),
+                json!({ "type": "Contract", "code": 3 }),
+            ),
+            (
+                ScError::WasmVm(ScErrorCode::InvalidAction),
+                json!({ "type": "WasmVm", "code": "InvalidAction" }),
+            ),
+            (
+                ScError::Budget(ScErrorCode::ExceededLimit),
+                json!({ "type": "Budget", "code": "ExceededLimit" }),
+            ),
+        ] {
+            let val = ScVal::Error(error);
+            assert_eq!(spec.xdr_to_json(&val, &ScType::Error).unwrap(), json);
+            assert_eq!(spec.from_json(&json, &ScType::Error).unwrap(), val);
+        }
+        assert!(spec
+            .from_json(&json!({ "type": "Budget", "code": 3 }), &ScType::Error)
+            .is_err());
+    }
+
+    #[test]
+    fn address_typed_bytes_as_strkey() {
+        let spec = Spec::default();
+        let bytes = ScVal::Bytes(ScBytes(vec![0; 32].try_into().unwrap()));
+        let strkey = json!("CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4");
+        let hex = json!("00".repeat(32));
+        assert_eq!(spec.xdr_to_json(&bytes, &ScType::Address).unwrap(), strkey);
+        assert_eq!(
+            spec.sc_object_to_json(&bytes, &ScType::Address).unwrap(),
+            strkey
+        );
+        assert_eq!(
+            spec.xdr_to_json(&bytes, &ScType::BytesN(ScSpecTypeBytesN { n: 32 }))
+                .unwrap(),
+            hex
+        );
+        // Without a spec type there's nothing to say the bytes are an address
+        assert_eq!(to_json(&bytes).unwrap(), hex);
+    }
+
+    #[test]
+    fn map_keeps_sc_map_order() {
+        let map = ScVal::Map(Some(
+            ScMap::sorted_from(
+                [10, 2, 9]
+                    .map(|k| (ScVal::U32(k), ScVal::Bool(true)))
+                    .to_vec(),
+            )
+            .unwrap(),
+        ));
+        assert_eq!(
+            serde_json::to_string(&to_json(&map).unwrap()).unwrap(),
+            r#"{"2":true,"9":true,"10":true}"#
+        );
+    }
+
+    #[test]
+    fn contract_instance_as_json() {
+        let spec = Spec::default();
+        let wasm = ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::Wasm(Hash([0xab; 32])),
+            storage: None,
+        });
+        assert_eq!(
+            spec.sc_object_to_json(&wasm, &ScType::Val).unwrap(),
+            json!({ "hash": "ab".repeat(32) })
+        );
+        assert_eq!(
+            spec.xdr_to_json(&wasm, &ScType::Val).unwrap(),
+            to_json(&wasm).unwrap()
+        );
+
+        let asset = ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::StellarAsset,
+            storage: None,
+        });
+        assert_eq!(
+            spec.sc_object_to_json(&asset, &ScType::Val).unwrap(),
+            json!({ "SAC": true })
+        );
+    }
 }
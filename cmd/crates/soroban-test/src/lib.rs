@@ -225,13 +225,15 @@ impl TestEnv {
                 rpc_url: Some(self.rpc_url.clone()),
                 network_passphrase: Some(LOCAL_NETWORK_PASSPHRASE.to_string()),
                 network: None,
+                ..network::Args::default()
             },
-            source_account: account.to_string(),
+            source_account: Some(account.to_string()),
             locator: config::locator::Args {
                 global: false,
                 config_dir,
             },
             hd_path: None,
+            ..config::Args::default()
         }
     }
 
@@ -247,10 +249,12 @@ impl TestEnv {
                 locator: config.locator.clone(),
                 filter_logs: Vec::default(),
                 quiet: false,
-                verbose: false,
+                verbose: 0,
                 very_verbose: false,
                 list: false,
                 no_cache: false,
+                refresh_cache: false,
+                env_file: None,
             }),
             Some(&config),
         )
@@ -265,6 +269,7 @@ impl TestEnv {
     /// Returns the public key corresponding to the test keys's `hd_path`
     pub fn test_address(&self, hd_path: usize) -> String {
         self.cmd::<keys::address::Cmd>(&format!("--hd-path={hd_path}"))
+            .address
             .public_key()
             .unwrap()
             .to_string()
@@ -1,9 +1,13 @@
 use assert_fs::TempDir;
+use predicates::prelude::PredicateBooleanExt;
 use soroban_test::{AssertExt, TestEnv};
-use std::{fs, path::Path};
+use std::{fmt::Write, fs, path::Path};
 
 use crate::util::{add_key, add_test_id, SecretKind, DEFAULT_SEED_PHRASE};
-use soroban_cli::commands::network::{self, LOCAL_NETWORK_PASSPHRASE};
+use soroban_cli::commands::{
+    events,
+    network::{self, LOCAL_NETWORK_PASSPHRASE},
+};
 
 fn ls(sandbox: &TestEnv) -> Vec<String> {
     sandbox
@@ -42,6 +46,57 @@ fn set_and_remove_network() {
     });
 }
 
+#[test]
+fn list_networks_as_json() {
+    let sandbox = TestEnv::default();
+    add_network(&sandbox, "local");
+    let output = sandbox
+        .new_assert_cmd("network")
+        .args(["ls", "--format", "json"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let networks: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        networks,
+        serde_json::json!([{
+            "name": "local",
+            "scope": "local",
+            "shadowed": false,
+            "rpc_url": "https://127.0.0.1",
+            "network_passphrase": LOCAL_NETWORK_PASSPHRASE,
+        }])
+    );
+}
+
+#[test]
+fn ping_unreachable_network_fails() {
+    TestEnv::with_default(|sandbox| {
+        sandbox
+            .new_assert_cmd("network")
+            .args([
+                "ping",
+                "--rpc-url=http://127.0.0.1:1",
+                "--network-passphrase",
+                LOCAL_NETWORK_PASSPHRASE,
+            ])
+            .assert()
+            .failure();
+    });
+}
+
+#[test]
+fn auto_passphrase_requires_reachable_rpc() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("network")
+        .env_remove("SOROBAN_NETWORK_PASSPHRASE")
+        .env_remove("STELLAR_NETWORK_PASSPHRASE")
+        .args(["ping", "--rpc-url=http://127.0.0.1:1", "--auto-passphrase"])
+        .assert()
+        .failure();
+}
+
 fn add_network(sandbox: &TestEnv, name: &str) {
     sandbox
         .new_assert_cmd("network")
@@ -105,6 +160,31 @@ fn set_and_remove_global_network() {
         .stdout("\n");
 }
 
+#[test]
+fn local_network_shadows_global() {
+    let sandbox = TestEnv::default();
+    let dir = TempDir::new().unwrap();
+    add_network_global(&sandbox, &dir, "local");
+    sandbox
+        .new_assert_cmd("network")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .args([
+            "add",
+            "--rpc-url=https://127.0.0.1",
+            "--network-passphrase",
+            LOCAL_NETWORK_PASSPHRASE,
+            "local",
+        ])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("network")
+        .env("XDG_CONFIG_HOME", dir.to_str().unwrap())
+        .arg("ls")
+        .assert()
+        .stdout("local\nlocal (global, shadowed by local)\n");
+}
+
 #[test]
 fn multiple_networks() {
     let sandbox = TestEnv::default();
@@ -139,6 +219,89 @@ fn multiple_networks() {
     assert_eq!(ls().as_slice(), ["local2".to_owned(), "local3".to_owned()]);
 }
 
+#[test]
+fn events_resolves_rpc_url_from_network_config() {
+    let sandbox = TestEnv::default();
+    add_network(&sandbox, "testnet");
+    let cmd = sandbox.cmd_arr::<events::Cmd>(&["--start-ledger", "1", "--network", "testnet"]);
+    let network = cmd.network(None).unwrap();
+    assert_eq!(network.rpc_url, "https://127.0.0.1");
+    assert_eq!(network.network_passphrase, LOCAL_NETWORK_PASSPHRASE);
+}
+
+#[test]
+fn set_and_use_default_network() {
+    let sandbox = TestEnv::default();
+    add_network(&sandbox, "local");
+    sandbox
+        .new_assert_cmd("network")
+        .args(["default", "local"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("network")
+        .arg("default")
+        .assert()
+        .success()
+        .stdout("local\n");
+    let cmd = sandbox.cmd_arr::<events::Cmd>(&["--start-ledger", "1"]);
+    let network = cmd.network(None).unwrap();
+    assert_eq!(network.rpc_url, "https://127.0.0.1");
+}
+
+#[test]
+fn default_network_must_exist() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("network")
+        .args(["default", "missing"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn exit_codes_distinguish_failures() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("network")
+        .args(["default", "missing"])
+        .assert()
+        .code(2);
+    sandbox
+        .new_assert_cmd("network")
+        .args([
+            "ping",
+            "--rpc-url=http://127.0.0.1:1",
+            "--network-passphrase",
+            LOCAL_NETWORK_PASSPHRASE,
+        ])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn env_file_overrides_environment() {
+    let sandbox = TestEnv::default();
+    let env_file = sandbox.dir().join("test.env");
+    fs::write(&env_file, "SOROBAN_RPC_URL=http://[invalid\n").unwrap();
+    sandbox
+        .new_assert_cmd("events")
+        .arg("--env-file")
+        .arg(&env_file)
+        .args(["--start-ledger", "1"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid rpc url"));
+    sandbox
+        .new_assert_cmd("events")
+        .arg("--env-file")
+        .arg(&env_file)
+        .args(["--start-ledger", "1", "--rpc-url", "http://127.0.0.1:1"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid rpc url").not());
+}
+
 #[test]
 fn read_key() {
     let sandbox = TestEnv::default();
@@ -153,6 +316,52 @@ fn read_key() {
         .stdout(predicates::str::contains("test_id\n"));
 }
 
+#[test]
+fn ls_show_address_lines_up() {
+    let sandbox = TestEnv::default();
+    let dir = sandbox.dir().as_ref();
+    add_test_id(dir);
+    add_key(dir, "seed", SecretKind::Seed, DEFAULT_SEED_PHRASE);
+    let out = sandbox
+        .new_assert_cmd("keys")
+        .args(["ls", "--show-address"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    for line in lines {
+        let address = &line["test_id".len() + 2..];
+        assert!(address.starts_with('G') && address.len() == 56, "{line}");
+    }
+}
+
+#[test]
+fn rename_key() {
+    let sandbox = TestEnv::default();
+    let dir = sandbox.dir().as_ref();
+    add_test_id(dir);
+    let ident_dir = dir.join(".soroban/identity");
+    let contents = fs::read_to_string(ident_dir.join("test_id.toml")).unwrap();
+    add_key(dir, "other", SecretKind::Seed, DEFAULT_SEED_PHRASE);
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["rename", "test_id", "other"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("already exists"));
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["rename", "test_id", "renamed"])
+        .assert()
+        .success();
+    assert!(!ident_dir.join("test_id.toml").exists());
+    assert_eq!(
+        fs::read_to_string(ident_dir.join("renamed.toml")).unwrap(),
+        contents
+    );
+}
+
 #[test]
 fn generate_key() {
     let sandbox = TestEnv::default();
@@ -257,3 +466,43 @@ fn use_env() {
         .success()
         .stdout("SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD\n");
 }
+
+#[test]
+fn show_public_key_and_raw_seed() {
+    let sandbox = TestEnv::default();
+    let secret_key = "SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD";
+    sandbox
+        .new_assert_cmd("keys")
+        .env("SOROBAN_SECRET_KEY", secret_key)
+        .arg("add")
+        .arg("bob")
+        .assert()
+        .success();
+    let address = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "bob"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["show", "bob", "--public"])
+        .assert()
+        .success()
+        .stdout(format!("{address}\n"));
+    let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret_key)
+        .unwrap()
+        .0;
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["show", "bob", "--raw-seed"])
+        .assert()
+        .success()
+        .stdout(format!(
+            "{}\n",
+            seed.iter().fold(String::new(), |mut hex, b| {
+                write!(hex, "{b:02x}").unwrap();
+                hex
+            })
+        ));
+}
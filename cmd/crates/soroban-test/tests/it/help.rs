@@ -59,6 +59,50 @@ async fn multi_arg_failure() {
     ));
 }
 
+#[tokio::test]
+async fn impersonate_requires_simulation() {
+    let e = &TestEnv::default();
+    let cmd: contract::invoke::Cmd = e.cmd_with_config(&[
+        "--id",
+        DEFAULT_CONTRACT_ID,
+        "--impersonate",
+        "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+        "--",
+        "multi_args",
+    ]);
+    assert!(matches!(
+        e.run_cmd_with(cmd, "test").await.unwrap_err(),
+        contract::invoke::Error::ImpersonateWithoutSimulation
+    ));
+}
+
+#[tokio::test]
+async fn args_file_failures() {
+    let e = &TestEnv::default();
+    let invoke_with_args_file = |contents: &str| {
+        let path = e.dir().join("args.json");
+        std::fs::write(&path, contents).unwrap();
+        let mut cmd: contract::invoke::Cmd = e.cmd_with_config(&[
+            "--id",
+            DEFAULT_CONTRACT_ID,
+            "--args-file",
+            path.to_str().unwrap(),
+            "--",
+            "multi_args",
+        ]);
+        cmd.wasm = Some(CUSTOM_TYPES.path());
+        e.run_cmd_with(cmd, "test")
+    };
+    assert!(matches!(
+        invoke_with_args_file("[1, 2]").await.unwrap_err(),
+        contract::invoke::Error::InvalidArgsFile(_)
+    ));
+    assert!(matches!(
+        invoke_with_args_file(r#"{ "b": true }"#).await.unwrap_err(),
+        contract::invoke::Error::MissingArgument(missing) if missing == "a"
+    ));
+}
+
 #[tokio::test]
 async fn handle_arg_larger_than_i32_failure() {
     let res = invoke_custom("i32_", &format!("--i32_={}", u32::MAX)).await;
@@ -77,6 +121,18 @@ async fn handle_arg_larger_than_i64_failure() {
     ));
 }
 
+#[tokio::test]
+async fn invalid_xdr_arg_failure() {
+    let res = invoke_custom("i32_", "--i32_-xdr=not-xdr").await;
+    assert!(matches!(
+        res,
+        Err(contract::invoke::Error::CannotParseArg {
+            error: soroban_spec_tools::Error::Xdr(_),
+            ..
+        })
+    ));
+}
+
 #[test]
 fn build() {
     let sandbox = TestEnv::default();
@@ -2,6 +2,7 @@ mod bindings;
 mod custom_types;
 mod dotenv;
 mod hello_world;
+mod install;
 mod tx;
 mod util;
 mod wrap;
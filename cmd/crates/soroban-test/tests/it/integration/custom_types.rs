@@ -25,6 +25,7 @@ async fn parse() {
     bytes_as_file(sandbox, id);
     map(sandbox, id).await;
     vec_(sandbox, id).await;
+    vec_as_list(sandbox, id);
     tuple(sandbox, id).await;
     strukt(sandbox, id).await;
     tuple_strukt(sandbox, id).await;
@@ -112,6 +113,14 @@ async fn vec_(sandbox: &TestEnv, id: &str) {
     invoke_with_roundtrip(sandbox, id, "vec", json!([0, 1])).await;
 }
 
+fn vec_as_list(sandbox: &TestEnv, id: &str) {
+    invoke_custom(sandbox, id, "vec")
+        .args(["--vec", "0,1", "--vec", "2"])
+        .assert()
+        .success()
+        .stdout("[0,1,2]\n");
+}
+
 async fn tuple(sandbox: &TestEnv, id: &str) {
     invoke_with_roundtrip(sandbox, id, "tuple", json!(["hello", 0])).await;
 }
@@ -182,7 +191,7 @@ fn number_arg_return_ok(sandbox: &TestEnv, id: &str) {
         .arg("1")
         .assert()
         .success()
-        .stdout("1\n");
+        .stdout("{\"ok\":1}\n");
 }
 
 async fn number_arg_return_err(sandbox: &TestEnv, id: &str) {
@@ -254,7 +263,12 @@ async fn bytes(sandbox: &TestEnv, id: &str) {
 }
 
 async fn const_enum(sandbox: &TestEnv, id: &str) {
-    invoke_with_roundtrip(sandbox, id, "card", "11").await;
+    invoke_with_roundtrip(sandbox, id, "card", json!("Jack")).await;
+    let res = sandbox
+        .invoke_with_test(&["--id", id, "--", "card", "--card", "11"])
+        .await
+        .unwrap();
+    assert_eq!(res, json!("Jack").to_string());
 }
 
 fn parse_u128(sandbox: &TestEnv, id: &str) {
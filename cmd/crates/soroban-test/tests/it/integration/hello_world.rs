@@ -1,10 +1,15 @@
 use predicates::boolean::PredicateBooleanExt;
-use soroban_cli::commands::{
-    config::{locator, secret},
-    contract::{self, fetch},
-    txn_result::TxnResult,
+use soroban_cli::{
+    client,
+    commands::{
+        config::{locator, secret},
+        contract::{self, fetch},
+        txn_result::TxnResult,
+    },
+    SorobanClient,
 };
 use soroban_rpc::GetLatestLedgerResponse;
+use soroban_sdk::xdr::{Limits, ReadXdr, ScVal};
 use soroban_test::{AssertExt, TestEnv, LOCAL_NETWORK_PASSPHRASE};
 
 use crate::integration::util::extend_contract;
@@ -18,7 +23,7 @@ async fn invoke_view_with_non_existent_source_account() {
     let id = deploy_hello(sandbox).await;
     let world = "world";
     let mut cmd = hello_world_cmd(&id, world);
-    cmd.config.source_account = String::new();
+    cmd.config.source_account = None;
     cmd.is_view = true;
     let res = sandbox.run_cmd_with(cmd, "test").await.unwrap();
     assert_eq!(res, TxnResult::Res(format!(r#"["Hello",{world:?}]"#)));
@@ -88,6 +93,9 @@ async fn invoke() {
     ulid::Ulid::from_string(&uid).expect("invalid ulid");
     // Note that all functions tested here have no state
     invoke_hello_world(sandbox, id);
+    invoke_hello_world_dry_run(sandbox, id);
+    invoke_hello_world_passphrase_mismatch(sandbox, id);
+    invoke_hello_world_output_formats(sandbox, id);
 
     sandbox
         .new_assert_cmd("events")
@@ -99,6 +107,7 @@ async fn invoke() {
         .stdout(predicates::str::contains(id).not())
         .success();
     invoke_hello_world_with_lib(sandbox, id).await;
+    invoke_hello_world_with_client(sandbox, id).await;
     let config_locator = locator::Args {
         global: false,
         config_dir: Some(dir.to_path_buf()),
@@ -129,6 +138,7 @@ async fn invoke() {
     invoke_auth_with_identity(sandbox, id, "test", &addr);
     invoke_auth_with_identity(sandbox, id, "testone", &addr_1);
     invoke_auth_with_different_test_account_fail(sandbox, id, &addr_1).await;
+    invoke_auth_with_different_test_account_warns(sandbox, id, &addr_1);
     // invoke_auth_with_different_test_account(sandbox, id);
     contract_data_read_failure(sandbox, id);
     invoke_with_seed(sandbox, id, &seed_phrase).await;
@@ -155,6 +165,67 @@ fn invoke_hello_world(sandbox: &TestEnv, id: &str) {
         .success();
 }
 
+fn invoke_hello_world_dry_run(sandbox: &TestEnv, id: &str) {
+    let summary = sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--dry-run")
+        .arg("--id")
+        .arg(id)
+        .arg("--")
+        .arg("hello")
+        .arg("--world=world")
+        .assert()
+        .success()
+        .stdout_as_str();
+    let summary: serde_json::Value = serde_json::from_str(&summary).unwrap();
+    assert_eq!(
+        summary["return_value"],
+        serde_json::json!(["Hello", "world"])
+    );
+    assert!(summary["cost"]["cpu_instructions"].as_u64().unwrap() > 0);
+    assert!(summary["footprint"]["read_only"].is_array());
+}
+
+fn invoke_hello_world_passphrase_mismatch(sandbox: &TestEnv, id: &str) {
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--network-passphrase")
+        .arg("Some Other Network")
+        .arg("--id")
+        .arg(id)
+        .arg("--")
+        .arg("hello")
+        .arg("--world=world")
+        .assert()
+        .stderr(predicates::str::contains("does not match"))
+        .failure();
+}
+
+fn invoke_hello_world_output_formats(sandbox: &TestEnv, id: &str) {
+    let invoke = |output: &str| {
+        sandbox
+            .new_assert_cmd("contract")
+            .arg("invoke")
+            .arg("--is-view")
+            .arg("--output")
+            .arg(output)
+            .arg("--id")
+            .arg(id)
+            .arg("--")
+            .arg("hello")
+            .arg("--world=world")
+            .assert()
+            .success()
+    };
+    let xdr = invoke("xdr").stdout_as_str();
+    let val = ScVal::from_xdr_base64(xdr, Limits::none()).unwrap();
+    assert!(matches!(val, ScVal::Vec(Some(v)) if v.len() == 2));
+    invoke("json-pretty").stdout("[\n  \"Hello\",\n  \"world\"\n]\n");
+    invoke("quiet").stdout("");
+}
+
 fn hello_world_cmd(id: &str, arg: &str) -> contract::invoke::Cmd {
     contract::invoke::Cmd {
         contract_id: id.to_string(),
@@ -169,6 +240,25 @@ async fn invoke_hello_world_with_lib(e: &TestEnv, id: &str) {
     assert_eq!(res, TxnResult::Res(r#"["Hello","world"]"#.to_string()));
 }
 
+async fn invoke_hello_world_with_client(e: &TestEnv, id: &str) {
+    let client = SorobanClient {
+        config: e.clone_config("test"),
+    };
+    let res = client
+        .invoke(id, "hello", serde_json::json!({ "world": "world" }))
+        .await
+        .unwrap();
+    assert_eq!(res, serde_json::json!(["Hello", "world"]));
+    let err = client
+        .invoke(id, "goodbye", serde_json::json!({}))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        client::Error::Invoke(contract::invoke::Error::StrVal(_))
+    ));
+}
+
 fn invoke_auth(sandbox: &TestEnv, id: &str, addr: &str) {
     sandbox
         .new_assert_cmd("contract")
@@ -235,6 +325,24 @@ async fn invoke_auth_with_different_test_account_fail(sandbox: &TestEnv, id: &st
     );
 }
 
+fn invoke_auth_with_different_test_account_warns(sandbox: &TestEnv, id: &str, addr: &str) {
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("invoke")
+        .arg("--sim-only")
+        .arg("--id")
+        .arg(id)
+        .arg("--")
+        .arg("auth")
+        .arg(format!("--addr={addr}"))
+        .arg("--world=world")
+        .assert()
+        .stderr(predicates::str::contains(format!(
+            "this call requires authorization from {addr}"
+        )))
+        .success();
+}
+
 fn contract_data_read_failure(sandbox: &TestEnv, id: &str) {
     sandbox
         .new_assert_cmd("contract")
@@ -313,7 +421,7 @@ async fn half_max_instructions() {
         .arg(wasm.path())
         .arg("--ignore-checks")
         .assert()
-        .stderr("")
+        .stderr(predicates::str::starts_with("Transaction hash is "))
         .stdout_as_str();
 }
 
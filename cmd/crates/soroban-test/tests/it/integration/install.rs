@@ -0,0 +1,31 @@
+use soroban_test::{AssertExt, TestEnv};
+
+use super::util::{CUSTOM_TYPES, HELLO_WORLD};
+
+#[tokio::test]
+async fn install_multiple_wasms() {
+    let sandbox = &TestEnv::new();
+    let hello = HELLO_WORLD.path();
+    let custom = CUSTOM_TYPES.path();
+    let output = sandbox
+        .new_assert_cmd("contract")
+        .arg("install")
+        .arg("--wasm")
+        .arg(&hello)
+        .arg("--wasm")
+        .arg(&custom)
+        .arg("--output")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout_as_str();
+    let hashes: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        hashes[hello.display().to_string()],
+        HELLO_WORLD.hash().unwrap().to_string()
+    );
+    assert_eq!(
+        hashes[custom.display().to_string()],
+        CUSTOM_TYPES.hash().unwrap().to_string()
+    );
+}
@@ -1,7 +1,11 @@
-use soroban_sdk::xdr::{Limits, ReadXdr, TransactionEnvelope, WriteXdr};
-use soroban_test::{AssertExt, TestEnv};
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{
+    Hash, Limits, MuxedAccount, ReadXdr, TransactionEnvelope, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, Uint256, WriteXdr,
+};
+use soroban_test::{AssertExt, TestEnv, LOCAL_NETWORK_PASSPHRASE};
 
-use crate::integration::util::{deploy_contract, DeployKind, HELLO_WORLD};
+use crate::integration::util::{deploy_contract, deploy_hello, DeployKind, HELLO_WORLD};
 
 #[tokio::test]
 async fn txn_simulate() {
@@ -30,3 +34,110 @@ async fn txn_simulate() {
         assembled_str
     );
 }
+
+#[tokio::test]
+async fn txn_sign_hash_only() {
+    let sandbox = &TestEnv::new();
+    let xdr_base64_build_only = deploy_contract(sandbox, HELLO_WORLD, DeployKind::BuildOnly).await;
+    let tx_env =
+        TransactionEnvelope::from_xdr_base64(&xdr_base64_build_only, Limits::none()).unwrap();
+    let tx = soroban_cli::commands::tx::xdr::unwrap_envelope_v1(tx_env).unwrap();
+    let hash = sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--hash-only")
+        .write_stdin(xdr_base64_build_only.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(LOCAL_NETWORK_PASSPHRASE).into()),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(tx),
+    };
+    let expected = Sha256::digest(payload.to_xdr(Limits::none()).unwrap());
+    assert_eq!(hash, format!("{expected:x}"));
+}
+
+#[tokio::test]
+async fn txn_sign_from_input_file() {
+    let sandbox = &TestEnv::new();
+    let xdr_base64_build_only = deploy_contract(sandbox, HELLO_WORLD, DeployKind::BuildOnly).await;
+    let sign = |input_file: Option<&std::path::Path>| {
+        let mut cmd = sandbox.new_assert_cmd("tx");
+        cmd.arg("sign");
+        if let Some(path) = input_file {
+            cmd.arg("--input-file").arg(path);
+        } else {
+            cmd.write_stdin(xdr_base64_build_only.as_bytes());
+        }
+        cmd.assert().success().stdout_as_str()
+    };
+    let path = sandbox.dir().join("tx.txt");
+    std::fs::write(&path, &xdr_base64_build_only).unwrap();
+    assert_eq!(sign(Some(&path)), sign(None));
+}
+
+#[tokio::test]
+async fn txn_sign_output_file() {
+    let sandbox = &TestEnv::new();
+    let xdr_base64_build_only = deploy_contract(sandbox, HELLO_WORLD, DeployKind::BuildOnly).await;
+    let signed = sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .write_stdin(xdr_base64_build_only.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let path = sandbox.dir().join("signed.txt");
+    let sign_to_file = |overwrite: bool| {
+        let mut cmd = sandbox.new_assert_cmd("tx");
+        cmd.arg("sign").arg("--output-file").arg(&path);
+        if overwrite {
+            cmd.arg("--overwrite");
+        }
+        cmd.write_stdin(xdr_base64_build_only.as_bytes()).assert()
+    };
+    sign_to_file(false).success().stdout("");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), signed);
+    sign_to_file(false)
+        .failure()
+        .stderr(predicates::str::contains("--overwrite"));
+    sign_to_file(true).success();
+}
+
+#[tokio::test]
+async fn fee_source_pays_for_transaction() {
+    let sandbox = &TestEnv::new();
+    sandbox.generate_account("payer", None).assert().success();
+    sandbox.fund_account("payer").success();
+    let account = |name: &str| {
+        let address = sandbox
+            .new_assert_cmd("keys")
+            .args(["address", name])
+            .assert()
+            .stdout_as_str();
+        let key = stellar_strkey::ed25519::PublicKey::from_string(&address).unwrap();
+        MuxedAccount::Ed25519(Uint256(key.0))
+    };
+    let built = sandbox
+        .new_assert_cmd("contract")
+        .arg("deploy")
+        .arg("--wasm")
+        .arg(HELLO_WORLD.path())
+        .args(["--fee-source", "payer", "--build-only"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx_env = TransactionEnvelope::from_xdr_base64(&built, Limits::none()).unwrap();
+    let tx = soroban_cli::commands::tx::xdr::unwrap_envelope_v1(tx_env).unwrap();
+    assert_eq!(tx.source_account, account("payer"));
+    assert_eq!(tx.operations[0].source_account, Some(account("test")));
+
+    let id = &deploy_hello(sandbox).await;
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", id, "--fee-source", "payer", "--", "inc"])
+        .assert()
+        .success()
+        .stdout("1\n");
+}
@@ -67,8 +67,8 @@ async fn burn() {
         .assert()
         .stdout("\"9223372036854775807\"\n");
     sandbox
-        .new_assert_cmd("contract")
-        .arg("invoke")
+        .new_assert_cmd("--quiet")
+        .args(["contract", "invoke"])
         .env("RUST_LOGS", "trace")
         .args([
             "--source=test",
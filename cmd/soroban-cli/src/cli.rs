@@ -1,3 +1,5 @@
+use std::{ffi::OsString, path::PathBuf};
+
 use clap::CommandFactory;
 use dotenvy::dotenv;
 use tracing_subscriber::{fmt, EnvFilter};
@@ -6,6 +8,14 @@ use crate::{commands, Root};
 
 #[tokio::main]
 pub async fn main() {
+    // The env file has to be loaded before clap resolves `env` backed args,
+    // so it is read straight from the raw arguments.
+    if let Some(env_file) = env_file_arg(std::env::args_os()) {
+        if let Err(e) = dotenvy::from_path_override(&env_file) {
+            eprintln!("error: cannot load env file {}: {e}", env_file.display());
+            std::process::exit(crate::exit_code::USAGE);
+        }
+    }
     let _ = dotenv().unwrap_or_default();
 
     // Map SOROBAN_ env vars to STELLAR_ env vars for backwards compatibility
@@ -38,7 +48,7 @@ pub async fn main() {
         }
         e => {
             eprintln!("{e}");
-            std::process::exit(1);
+            std::process::exit(e.exit_code());
         }
     });
     // Now use root to setup the logger
@@ -54,7 +64,7 @@ pub async fn main() {
                     .parse()
                     .map_err(|e| {
                         eprintln!("{e}: {filter}");
-                        std::process::exit(1);
+                        std::process::exit(crate::exit_code::USAGE);
                     })
                     .unwrap(),
             );
@@ -72,6 +82,24 @@ pub async fn main() {
 
     if let Err(e) = root.run().await {
         eprintln!("error: {e}");
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
+    }
+}
+
+/// Find the value of `--env-file`, ignoring anything after `--` which belongs
+/// to the contract being invoked.
+fn env_file_arg(args: impl IntoIterator<Item = OsString>) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--env-file" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.to_str().and_then(|a| a.strip_prefix("--env-file=")) {
+            return Some(PathBuf::from(path));
+        }
     }
+    None
 }
@@ -0,0 +1,265 @@
+//! Invoke contracts from Rust, without going through the CLI's argument parsing.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), soroban_cli::client::Error> {
+//! let client = soroban_cli::SorobanClient::new("testnet", "alice");
+//! let greeting = client
+//!     .invoke("hello_world", "hello", serde_json::json!({ "world": "Stellar" }))
+//!     .await?;
+//! assert_eq!(greeting, serde_json::json!(["Hello", "Stellar"]));
+//! # Ok(())
+//! # }
+//! ```
+use crate::commands::{config, contract::invoke, global, network, NetworkRunnable};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("function arguments must be a JSON object mapping parameter names to values, got {0}")]
+    ArgsNotAnObject(serde_json::Value),
+    #[error(transparent)]
+    Invoke(#[from] invoke::Error),
+    #[error("parsing the return value as JSON: {0}")]
+    InvalidReturnValue(serde_json::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::ArgsNotAnObject(_) => crate::exit_code::USAGE,
+            Error::Invoke(e) => e.exit_code(),
+            Error::InvalidReturnValue(_) => crate::exit_code::FAILURE,
+        }
+    }
+}
+
+/// Invokes contracts the way `stellar contract invoke` does: simulating the call, and signing
+/// and sending it when it writes to the ledger, publishes events, or needs authorization.
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub struct SorobanClient {
+    /// Network, source account, and config directory, as given to commands with `--network`,
+    /// `--source-account`, `--config-dir`, and the other config options
+    pub config: config::Args,
+}
+
+impl SorobanClient {
+    /// A client for `network`, a network in the config directory such as one added with
+    /// `stellar network add`, that signs with `source_account`, an identity, secret key, or seed
+    /// phrase.
+    pub fn new(network: &str, source_account: &str) -> Self {
+        SorobanClient {
+            config: config::Args {
+                network: network::Args {
+                    network: Some(network.to_string()),
+                    ..network::Args::default()
+                },
+                source_account: Some(source_account.to_string()),
+                ..config::Args::default()
+            },
+        }
+    }
+
+    /// Call `function` on the contract `contract_id`, an ID or alias, with `args`, a JSON object
+    /// mapping parameter names to values as in `--args-file`. Returns the function's return
+    /// value as JSON, or null when it has none.
+    pub async fn invoke(
+        &self,
+        contract_id: &str,
+        function: &str,
+        args: serde_json::Value,
+    ) -> Result<serde_json::Value, Error> {
+        let serde_json::Value::Object(args) = args else {
+            return Err(Error::ArgsNotAnObject(args));
+        };
+        let global_args = global::Args {
+            locator: self.config.locator.clone(),
+            ..global::Args::default()
+        };
+        let cmd = invoke::Cmd {
+            contract_id: contract_id.to_string(),
+            function: Some(function.to_string()),
+            args,
+            config: self.config.clone(),
+            ..invoke::Cmd::default()
+        };
+        // Without --build-only or the like, the result is never the unsent transaction
+        let res = cmd
+            .run_against_rpc_server(Some(&global_args), None)
+            .await?
+            .into_result()
+            .unwrap_or_default();
+        if res.is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+        serde_json::from_str(&res).map_err(Error::InvalidReturnValue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::{config::data, network::LOCAL_NETWORK_PASSPHRASE},
+        utils::rpc::tests::mock_server,
+        xdr::{
+            AccountEntry, AccountEntryExt, AccountId, ContractDataEntry, ContractExecutable,
+            ExtensionPoint, HostFunction, LedgerEntryData, LedgerFootprint, LedgerKey,
+            LedgerKeyContractData, Limits, OperationBody, PublicKey, ReadXdr, ScAddress,
+            ScContractInstance, ScVal, SequenceNumber, SorobanResources, SorobanTransactionData,
+            String32, Thresholds, TransactionEnvelope, Uint256, VecM, WriteXdr,
+        },
+    };
+    use serde_json::{json, Value};
+
+    const HOLDER: [u8; 32] = [3; 32];
+
+    /// The ledger entry for `key`: an account for any account, and a Stellar Asset Contract for
+    /// any contract.
+    fn ledger_entry(key: LedgerKey) -> LedgerEntryData {
+        match key {
+            LedgerKey::Account(account) => LedgerEntryData::Account(AccountEntry {
+                account_id: account.account_id,
+                balance: 0,
+                seq_num: SequenceNumber(1),
+                num_sub_entries: 0,
+                inflation_dest: None,
+                flags: 0,
+                home_domain: String32::default(),
+                thresholds: Thresholds([1, 0, 0, 0]),
+                signers: VecM::default(),
+                ext: AccountEntryExt::V0,
+            }),
+            LedgerKey::ContractData(LedgerKeyContractData {
+                contract,
+                key,
+                durability,
+            }) => LedgerEntryData::ContractData(ContractDataEntry {
+                ext: ExtensionPoint::V0,
+                contract,
+                key,
+                durability,
+                val: ScVal::ContractInstance(ScContractInstance {
+                    executable: ContractExecutable::StellarAsset,
+                    storage: None,
+                }),
+            }),
+            key => panic!("unexpected ledger key {key:?}"),
+        }
+    }
+
+    /// Simulate a call of the token `balance` function, which returns 100 when it's called with
+    /// the address of `HOLDER`.
+    fn simulate(tx: &str) -> Value {
+        let TransactionEnvelope::Tx(envelope) =
+            TransactionEnvelope::from_xdr_base64(tx, Limits::none()).unwrap()
+        else {
+            panic!("unexpected transaction envelope");
+        };
+        let OperationBody::InvokeHostFunction(op) = &envelope.tx.operations[0].body else {
+            panic!("unexpected operation");
+        };
+        let HostFunction::InvokeContract(call) = &op.host_function else {
+            panic!("unexpected host function");
+        };
+        let holder = ScVal::Address(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256(HOLDER)),
+        )));
+        if call.function_name.to_utf8_string_lossy() != "balance" || call.args.to_vec() != [holder]
+        {
+            return json!({ "error": { "code": -32602, "message": format!("{call:?}") } });
+        }
+        let data = SorobanTransactionData {
+            ext: ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 0,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        };
+        json!({
+            "result": {
+                "transactionData": data.to_xdr_base64(Limits::none()).unwrap(),
+                "minResourceFee": "100",
+                "results": [{
+                    "auth": [],
+                    "xdr": ScVal::from(100i128).to_xdr_base64(Limits::none()).unwrap(),
+                }],
+                "cost": { "cpuInsns": "0", "memBytes": "0" },
+                "latestLedger": 1,
+            }
+        })
+    }
+
+    fn rpc_response(call: &Value) -> Value {
+        match call["method"].as_str() {
+            Some("getNetwork") => json!({
+                "result": { "passphrase": LOCAL_NETWORK_PASSPHRASE, "protocolVersion": 21 }
+            }),
+            Some("getLedgerEntries") => {
+                let key = call["params"]["keys"][0].as_str().unwrap();
+                let entry = ledger_entry(LedgerKey::from_xdr_base64(key, Limits::none()).unwrap());
+                json!({
+                    "result": {
+                        "entries": [{
+                            "key": key,
+                            "xdr": entry.to_xdr_base64(Limits::none()).unwrap(),
+                            "lastModifiedLedgerSeq": 1,
+                        }],
+                        "latestLedger": 1,
+                    }
+                })
+            }
+            Some("simulateTransaction") => {
+                simulate(call["params"]["transaction"].as_str().unwrap())
+            }
+            method => panic!("unexpected method {method:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn invokes_with_json_args() {
+        data::test::use_temp_data_home();
+        let url = mock_server(rpc_response).await;
+        let client = SorobanClient {
+            config: config::Args {
+                network: network::Args {
+                    rpc_url: Some(url),
+                    network_passphrase: Some(LOCAL_NETWORK_PASSPHRASE.to_string()),
+                    ..network::Args::default()
+                },
+                source_account: Some(stellar_strkey::ed25519::PrivateKey([1; 32]).to_string()),
+                ..config::Args::default()
+            },
+        };
+        let contract_id = stellar_strkey::Contract([2; 32]).to_string();
+        let holder = stellar_strkey::ed25519::PublicKey(HOLDER).to_string();
+        let balance = client
+            .invoke(&contract_id, "balance", json!({ "id": holder }))
+            .await
+            .unwrap();
+        assert_eq!(balance, json!("100"));
+
+        let err = client
+            .invoke(&contract_id, "no_such_function", json!({}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.exit_code(), crate::exit_code::USAGE);
+    }
+
+    #[tokio::test]
+    async fn args_must_be_an_object() {
+        let client = SorobanClient::new("local", "alice");
+        let err = client
+            .invoke("C123", "hello", serde_json::json!(["world"]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ArgsNotAnObject(_)));
+        assert_eq!(err.exit_code(), crate::exit_code::USAGE);
+    }
+}
@@ -187,13 +187,23 @@ fn to_xdr(data: &impl WriteXdr) -> Result<String, xdr::Error> {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
 
+    /// Point [`XDG_DATA_HOME`] at a temporary directory. All tests share one, since the variable
+    /// is process-wide and tests run concurrently.
+    pub(crate) fn use_temp_data_home() {
+        static DIR: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
+        let dir = DIR.get_or_init(|| {
+            let dir = assert_fs::TempDir::new().unwrap().into_persistent();
+            dir.path().to_path_buf()
+        });
+        std::env::set_var(XDG_DATA_HOME, dir);
+    }
+
     #[test]
     fn test_write_read() {
-        let t = assert_fs::TempDir::new().unwrap();
-        std::env::set_var(XDG_DATA_HOME, t.path().to_str().unwrap());
+        use_temp_data_home();
         let rpc_uri = http::uri::Uri::from_str("http://localhost:8000").unwrap();
         let sim = SimulateTransactionResponse::default();
         let original_action: Action = sim.into();
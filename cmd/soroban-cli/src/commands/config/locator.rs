@@ -1,5 +1,5 @@
 use clap::arg;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     ffi::OsStr,
     fmt::Display,
@@ -55,6 +55,10 @@ pub enum Error {
     ConfigRemoval(String, String),
     #[error("Failed to find config {0} for {1}")]
     ConfigMissing(String, String),
+    #[error("Failed to rename {0} {1}: {2} already exists")]
+    ConfigExists(String, String, String),
+    #[error("Failed to rename {0} {1} to {2}: {3}")]
+    ConfigRename(String, String, String, io::Error),
     #[error(transparent)]
     String(#[from] std::string::FromUtf8Error),
     #[error(transparent)]
@@ -79,6 +83,14 @@ pub struct Args {
     pub config_dir: Option<PathBuf>,
 }
 
+/// Settings stored in `config.toml` in a config directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Network used when none is given with --network or --rpc-url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_network: Option<String>,
+}
+
 pub enum Location {
     Local(PathBuf),
     Global(PathBuf),
@@ -114,6 +126,15 @@ impl Location {
             Location::Global(_) => Location::Global(p),
         }
     }
+
+    /// `"local"` or `"global"`.
+    #[must_use]
+    pub fn scope(&self) -> &'static str {
+        match self {
+            Location::Local(_) => "local",
+            Location::Global(_) => "global",
+        }
+    }
 }
 
 impl Args {
@@ -175,14 +196,19 @@ impl Args {
     }
 
     pub fn list_networks(&self) -> Result<Vec<String>, Error> {
-        Ok(KeyType::Network
-            .list_paths(&self.local_and_global()?)
+        Ok(self
+            .list_network_locations()?
             .into_iter()
-            .flatten()
             .map(|x| x.0)
             .collect())
     }
 
+    /// Names of the local and then global networks, with where each is stored. A local network
+    /// takes precedence over a global one with the same name.
+    pub fn list_network_locations(&self) -> Result<Vec<(String, Location)>, Error> {
+        KeyType::Network.list_paths(&self.local_and_global()?)
+    }
+
     pub fn list_networks_long(&self) -> Result<Vec<(String, Network, Location)>, Error> {
         Ok(KeyType::Network
             .list_paths(&self.local_and_global()?)
@@ -213,6 +239,26 @@ impl Args {
         res
     }
 
+    /// The default network set with `network default`, preferring the local config over the
+    /// global one.
+    pub fn default_network(&self) -> Result<Option<String>, Error> {
+        for dir in [self.local_config()?, global_config_path()?] {
+            if let Some(name) = read_config(&dir)?.default_network {
+                return Ok(Some(name));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn write_default_network(&self, name: &str) -> Result<(), Error> {
+        let dir = self.config_dir()?;
+        let mut config = read_config(&dir)?;
+        config.default_network = Some(name.to_string());
+        let path = ensure_directory(dir.join(CONFIG_FILE))?;
+        let data = toml::to_string(&config).map_err(|_| Error::ConfigSerialization)?;
+        Ok(fs::write(path, data)?)
+    }
+
     pub fn remove_identity(&self, name: &str) -> Result<(), Error> {
         KeyType::Identity.remove(name, &self.config_dir()?)
     }
@@ -221,6 +267,10 @@ impl Args {
         KeyType::Network.remove(name, &self.config_dir()?)
     }
 
+    pub fn rename_identity(&self, old: &str, new: &str) -> Result<(), Error> {
+        KeyType::Identity.rename(old, new, &self.config_dir()?)
+    }
+
     fn load_contract_from_alias(&self, alias: &str) -> Result<Option<alias::Data>, Error> {
         let path = self.alias_path(alias)?;
 
@@ -296,6 +346,16 @@ impl Args {
     }
 }
 
+const CONFIG_FILE: &str = "config.toml";
+
+fn read_config(dir: &Path) -> Result<Config, Error> {
+    let path = dir.join(CONFIG_FILE);
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    Ok(toml::from_slice(&fs::read(path)?)?)
+}
+
 fn ensure_directory(dir: PathBuf) -> Result<PathBuf, Error> {
     let parent = dir.parent().ok_or(Error::HomeDirNotFound)?;
     std::fs::create_dir_all(parent).map_err(|_| dir_creation_failed(parent))?;
@@ -417,6 +477,24 @@ impl KeyType {
             Ok(())
         }
     }
+
+    /// Rename the file of `old` to that of `new`, leaving its contents as they are. Fails if
+    /// `old` doesn't exist or `new` already does.
+    pub fn rename(&self, old: &str, new: &str, pwd: &Path) -> Result<(), Error> {
+        let (from, to) = (self.path(pwd, old), self.path(pwd, new));
+        if !from.exists() {
+            return Err(Error::ConfigMissing(self.to_string(), old.to_string()));
+        }
+        if to.exists() {
+            return Err(Error::ConfigExists(
+                self.to_string(),
+                old.to_string(),
+                new.to_string(),
+            ));
+        }
+        fs::rename(&from, &to)
+            .map_err(|e| Error::ConfigRename(self.to_string(), old.to_string(), new.to_string(), e))
+    }
 }
 
 fn global_config_path() -> Result<PathBuf, Error> {
@@ -1,13 +1,19 @@
-use std::path::PathBuf;
+use std::{
+    io::{self, IsTerminal},
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+};
 
 use clap::{arg, command};
+use ed25519_dalek::SigningKey;
 use serde::{Deserialize, Serialize};
 
-use soroban_rpc::Client;
-
 use crate::{
     signer,
-    xdr::{Transaction, TransactionEnvelope},
+    utils::{self, rpc::RetryingClient},
+    xdr::{
+        self, MuxedAccount, Operation, SequenceNumber, Transaction, TransactionEnvelope, Uint256,
+    },
     Pwd,
 };
 
@@ -34,6 +40,29 @@ pub enum Error {
     Rpc(#[from] soroban_rpc::Error),
     #[error(transparent)]
     Signer(#[from] signer::Error),
+    #[error("a source account is required, use --source-account or --secret-key-stdin")]
+    MissingSourceAccount,
+    #[error("reading secret key from stdin: {0}")]
+    CannotReadSecretKey(io::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Network(e) => e.exit_code(),
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Identity(_)
+            | Error::Secret(_)
+            | Error::Config(_)
+            | Error::Signer(_)
+            | Error::MissingSourceAccount
+            | Error::CannotReadSecretKey(_) => crate::exit_code::USAGE,
+            Error::Xdr(_) => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 #[derive(Debug, clap::Args, Clone, Default)]
@@ -42,9 +71,22 @@ pub struct Args {
     #[command(flatten)]
     pub network: network::Args,
 
-    #[arg(long, visible_alias = "source", env = "STELLAR_ACCOUNT")]
+    #[arg(
+        long,
+        visible_alias = "source",
+        env = "STELLAR_ACCOUNT",
+        required_unless_present = "secret_key_stdin"
+    )]
     /// Account that signs the final transaction. Alias `source`. Can be an identity (--source alice), a secret key (--source SC36…), or a seed phrase (--source "kite urban…").
-    pub source_account: String,
+    pub source_account: Option<String>,
+
+    #[arg(long)]
+    /// Account that pays the transaction's fee and provides its sequence number, while the source account is the source of its operations. Can be an identity, a secret key, or a seed phrase, like --source-account. The transaction is signed by both accounts
+    pub fee_source: Option<String>,
+
+    #[arg(long)]
+    /// Read the secret key that signs the final transaction from stdin, or prompt for it in a terminal, so that it doesn't appear in the process list or shell history. Takes precedence over --source-account
+    pub secret_key_stdin: bool,
 
     #[arg(long)]
     /// If using a seed phrase, which hierarchical deterministic path to use, e.g. `m/44'/148'/{hd_path}`. Example: `--hd-path 1`. Default: `0`
@@ -52,25 +94,91 @@ pub struct Args {
 
     #[command(flatten)]
     pub locator: locator::Args,
+
+    /// The key read with `--secret-key-stdin`, kept because stdin can only be read once. Shared
+    /// by clones of these args.
+    #[arg(skip)]
+    pub stdin_key: Arc<OnceLock<SigningKey>>,
 }
 
 impl Args {
     pub fn key_pair(&self) -> Result<ed25519_dalek::SigningKey, Error> {
-        let key = self.account(&self.source_account)?;
+        if self.secret_key_stdin {
+            return read_secret_key_stdin(&self.stdin_key);
+        }
+        let source_account = self
+            .source_account
+            .as_deref()
+            .ok_or(Error::MissingSourceAccount)?;
+        let key = self.account(source_account)?;
         Ok(key.key_pair(self.hd_path)?)
     }
 
+    /// The Ledger key of the source account, if it's a Ledger identity.
+    pub fn ledger_key(&self) -> Result<Option<secret::LedgerKey>, Error> {
+        if self.secret_key_stdin {
+            return Ok(None);
+        }
+        let source_account = self
+            .source_account
+            .as_deref()
+            .ok_or(Error::MissingSourceAccount)?;
+        Ok(match self.account(source_account)? {
+            Secret::Ledger { ledger } => Some(ledger),
+            _ => None,
+        })
+    }
+
+    /// The key of the `--fee-source` account, if one is given and it isn't the source account.
+    pub fn fee_source_key_pair(&self) -> Result<Option<SigningKey>, Error> {
+        let Some(fee_source) = &self.fee_source else {
+            return Ok(None);
+        };
+        let key = self.account(fee_source)?.key_pair(None)?;
+        Ok((key.verifying_key() != self.key_pair()?.verifying_key()).then_some(key))
+    }
+
+    /// Make the `--fee-source` account the source of `tx`, so that it pays the fee and its next
+    /// sequence number is used, and the source account the source of each operation that doesn't
+    /// have one. Does nothing without a fee source.
+    pub async fn apply_fee_source(
+        &self,
+        tx: &mut Transaction,
+        client: &RetryingClient,
+    ) -> Result<(), Error> {
+        let Some(fee_key) = self.fee_source_key_pair()? else {
+            return Ok(());
+        };
+        let fee_public_key = fee_key.verifying_key().to_bytes();
+        let account = client
+            .get_account(&stellar_strkey::ed25519::PublicKey(fee_public_key).to_string())
+            .await?;
+        let source = std::mem::replace(
+            &mut tx.source_account,
+            MuxedAccount::Ed25519(Uint256(fee_public_key)),
+        );
+        tx.seq_num = SequenceNumber(account.seq_num.0 + 1);
+        let mut operations: Vec<Operation> = tx.operations.clone().into();
+        for op in &mut operations {
+            op.source_account.get_or_insert_with(|| source.clone());
+        }
+        tx.operations = operations.try_into()?;
+        Ok(())
+    }
+
     pub async fn sign_with_local_key(&self, tx: Transaction) -> Result<TransactionEnvelope, Error> {
         self.sign(tx).await
     }
 
     #[allow(clippy::unused_async)]
     pub async fn sign(&self, tx: Transaction) -> Result<TransactionEnvelope, Error> {
-        let key = self.key_pair()?;
+        let keys = std::iter::once(self.key_pair()?)
+            .chain(self.fee_source_key_pair()?)
+            .collect::<Vec<_>>();
         let Network {
             network_passphrase, ..
         } = &self.get_network()?;
-        Ok(signer::sign_tx(&key, &tx, network_passphrase)?)
+        Ok(signer::sign_tx_with_keys(&keys, &tx, network_passphrase)?)
     }
 
     pub async fn sign_soroban_authorizations(
@@ -80,7 +188,7 @@ impl Args {
     ) -> Result<Option<Transaction>, Error> {
         let network = self.get_network()?;
         let source_key = self.key_pair()?;
-        let client = Client::new(&network.rpc_url)?;
+        let client = self.rpc_client(&network).await?;
         let latest_ledger = client.get_latest_ledger().await?.sequence;
         let seq_num = latest_ledger + 60; // ~ 5 min
         Ok(signer::sign_soroban_authorizations(
@@ -104,11 +212,37 @@ impl Args {
         Ok(self.network.get(&self.locator)?)
     }
 
+    /// Create a client for `network`'s RPC server with the `--rpc-*` options, see
+    /// [`Network::rpc_client`].
+    pub async fn rpc_client(&self, network: &Network) -> Result<RetryingClient, Error> {
+        Ok(network.rpc_client(&self.network.rpc_options()).await?)
+    }
+
     pub fn config_dir(&self) -> Result<PathBuf, Error> {
         Ok(self.locator.config_dir()?)
     }
 }
 
+/// Read a secret key from the first line of stdin, prompting for it without echo if stdin is a
+/// terminal. Stdin can only be read once, so the key is kept in `cache` for later calls.
+fn read_secret_key_stdin(cache: &OnceLock<SigningKey>) -> Result<SigningKey, Error> {
+    if let Some(key) = cache.get() {
+        return Ok(key.clone());
+    }
+    let stdin = io::stdin();
+    let line = if stdin.is_terminal() {
+        rpassword::prompt_password("Type a secret key: ").map_err(Error::CannotReadSecretKey)?
+    } else {
+        let mut line = String::new();
+        stdin
+            .read_line(&mut line)
+            .map_err(Error::CannotReadSecretKey)?;
+        line
+    };
+    let key = utils::parse_secret_key(line.trim()).map_err(|_| secret::Error::InvalidSecretKey)?;
+    Ok(cache.get_or_init(|| key).clone())
+}
+
 impl Pwd for Args {
     fn set_pwd(&mut self, pwd: &std::path::Path) {
         self.locator.set_pwd(pwd);
@@ -117,3 +251,20 @@ impl Pwd for Args {
 
 #[derive(Default, Serialize, Deserialize)]
 pub struct Config {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stdin_key_is_kept_per_args() {
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let args = Args {
+            secret_key_stdin: true,
+            ..Args::default()
+        };
+        args.stdin_key.set(key.clone()).unwrap();
+        assert_eq!(args.clone().key_pair().unwrap().to_bytes(), key.to_bytes());
+        assert!(Args::default().stdin_key.get().is_none());
+    }
+}
@@ -1,4 +1,7 @@
+use aes_gcm::{aead::Aead, Aes256Gcm, Key, KeyInit, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use clap::arg;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::{io::Write, str::FromStr};
 use stellar_strkey::ed25519::{PrivateKey, PublicKey};
@@ -21,8 +24,26 @@ pub enum Error {
     Ed25519(#[from] ed25519_dalek::SignatureError),
     #[error("Invalid address {0}")]
     InvalidAddress(String),
+    #[error("failed to encrypt secret")]
+    Encryption,
+    #[error("failed to decrypt secret, the password may be wrong")]
+    Decryption,
+    #[error("invalid encrypted secret")]
+    InvalidEncryptedSecret,
+    #[error("the secret key of a Ledger identity never leaves the device, only `tx sign` can sign with it")]
+    LedgerSecretKey,
 }
 
+/// Version of the format written by [`Secret::encrypt`].
+const ENCRYPTED_VERSION: u8 = 1;
+/// scrypt cost parameter `log_n` used by [`Secret::encrypt`], the recommended value.
+const SCRYPT_LOG_N: u8 = 17;
+/// Highest scrypt `log_n` accepted in a blob. Deriving the key takes `128 * 8 * 2^log_n` bytes of
+/// memory, so this bounds it at 1 GiB for a crafted blob.
+const MAX_SCRYPT_LOG_N: u8 = 20;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
 #[derive(Debug, clap::Args, Clone)]
 #[group(skip)]
 pub struct Args {
@@ -33,6 +54,14 @@ pub struct Args {
     /// Add using 12 word seed phrase to generate secret_key
     #[arg(long, conflicts_with = "secret_key")]
     pub seed_phrase: bool,
+    /// Add the key of a connected Ledger device, which stays on the device. Only its public key
+    /// is stored. Needs a build with the `ledger` feature
+    #[arg(long, conflicts_with_all = ["secret_key", "seed_phrase"])]
+    pub ledger: bool,
+    /// With --ledger, the index of the account on the device, i.e. `n` in `m/44'/148'/n'`.
+    /// Default: `0`
+    #[arg(long, requires = "ledger")]
+    pub hd_path: Option<u32>,
 }
 
 impl Args {
@@ -72,6 +101,24 @@ impl Args {
 pub enum Secret {
     SecretKey { secret_key: String },
     SeedPhrase { seed_phrase: String },
+    Ledger { ledger: LedgerKey },
+}
+
+/// A key held by a Ledger device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerKey {
+    /// Index of the account on the device, i.e. `n` in `m/44'/148'/n'`
+    pub hd_path: u32,
+    /// Public key of the account, read from the device when the identity was added
+    pub public_key: String,
+}
+
+impl LedgerKey {
+    pub fn verifying_key(&self) -> Result<ed25519_dalek::VerifyingKey, Error> {
+        Ok(ed25519_dalek::VerifyingKey::from_bytes(
+            &PublicKey::from_string(&self.public_key)?.0,
+        )?)
+    }
 }
 
 impl FromStr for Secret {
@@ -110,10 +157,14 @@ impl Secret {
                     .private()
                     .0,
             )?,
+            Secret::Ledger { .. } => return Err(Error::LedgerSecretKey),
         })
     }
 
     pub fn public_key(&self, index: Option<usize>) -> Result<PublicKey, Error> {
+        if let Secret::Ledger { ledger } = self {
+            return Ok(PublicKey::from_string(&ledger.public_key)?);
+        }
         let key = self.key_pair(index)?;
         Ok(stellar_strkey::ed25519::PublicKey::from_payload(
             key.verifying_key().as_bytes(),
@@ -138,9 +189,133 @@ impl Secret {
     pub fn test_seed_phrase() -> Result<Self, Error> {
         Self::from_seed(Some("0000000000000000"))
     }
+
+    /// Encrypt the secret with a key derived from `password` using scrypt, returning a base64
+    /// blob that can be decrypted with [`Secret::decrypt`].
+    ///
+    /// The blob holds a version byte, the scrypt `log_n` parameter, the salt, the AES-256-GCM
+    /// nonce, and the encrypted TOML of the secret.
+    pub fn encrypt(&self, password: &str) -> Result<String, Error> {
+        self.encrypt_with(password, SCRYPT_LOG_N)
+    }
+
+    fn encrypt_with(&self, password: &str, log_n: u8) -> Result<String, Error> {
+        let mut rng = rand::thread_rng();
+        let salt: [u8; SALT_LEN] = rng.gen();
+        let nonce: [u8; NONCE_LEN] = rng.gen();
+        let plaintext = toml::to_string(self).map_err(|_| Error::Encryption)?;
+        let ciphertext = cipher(password, &salt, log_n)?
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(|_| Error::Encryption)?;
+        let mut blob = vec![ENCRYPTED_VERSION, log_n];
+        blob.extend_from_slice(&salt);
+        blob.extend_from_slice(&nonce);
+        blob.extend_from_slice(&ciphertext);
+        Ok(STANDARD.encode(blob))
+    }
+
+    /// Decrypt a blob written by [`Secret::encrypt`].
+    pub fn decrypt(encrypted: &str, password: &str) -> Result<Self, Error> {
+        let blob = STANDARD
+            .decode(encrypted.trim())
+            .map_err(|_| Error::InvalidEncryptedSecret)?;
+        let [ENCRYPTED_VERSION, log_n, rest @ ..] = blob.as_slice() else {
+            return Err(Error::InvalidEncryptedSecret);
+        };
+        if rest.len() < SALT_LEN + NONCE_LEN {
+            return Err(Error::InvalidEncryptedSecret);
+        }
+        let (salt, rest) = rest.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let plaintext = cipher(password, salt, *log_n)?
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| Error::Decryption)?;
+        toml::from_slice(&plaintext).map_err(|_| Error::InvalidEncryptedSecret)
+    }
+}
+
+fn cipher(password: &str, salt: &[u8], log_n: u8) -> Result<Aes256Gcm, Error> {
+    if log_n > MAX_SCRYPT_LOG_N {
+        return Err(Error::InvalidEncryptedSecret);
+    }
+    let params = scrypt::Params::new(log_n, 8, 1, 32).map_err(|_| Error::InvalidEncryptedSecret)?;
+    let mut key = [0; 32];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key).map_err(|_| Error::Encryption)?;
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
 }
 
 fn read_password() -> Result<String, Error> {
     std::io::stdout().flush().map_err(|_| Error::PasswordRead)?;
     rpassword::read_password().map_err(|_| Error::PasswordRead)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A low scrypt cost keeps the tests fast; the cost is stored in the blob.
+    const TEST_LOG_N: u8 = 4;
+
+    #[test]
+    fn encrypted_secrets_round_trip() {
+        let seed_phrase = Secret::test_seed_phrase().unwrap();
+        let secret_key = Secret::from(seed_phrase.private_key(None).unwrap());
+        for secret in [seed_phrase, secret_key] {
+            let encrypted = secret.encrypt_with("hunter2", TEST_LOG_N).unwrap();
+            let decrypted = Secret::decrypt(&encrypted, "hunter2").unwrap();
+            assert_eq!(
+                toml::to_string(&decrypted).unwrap(),
+                toml::to_string(&secret).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn wrong_password_fails_to_decrypt() {
+        let secret = Secret::test_seed_phrase().unwrap();
+        let encrypted = secret.encrypt_with("hunter2", TEST_LOG_N).unwrap();
+        assert!(matches!(
+            Secret::decrypt(&encrypted, "hunter3"),
+            Err(Error::Decryption)
+        ));
+        assert!(matches!(
+            Secret::decrypt("bm90IGEgc2VjcmV0", "hunter2"),
+            Err(Error::InvalidEncryptedSecret)
+        ));
+    }
+
+    #[test]
+    fn excessive_scrypt_cost_is_rejected() {
+        let secret = Secret::test_seed_phrase().unwrap();
+        let mut blob = STANDARD
+            .decode(secret.encrypt_with("hunter2", TEST_LOG_N).unwrap())
+            .unwrap();
+        blob[1] = 63;
+        assert!(matches!(
+            Secret::decrypt(&STANDARD.encode(blob), "hunter2"),
+            Err(Error::InvalidEncryptedSecret)
+        ));
+    }
+
+    #[test]
+    fn ledger_identity_has_only_a_public_key() {
+        let public_key = Secret::test_seed_phrase()
+            .unwrap()
+            .public_key(None)
+            .unwrap()
+            .to_string();
+        let secret: Secret = toml::from_str(&format!(
+            "[ledger]\nhd_path = 1\npublic_key = \"{public_key}\"\n"
+        ))
+        .unwrap();
+        let Secret::Ledger { ledger } = &secret else {
+            panic!("expected a ledger identity");
+        };
+        assert_eq!(ledger.hd_path, 1);
+        assert_eq!(secret.public_key(None).unwrap().to_string(), public_key);
+        assert!(matches!(
+            secret.private_key(None),
+            Err(Error::LedgerSecretKey)
+        ));
+    }
+}
@@ -16,6 +16,16 @@ pub enum Error {
     Deploy(#[from] deploy::asset::Error),
 }
 
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Id(_) => crate::exit_code::USAGE,
+            Error::Deploy(e) => e.exit_code(),
+        }
+    }
+}
+
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         match &self {
@@ -3,8 +3,8 @@ use soroban_env_host::{
     xdr::{
         Asset, ContractDataDurability, ContractExecutable, ContractIdPreimage, CreateContractArgs,
         Error as XdrError, Hash, HostFunction, InvokeHostFunctionOp, LedgerKey::ContractData,
-        LedgerKeyContractData, Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions,
-        ScAddress, ScVal, SequenceNumber, Transaction, TransactionExt, Uint256, VecM, WriteXdr,
+        LedgerKeyContractData, Memo, MuxedAccount, Operation, OperationBody, Preconditions,
+        ScAddress, ScVal, SequenceNumber, Transaction, TransactionExt, Uint256, VecM,
     },
     HostError,
 };
@@ -18,7 +18,7 @@ use crate::{
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable,
     },
-    rpc::{Client, Error as SorobanRpcError},
+    rpc::Error as SorobanRpcError,
     utils::{contract_id_hash_from_asset, parsing::parse_asset},
 };
 
@@ -44,6 +44,20 @@ pub enum Error {
     Data(#[from] data::Error),
     #[error(transparent)]
     Network(#[from] network::Error),
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Config(e) => e.exit_code(),
+            Error::Network(e) => e.exit_code(),
+            Error::Fee(e) => e.exit_code(),
+            _ => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 impl From<Infallible> for Error {
@@ -69,7 +83,7 @@ impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         let res = self.run_against_rpc_server(None, None).await?.to_envelope();
         match res {
-            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", self.fee.tx_output(&tx)?),
             TxnEnvelopeResult::Res(contract) => {
                 println!("{contract}");
             }
@@ -93,10 +107,7 @@ impl NetworkRunnable for Cmd {
         let asset = parse_asset(&self.asset)?;
 
         let network = config.get_network()?;
-        let client = Client::new(&network.rpc_url)?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
-            .await?;
+        let client = config.rpc_client(&network).await?;
         let key = config.key_pair()?;
 
         // Get the account sequence number
@@ -107,7 +118,7 @@ impl NetworkRunnable for Cmd {
         let sequence: i64 = account_details.seq_num.into();
         let network_passphrase = &network.network_passphrase;
         let contract_id = contract_id_hash_from_asset(&asset, network_passphrase)?;
-        let tx = build_wrap_token_tx(
+        let mut tx = build_wrap_token_tx(
             &asset,
             &contract_id,
             sequence + 1,
@@ -115,7 +126,10 @@ impl NetworkRunnable for Cmd {
             network_passphrase,
             &key,
         )?;
-        if self.fee.build_only {
+        tx.memo = self.fee.memo();
+        tx.cond = self.fee.preconditions(&client).await?;
+        config.apply_fee_source(&mut tx, &client).await?;
+        if self.fee.is_build_only() {
             return Ok(TxnResult::Txn(tx));
         }
         let txn = client.simulate_and_assemble_transaction(&tx).await?;
@@ -124,7 +138,7 @@ impl NetworkRunnable for Cmd {
             return Ok(TxnResult::Txn(txn));
         }
         let get_txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(txn).await?)
+            .send_transaction_polling(&config.sign_with_local_key(txn).await?)
             .await?
             .try_into()?;
         if args.map_or(true, |a| !a.no_cache) {
@@ -3,29 +3,30 @@ use std::fmt::Debug;
 use std::num::ParseIntError;
 
 use clap::{arg, command, Parser};
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use regex::Regex;
 use soroban_env_host::{
     xdr::{
-        AccountId, ContractExecutable, ContractIdPreimage, ContractIdPreimageFromAddress,
-        CreateContractArgs, Error as XdrError, Hash, HostFunction, InvokeHostFunctionOp, Limits,
-        Memo, MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ScAddress,
-        SequenceNumber, Transaction, TransactionExt, Uint256, VecM, WriteXdr,
+        ContractExecutable, CreateContractArgs, Error as XdrError, Hash, HostFunction,
+        InvokeHostFunctionOp, Memo, MuxedAccount, Operation, OperationBody, Preconditions,
+        SequenceNumber, Transaction, TransactionExt, Uint256, VecM,
     },
     HostError,
 };
 
 use crate::commands::{
     config::{data, locator},
-    contract::{self, id::wasm::get_contract_id},
+    contract::{
+        self,
+        id::wasm::{contract_preimage, get_contract_id},
+    },
     global, network,
     txn_result::{TxnEnvelopeResult, TxnResult},
     NetworkRunnable,
 };
 use crate::{
     commands::{config, contract::install, HEADING_RPC},
-    rpc::{self, Client},
-    utils, wasm,
+    rpc, utils, wait, wasm,
 };
 
 #[derive(Parser, Debug, Clone)]
@@ -48,17 +49,31 @@ pub struct Cmd {
         help_heading = HEADING_RPC,
     )]
     pub salt: Option<String>,
+    /// Generate the salt from this seed instead of randomly, so that repeated deploys of the
+    /// same wasm from the same source account get the same contract id
+    #[arg(
+        long,
+        conflicts_with = "salt",
+        help_heading = HEADING_RPC,
+    )]
+    pub salt_seed: Option<u64>,
     #[command(flatten)]
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub wait: wait::Args,
     #[arg(long, short = 'i', default_value = "false")]
     /// Whether to ignore safety checks when deploying contracts
     pub ignore_checks: bool,
     /// The alias that will be used to save the contract's id.
     /// Whenever used, `--alias` will always overwrite the existing contract id
     /// configuration without asking for confirmation.
-    #[arg(long, value_parser = clap::builder::ValueParser::new(alias_validator))]
+    #[arg(
+        long,
+        value_parser = clap::builder::ValueParser::new(alias_validator),
+        conflicts_with = "no_wait"
+    )]
     pub alias: Option<String>,
 }
 
@@ -93,6 +108,8 @@ pub enum Error {
     #[error(transparent)]
     Rpc(#[from] rpc::Error),
     #[error(transparent)]
+    Wait(#[from] wait::Error),
+    #[error(transparent)]
     Config(#[from] config::Error),
     #[error(transparent)]
     StrKey(#[from] stellar_strkey::DecodeError),
@@ -112,13 +129,41 @@ pub enum Error {
     InvalidAliasFormat { alias: String },
     #[error(transparent)]
     Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Config(e) => e.exit_code(),
+            Error::Network(e) => e.exit_code(),
+            Error::Fee(e) => e.exit_code(),
+            Error::Wait(e) => e.exit_code(),
+            _ => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 impl Cmd {
+    /// The salt given with --salt, generated from --salt-seed, or else random.
+    fn salt(&self) -> Result<[u8; 32], Error> {
+        Ok(match (&self.salt, self.salt_seed) {
+            (Some(h), _) => soroban_spec_tools::utils::padded_hex_from_str(h, 32)
+                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?
+                .try_into()
+                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?,
+            (None, Some(seed)) => StdRng::seed_from_u64(seed).gen::<[u8; 32]>(),
+            (None, None) => rand::thread_rng().gen::<[u8; 32]>(),
+        })
+    }
+
     pub async fn run(&self) -> Result<(), Error> {
         let res = self.run_against_rpc_server(None, None).await?.to_envelope();
         match res {
-            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", self.fee.tx_output(&tx)?),
             TxnEnvelopeResult::Res(contract) => {
                 let network = self.config.get_network()?;
 
@@ -161,18 +206,21 @@ impl NetworkRunnable for Cmd {
     ) -> Result<TxnResult<String>, Error> {
         let config = config.unwrap_or(&self.config);
         let wasm_hash = if let Some(wasm) = &self.wasm {
-            let hash = if self.fee.build_only || self.fee.sim_only {
+            let hash = if self.fee.is_build_only() || self.fee.sim_only {
                 wasm::Args { wasm: wasm.clone() }.hash()?
             } else {
                 install::Cmd {
-                    wasm: wasm::Args { wasm: wasm.clone() },
+                    wasms: vec![wasm.clone()],
                     config: config.clone(),
                     fee: self.fee.clone(),
                     ignore_checks: self.ignore_checks,
+                    output: install::Output::Plain,
                 }
                 .run_against_rpc_server(global_args, Some(config))
                 .await?
                 .into_result()
+                .and_then(|hashes| hashes.into_iter().next())
+                .map(|(_, hash)| hash)
                 .expect("the value (hash) is expected because it should always be available since build-only is a shared parameter")
             };
             hex::encode(hash)
@@ -190,18 +238,9 @@ impl NetworkRunnable for Cmd {
             }
         })?);
         let network = config.get_network()?;
-        let salt: [u8; 32] = match &self.salt {
-            Some(h) => soroban_spec_tools::utils::padded_hex_from_str(h, 32)
-                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?
-                .try_into()
-                .map_err(|_| Error::CannotParseSalt { salt: h.clone() })?,
-            None => rand::thread_rng().gen::<[u8; 32]>(),
-        };
+        let salt = self.salt()?;
 
-        let client = Client::new(&network.rpc_url)?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
-            .await?;
+        let client = config.rpc_client(&network).await?;
         let key = config.key_pair()?;
 
         // Get the account sequence number
@@ -210,7 +249,7 @@ impl NetworkRunnable for Cmd {
 
         let account_details = client.get_account(&public_strkey).await?;
         let sequence: i64 = account_details.seq_num.into();
-        let (txn, contract_id) = build_create_contract_tx(
+        let (mut txn, contract_id) = build_create_contract_tx(
             wasm_hash,
             sequence + 1,
             self.fee.fee,
@@ -218,7 +257,10 @@ impl NetworkRunnable for Cmd {
             salt,
             &key,
         )?;
-        if self.fee.build_only {
+        txn.memo = self.fee.memo();
+        txn.cond = self.fee.preconditions(&client).await?;
+        config.apply_fee_source(&mut txn, &client).await?;
+        if self.fee.is_build_only() {
             return Ok(TxnResult::Txn(txn));
         }
 
@@ -227,10 +269,20 @@ impl NetworkRunnable for Cmd {
         if self.fee.sim_only {
             return Ok(TxnResult::Txn(txn));
         }
-        let get_txn_resp = client
-            .send_transaction_polling(&config.sign_with_local_key(txn).await?)
+        let res = match self
+            .wait
+            .submit(
+                &client,
+                &config.sign_with_local_key(txn).await?,
+                &network.network_passphrase,
+                global_args.is_some_and(|a| a.quiet),
+            )
             .await?
-            .try_into()?;
+        {
+            wait::Submitted::Pending(hash) => return Ok(TxnResult::Res(hash.to_string())),
+            wait::Submitted::Done(res) => *res,
+        };
+        let get_txn_resp = res.try_into()?;
         if global_args.map_or(true, |a| !a.no_cache) {
             data::write(get_txn_resp, &network.rpc_uri()?)?;
         }
@@ -248,14 +300,7 @@ fn build_create_contract_tx(
     salt: [u8; 32],
     key: &ed25519_dalek::SigningKey,
 ) -> Result<(Transaction, Hash), Error> {
-    let source_account = AccountId(PublicKey::PublicKeyTypeEd25519(
-        key.verifying_key().to_bytes().into(),
-    ));
-
-    let contract_id_preimage = ContractIdPreimage::Address(ContractIdPreimageFromAddress {
-        address: ScAddress::Account(source_account),
-        salt: Uint256(salt),
-    });
+    let contract_id_preimage = contract_preimage(&key.verifying_key(), salt);
     let contract_id = get_contract_id(contract_id_preimage.clone(), network_passphrase)?;
 
     let op = Operation {
@@ -304,6 +349,54 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_build_create_contract_matches_contract_id() {
+        let network_passphrase = "Public Global Stellar Network ; September 2015";
+        let key =
+            utils::parse_secret_key("SBFGFF27Y64ZUGFAIG5AMJGQODZZKV2YQKAVUUN4HNE24XZXD2OEUVUP")
+                .unwrap();
+        let salt = [1u8; 32];
+        let (_, deployed_id) =
+            build_create_contract_tx(Hash([0; 32]), 300, 1, network_passphrase, salt, &key)
+                .unwrap();
+        let computed_id = get_contract_id(
+            contract_preimage(&key.verifying_key(), salt),
+            network_passphrase,
+        )
+        .unwrap();
+        assert_eq!(deployed_id, computed_id);
+    }
+
+    #[test]
+    fn salt_seed_gives_the_same_salt() {
+        let salt = |seed: &str| {
+            Cmd::parse_from([
+                "deploy",
+                "--wasm-hash",
+                "00",
+                "--source-account",
+                "alice",
+                "--salt-seed",
+                seed,
+            ])
+            .salt()
+            .unwrap()
+        };
+        assert_eq!(salt("1"), salt("1"));
+        assert_ne!(salt("1"), salt("2"));
+    }
+
+    #[test]
+    fn no_wait_conflicts_with_alias_and_wait_timeout() {
+        let parse = |args: &[&str]| {
+            let base = ["deploy", "--wasm-hash", "00", "--source-account", "alice"];
+            Cmd::try_parse_from([&base[..], &["--no-wait"], args].concat())
+        };
+        assert!(parse(&[]).unwrap().wait.no_wait);
+        assert!(parse(&["--alias", "hello"]).is_err());
+        assert!(parse(&["--wait-timeout", "5"]).is_err());
+    }
+
     #[test]
     fn test_alias_validator_with_valid_inputs() {
         let valid_inputs = [
@@ -0,0 +1,255 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use clap::{arg, Parser};
+use serde_json::json;
+use soroban_env_host::xdr::{ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef};
+
+use crate::wasm;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Path to the wasm of the current contract
+    pub old: PathBuf,
+    /// Path to the wasm of the contract to compare against it, e.g. an upgrade
+    pub new: PathBuf,
+    /// Format of the summary
+    #[arg(long, value_enum, default_value_t)]
+    pub output: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// One function per line, prefixed with `+` if added, `-` if removed, or `~` if changed
+    #[default]
+    Plain,
+    /// JSON object with the added, removed and changed functions
+    Json,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("{0} breaking change(s): functions were removed or their signatures changed")]
+    Breaking(usize),
+}
+
+/// Functions that differ between two contract specs, with each function as its signature.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Diff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Old and new signatures of functions whose parameters or return type changed
+    pub changed: Vec<(String, String)>,
+}
+
+impl Diff {
+    /// Compare the functions in two specs. Functions are matched by name.
+    pub fn new(old: &[ScSpecEntry], new: &[ScSpecEntry]) -> Self {
+        let (old, new) = (functions(old), functions(new));
+        let mut diff = Diff::default();
+        for (name, old_fn) in &old {
+            match new.get(name) {
+                None => diff.removed.push(signature(old_fn)),
+                Some(new_fn)
+                    if old_fn.inputs != new_fn.inputs || old_fn.outputs != new_fn.outputs =>
+                {
+                    diff.changed.push((signature(old_fn), signature(new_fn)));
+                }
+                Some(_) => {}
+            }
+        }
+        diff.added = new
+            .iter()
+            .filter(|(name, _)| !old.contains_key(*name))
+            .map(|(_, f)| signature(f))
+            .collect();
+        diff
+    }
+
+    /// Number of changes that break callers of the old contract: removed and changed functions.
+    pub fn breaking(&self) -> usize {
+        self.removed.len() + self.changed.len()
+    }
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let old = wasm::Args::from(&self.old).parse()?;
+        let new = wasm::Args::from(&self.new).parse()?;
+        let diff = Diff::new(&old.spec, &new.spec);
+        match self.output {
+            OutputFormat::Plain => {
+                for f in &diff.added {
+                    println!("+ {f}");
+                }
+                for f in &diff.removed {
+                    println!("- {f}");
+                }
+                for (old, new) in &diff.changed {
+                    println!("~ {old}");
+                    println!("  {new}");
+                }
+            }
+            OutputFormat::Json => {
+                let changed = diff
+                    .changed
+                    .iter()
+                    .map(|(old, new)| json!({ "old": old, "new": new }))
+                    .collect::<Vec<_>>();
+                let json = json!({
+                    "added": diff.added,
+                    "removed": diff.removed,
+                    "changed": changed,
+                    "breaking": diff.breaking() > 0,
+                });
+                println!("{}", serde_json::to_string_pretty(&json)?);
+            }
+        }
+        match diff.breaking() {
+            0 => Ok(()),
+            n => Err(Error::Breaking(n)),
+        }
+    }
+}
+
+fn functions(spec: &[ScSpecEntry]) -> BTreeMap<String, &ScSpecFunctionV0> {
+    spec.iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(f) => Some((f.name.to_utf8_string_lossy(), f)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A function as `name(param: type, ...) -> type`, leaving out the return type if there is none.
+fn signature(f: &ScSpecFunctionV0) -> String {
+    let inputs = f
+        .inputs
+        .iter()
+        .map(|input| {
+            format!(
+                "{}: {}",
+                input.name.to_utf8_string_lossy(),
+                type_name(&input.type_)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let name = f.name.to_utf8_string_lossy();
+    match f.outputs.first() {
+        Some(output) => format!("{name}({inputs}) -> {}", type_name(output)),
+        None => format!("{name}({inputs})"),
+    }
+}
+
+/// A spec type as it is written in Rust contracts.
+fn type_name(type_: &ScSpecTypeDef) -> String {
+    match type_ {
+        ScSpecTypeDef::Val => "Val".to_string(),
+        ScSpecTypeDef::Bool => "bool".to_string(),
+        ScSpecTypeDef::Void => "()".to_string(),
+        ScSpecTypeDef::Error => "Error".to_string(),
+        ScSpecTypeDef::U32 => "u32".to_string(),
+        ScSpecTypeDef::I32 => "i32".to_string(),
+        ScSpecTypeDef::U64 => "u64".to_string(),
+        ScSpecTypeDef::I64 => "i64".to_string(),
+        ScSpecTypeDef::Timepoint => "Timepoint".to_string(),
+        ScSpecTypeDef::Duration => "Duration".to_string(),
+        ScSpecTypeDef::U128 => "u128".to_string(),
+        ScSpecTypeDef::I128 => "i128".to_string(),
+        ScSpecTypeDef::U256 => "U256".to_string(),
+        ScSpecTypeDef::I256 => "I256".to_string(),
+        ScSpecTypeDef::Bytes => "Bytes".to_string(),
+        ScSpecTypeDef::String => "String".to_string(),
+        ScSpecTypeDef::Symbol => "Symbol".to_string(),
+        ScSpecTypeDef::Address => "Address".to_string(),
+        ScSpecTypeDef::Option(o) => format!("Option<{}>", type_name(&o.value_type)),
+        ScSpecTypeDef::Result(r) => format!(
+            "Result<{}, {}>",
+            type_name(&r.ok_type),
+            type_name(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("Vec<{}>", type_name(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "Map<{}, {}>",
+            type_name(&m.key_type),
+            type_name(&m.value_type)
+        ),
+        ScSpecTypeDef::Tuple(t) => format!(
+            "({})",
+            t.value_types
+                .iter()
+                .map(type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::BytesN(b) => format!("BytesN<{}>", b.n),
+        ScSpecTypeDef::Udt(u) => u.name.to_utf8_string_lossy(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ScSpecFunctionInputV0, StringM};
+
+    fn function(
+        name: &str,
+        inputs: &[(&str, ScSpecTypeDef)],
+        output: Option<ScSpecTypeDef>,
+    ) -> ScSpecEntry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: StringM::default(),
+            name: name.try_into().unwrap(),
+            inputs: inputs
+                .iter()
+                .map(|(name, type_)| ScSpecFunctionInputV0 {
+                    doc: StringM::default(),
+                    name: (*name).try_into().unwrap(),
+                    type_: type_.clone(),
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            outputs: output.into_iter().collect::<Vec<_>>().try_into().unwrap(),
+        })
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_functions() {
+        let old = [
+            function(
+                "balance",
+                &[("id", ScSpecTypeDef::Address)],
+                Some(ScSpecTypeDef::I128),
+            ),
+            function("burn", &[("amount", ScSpecTypeDef::I128)], None),
+            function("name", &[], Some(ScSpecTypeDef::String)),
+        ];
+        let new = [
+            function(
+                "balance",
+                &[("id", ScSpecTypeDef::Address)],
+                Some(ScSpecTypeDef::U64),
+            ),
+            function("name", &[], Some(ScSpecTypeDef::String)),
+            function("symbol", &[], Some(ScSpecTypeDef::String)),
+        ];
+        let diff = Diff::new(&old, &new);
+        assert_eq!(diff.added, ["symbol() -> String"]);
+        assert_eq!(diff.removed, ["burn(amount: i128)"]);
+        assert_eq!(
+            diff.changed,
+            [(
+                "balance(id: Address) -> i128".to_string(),
+                "balance(id: Address) -> u64".to_string()
+            )]
+        );
+        assert_eq!(diff.breaking(), 2);
+        assert_eq!(Diff::new(&old, &old).breaking(), 0);
+    }
+}
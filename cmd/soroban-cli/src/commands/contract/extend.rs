@@ -3,9 +3,9 @@ use std::{fmt::Debug, path::Path, str::FromStr};
 use clap::{command, Parser};
 use soroban_env_host::xdr::{
     Error as XdrError, ExtendFootprintTtlOp, ExtensionPoint, LedgerEntry, LedgerEntryChange,
-    LedgerEntryData, LedgerFootprint, Limits, Memo, MuxedAccount, Operation, OperationBody,
-    Preconditions, SequenceNumber, SorobanResources, SorobanTransactionData, Transaction,
-    TransactionExt, TransactionMeta, TransactionMetaV3, TtlEntry, Uint256, WriteXdr,
+    LedgerEntryData, LedgerFootprint, MuxedAccount, Operation, OperationBody, SequenceNumber,
+    SorobanResources, SorobanTransactionData, Transaction, TransactionExt, TransactionMeta,
+    TransactionMetaV3, TtlEntry, Uint256,
 };
 
 use crate::{
@@ -15,9 +15,7 @@ use crate::{
         txn_result::{TxnEnvelopeResult, TxnResult},
         NetworkRunnable,
     },
-    key,
-    rpc::{self, Client},
-    wasm, Pwd,
+    key, rpc, wasm, Pwd,
 };
 
 const MAX_LEDGERS_TO_EXTEND: u32 = 535_679;
@@ -26,7 +24,7 @@ const MAX_LEDGERS_TO_EXTEND: u32 = 535_679;
 #[group(skip)]
 pub struct Cmd {
     /// Number of ledgers to extend the entries
-    #[arg(long, required = true)]
+    #[arg(long, required = true, alias = "ledgers-to-live")]
     pub ledgers_to_extend: u32,
     /// Only print the new Time To Live ledger
     #[arg(long)]
@@ -86,6 +84,21 @@ pub enum Error {
     Network(#[from] network::Error),
     #[error(transparent)]
     Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Config(e) => e.exit_code(),
+            Error::Network(e) => e.exit_code(),
+            Error::Fee(e) => e.exit_code(),
+            _ => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 impl Cmd {
@@ -93,7 +106,7 @@ impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         let res = self.run_against_rpc_server(None, None).await?.to_envelope();
         match res {
-            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", self.fee.tx_output(&tx)?),
             TxnEnvelopeResult::Res(ttl_ledger) => {
                 if self.ttl_ledger_only {
                     println!("{ttl_ledger}");
@@ -135,8 +148,7 @@ impl NetworkRunnable for Cmd {
             &network.network_passphrase,
         )?;
         let keys = self.key.parse_keys(contract)?;
-        let network = &config.get_network()?;
-        let client = Client::new(&network.rpc_url)?;
+        let client = config.rpc_client(&network).await?;
         let key = config.key_pair()?;
         let extend_to = self.ledgers_to_extend();
 
@@ -146,12 +158,12 @@ impl NetworkRunnable for Cmd {
         let account_details = client.get_account(&public_strkey).await?;
         let sequence: i64 = account_details.seq_num.into();
 
-        let tx = Transaction {
+        let mut tx = Transaction {
             source_account: MuxedAccount::Ed25519(Uint256(key.verifying_key().to_bytes())),
             fee: self.fee.fee,
             seq_num: SequenceNumber(sequence + 1),
-            cond: Preconditions::None,
-            memo: Memo::None,
+            cond: self.fee.preconditions(&client).await?,
+            memo: self.fee.memo(),
             operations: vec![Operation {
                 source_account: None,
                 body: OperationBody::ExtendFootprintTtl(ExtendFootprintTtlOp {
@@ -174,7 +186,8 @@ impl NetworkRunnable for Cmd {
                 resource_fee: 0,
             }),
         };
-        if self.fee.build_only {
+        config.apply_fee_source(&mut tx, &client).await?;
+        if self.fee.is_build_only() {
             return Ok(TxnResult::Txn(tx));
         }
         let tx = client
@@ -191,7 +204,7 @@ impl NetworkRunnable for Cmd {
 
         let events = res.events()?;
         if !events.is_empty() {
-            tracing::info!("Events:\n {events:#?}");
+            tracing::warn!("Events:\n {events:#?}");
         }
         let meta = res
             .result_meta
@@ -22,10 +22,7 @@ use stellar_strkey::DecodeError;
 use super::super::config::{self, locator};
 use crate::commands::network::{self, Network};
 use crate::commands::{global, NetworkRunnable};
-use crate::{
-    rpc::{self, Client},
-    Pwd,
-};
+use crate::{rpc, Pwd};
 
 #[derive(Parser, Debug, Default, Clone)]
 #[allow(clippy::struct_excessive_bools)]
@@ -34,7 +31,8 @@ pub struct Cmd {
     /// Contract ID to fetch
     #[arg(long = "id", env = "STELLAR_CONTRACT_ID")]
     pub contract_id: String,
-    /// Where to write output otherwise stdout is used
+    /// Where to write output otherwise stdout is used. On stdout the raw wasm bytes are written
+    /// as is, with nothing else printed, so they can be piped to another command
     #[arg(long, short = 'o')]
     pub out_file: Option<std::path::PathBuf>,
     #[command(flatten)]
@@ -88,6 +86,18 @@ pub enum Error {
     CannotCreateContractDir(PathBuf),
 }
 
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Config(e) => e.exit_code(),
+            Error::Network(e) => e.exit_code(),
+            _ => crate::exit_code::FAILURE,
+        }
+    }
+}
+
 impl From<Infallible> for Error {
     fn from(_: Infallible) -> Self {
         unreachable!()
@@ -141,13 +151,13 @@ impl NetworkRunnable for Cmd {
         _args: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<Vec<u8>, Error> {
-        let network = config.map_or_else(|| self.network(), |c| Ok(c.get_network()?))?;
+        let (network, options) = match config {
+            Some(config) => (config.get_network()?, config.network.rpc_options()),
+            None => (self.network()?, self.network.rpc_options()),
+        };
         tracing::trace!(?network);
         let contract_id = self.contract_id()?;
-        let client = Client::new(&network.rpc_url)?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
-            .await?;
+        let client = network.rpc_client(&options).await?;
         Ok(client.get_remote_wasm(&contract_id).await?)
     }
 }
@@ -1,5 +1,5 @@
 use clap::{command, Parser};
-use soroban_env_host::xdr;
+use soroban_env_host::xdr::{self, ScMetaEntry, ScMetaV0};
 use soroban_spec_tools::contract;
 use std::{fmt::Debug, path::PathBuf};
 use tracing::debug;
@@ -12,9 +12,12 @@ use crate::{commands::config::locator, wasm};
 pub struct Cmd {
     #[command(flatten)]
     wasm: wasm::Args,
-    /// Output just XDR in base64
+    /// Format of the spec output
     #[arg(long, default_value = "docs")]
     output: SpecOutput,
+    /// Only print the contract meta entries, one `key: value` pair per line
+    #[arg(long, conflicts_with = "output")]
+    meta_only: bool,
 
     #[clap(flatten)]
     locator: locator::Args,
@@ -35,7 +38,17 @@ pub enum Error {
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
         let wasm = self.wasm.parse()?;
-        debug!("File: {}", self.wasm.wasm.to_string_lossy());
+        debug!("File: {}", self.wasm.name());
+        if self.meta_only {
+            for ScMetaEntry::ScMetaV0(ScMetaV0 { key, val }) in &wasm.meta {
+                println!(
+                    "{}: {}",
+                    key.to_utf8_string_lossy(),
+                    val.to_utf8_string_lossy()
+                );
+            }
+            return Ok(());
+        }
         let output = match self.output {
             SpecOutput::XdrBase64 => wasm
                 .spec_base64
@@ -43,6 +56,7 @@ impl Cmd {
                 .ok_or_else(|| Error::MissingSpec(self.wasm.wasm.clone()))?,
             SpecOutput::XdrBase64Array => wasm.spec_as_json_array()?,
             SpecOutput::Docs => wasm.to_string(),
+            SpecOutput::Json => soroban_spec_json::generate(&wasm.spec),
         };
         println!("{output}");
         Ok(())
@@ -1,25 +1,26 @@
 use std::array::TryFromSliceError;
 use std::fmt::Debug;
 use std::num::ParseIntError;
+use std::path::PathBuf;
 
-use clap::{command, Parser};
+use clap::{command, Parser, ValueEnum};
 use soroban_env_host::xdr::{
     self, ContractCodeEntryExt, Error as XdrError, Hash, HostFunction, InvokeHostFunctionOp,
     LedgerEntryData, Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions, ReadXdr,
     ScMetaEntry, ScMetaV0, SequenceNumber, Transaction, TransactionExt, TransactionResult,
-    TransactionResultResult, Uint256, VecM, WriteXdr,
+    TransactionResultResult, Uint256, VecM,
 };
 
 use super::restore;
-use crate::commands::network;
+use crate::commands::network::{self, PUBLIC_NETWORK_PASSPHRASE};
 use crate::commands::txn_result::{TxnEnvelopeResult, TxnResult};
 use crate::commands::{config::data, global, NetworkRunnable};
 use crate::key;
-use crate::rpc::{self, Client};
+use crate::rpc;
+use crate::utils::rpc::RetryingClient;
 use crate::{commands::config, utils, wasm};
 
 const CONTRACT_META_SDK_KEY: &str = "rssdkver";
-const PUBLIC_NETWORK_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
@@ -28,11 +29,24 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
-    #[command(flatten)]
-    pub wasm: wasm::Args,
+    /// Path to wasm binary. Can be passed multiple times to install several
+    /// contracts in one batch
+    #[arg(long = "wasm", value_name = "WASM", required = true)]
+    pub wasms: Vec<PathBuf>,
     #[arg(long, short = 'i', default_value = "false")]
     /// Whether to ignore safety checks when deploying contracts
     pub ignore_checks: bool,
+    /// Format of the installed wasm hashes
+    #[arg(long, value_enum, default_value = "plain")]
+    pub output: Output,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// Hex hash per line, prefixed with the file when installing more than one wasm
+    Plain,
+    /// JSON object mapping each wasm file to its hash
+    Json,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -69,37 +83,71 @@ pub enum Error {
     Network(#[from] network::Error),
     #[error(transparent)]
     Data(#[from] data::Error),
+    #[error("--build-only and --sim-only only support a single --wasm, got {count}")]
+    MultipleWasmsWithoutSubmit { count: usize },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Config(e) => e.exit_code(),
+            Error::Network(e) => e.exit_code(),
+            Error::Fee(e) => e.exit_code(),
+            _ => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         let res = self.run_against_rpc_server(None, None).await?.to_envelope();
         match res {
-            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
-            TxnEnvelopeResult::Res(hash) => println!("{}", hex::encode(hash)),
+            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", self.fee.tx_output(&tx)?),
+            TxnEnvelopeResult::Res(hashes) => self.print_hashes(&hashes)?,
         };
         Ok(())
     }
-}
 
-#[async_trait::async_trait]
-impl NetworkRunnable for Cmd {
-    type Error = Error;
-    type Result = TxnResult<Hash>;
-    async fn run_against_rpc_server(
+    fn print_hashes(&self, hashes: &[(PathBuf, Hash)]) -> Result<(), Error> {
+        match self.output {
+            Output::Plain if hashes.len() == 1 => println!("{}", hex::encode(&hashes[0].1)),
+            Output::Plain => {
+                for (wasm, hash) in hashes {
+                    println!("{}: {}", wasm.display(), hex::encode(hash));
+                }
+            }
+            Output::Json => {
+                let map = hashes
+                    .iter()
+                    .map(|(wasm, hash)| (wasm.display().to_string(), hex::encode(hash).into()))
+                    .collect::<serde_json::Map<String, serde_json::Value>>();
+                println!("{}", serde_json::to_string(&map)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Install a single wasm, submitting with `sequence`. Returns the hash and
+    /// the number of transactions sent from the source account.
+    #[allow(clippy::too_many_lines)]
+    async fn install(
         &self,
+        wasm: &wasm::Args,
+        sequence: i64,
+        client: &RetryingClient,
+        network: &network::Network,
         args: Option<&global::Args>,
-        config: Option<&config::Args>,
-    ) -> Result<TxnResult<Hash>, Error> {
-        let config = config.unwrap_or(&self.config);
-        let contract = self.wasm.read()?;
-        let network = config.get_network()?;
-        let client = Client::new(&network.rpc_url)?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
-            .await?;
-        let wasm_spec = &self.wasm.parse().map_err(|e| Error::CannotParseWasm {
-            wasm: self.wasm.wasm.clone(),
+        config: &config::Args,
+    ) -> Result<(TxnResult<Hash>, i64), Error> {
+        let contract = wasm.read()?;
+        let wasm_spec = &wasm.parse().map_err(|e| Error::CannotParseWasm {
+            wasm: wasm.wasm.clone(),
             error: e,
         })?;
         // Check Rust SDK version if using the public network.
@@ -109,28 +157,27 @@ impl NetworkRunnable for Cmd {
                 && network.network_passphrase == PUBLIC_NETWORK_PASSPHRASE
             {
                 return Err(Error::ContractCompiledWithReleaseCandidateSdk {
-                    wasm: self.wasm.wasm.clone(),
+                    wasm: wasm.wasm.clone(),
                     version: rs_sdk_ver,
                 });
             } else if rs_sdk_ver.contains("rc")
                 && network.network_passphrase == PUBLIC_NETWORK_PASSPHRASE
             {
-                tracing::warn!("the deployed smart contract {path} was built with Soroban Rust SDK v{rs_sdk_ver}, a release candidate version not intended for use with the Stellar Public Network", path = self.wasm.wasm.display());
+                tracing::warn!("the deployed smart contract {path} was built with Soroban Rust SDK v{rs_sdk_ver}, a release candidate version not intended for use with the Stellar Public Network", path = wasm.wasm.display());
             }
         }
         let key = config.key_pair()?;
 
-        // Get the account sequence number
-        let public_strkey =
-            stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string();
-        let account_details = client.get_account(&public_strkey).await?;
-        let sequence: i64 = account_details.seq_num.into();
-
-        let (tx_without_preflight, hash) =
+        let (mut tx_without_preflight, hash) =
             build_install_contract_code_tx(&contract, sequence + 1, self.fee.fee, &key)?;
+        tx_without_preflight.memo = self.fee.memo();
+        tx_without_preflight.cond = self.fee.preconditions(client).await?;
+        config
+            .apply_fee_source(&mut tx_without_preflight, client)
+            .await?;
 
-        if self.fee.build_only {
-            return Ok(TxnResult::Txn(tx_without_preflight));
+        if self.fee.is_build_only() {
+            return Ok((TxnResult::Txn(tx_without_preflight), 0));
         }
         // Don't check whether the contract is already installed when the user
         // has requested to perform simulation only and is hoping to get a
@@ -153,7 +200,7 @@ impl NetworkRunnable for Cmd {
                             // Skip reupload if this isn't V0 because V1 extension already
                             // exists.
                             if code.ext.ne(&ContractCodeEntryExt::V0) {
-                                return Ok(TxnResult::Res(hash));
+                                return Ok((TxnResult::Res(hash), 0));
                             }
                         }
                         _ => {
@@ -168,14 +215,15 @@ impl NetworkRunnable for Cmd {
             .await?;
         let txn = self.fee.apply_to_assembled_txn(txn).transaction().clone();
         if self.fee.sim_only {
-            return Ok(TxnResult::Txn(txn));
+            return Ok((TxnResult::Txn(txn), 0));
         }
         let txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(txn).await?)
+            .send_transaction_polling(&config.sign_with_local_key(txn).await?)
             .await?;
         if args.map_or(true, |a| !a.no_cache) {
             data::write(txn_resp.clone().try_into().unwrap(), &network.rpc_uri()?)?;
         }
+        let mut sent = 1;
         // Currently internal errors are not returned if the contract code is expired
         if let Some(TransactionResult {
             result: TransactionResultResult::TxInternalError,
@@ -188,7 +236,7 @@ impl NetworkRunnable for Cmd {
                     contract_id: None,
                     key: None,
                     key_xdr: None,
-                    wasm: Some(self.wasm.wasm.clone()),
+                    wasm: Some(wasm.wasm.clone()),
                     wasm_hash: None,
                     durability: super::Durability::Persistent,
                 },
@@ -199,11 +247,52 @@ impl NetworkRunnable for Cmd {
             }
             .run_against_rpc_server(args, None)
             .await?;
+            sent += 1;
         }
         if args.map_or(true, |a| !a.no_cache) {
             data::write_spec(&hash.to_string(), &wasm_spec.spec)?;
         }
-        Ok(TxnResult::Res(hash))
+        Ok((TxnResult::Res(hash), sent))
+    }
+}
+
+#[async_trait::async_trait]
+impl NetworkRunnable for Cmd {
+    type Error = Error;
+    type Result = TxnResult<Vec<(PathBuf, Hash)>>;
+    async fn run_against_rpc_server(
+        &self,
+        args: Option<&global::Args>,
+        config: Option<&config::Args>,
+    ) -> Result<Self::Result, Error> {
+        let config = config.unwrap_or(&self.config);
+        if (self.fee.is_build_only() || self.fee.sim_only) && self.wasms.len() > 1 {
+            return Err(Error::MultipleWasmsWithoutSubmit {
+                count: self.wasms.len(),
+            });
+        }
+        let network = config.get_network()?;
+        let client = config.rpc_client(&network).await?;
+        let key = config.key_pair()?;
+
+        // Get the account sequence number once and reuse it for every wasm in the batch
+        let public_strkey =
+            stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string();
+        let account_details = client.get_account(&public_strkey).await?;
+        let mut sequence: i64 = account_details.seq_num.into();
+
+        let mut hashes = Vec::with_capacity(self.wasms.len());
+        for wasm in &self.wasms {
+            let (res, sent) = self
+                .install(&wasm.into(), sequence, &client, &network, args, config)
+                .await?;
+            sequence += sent;
+            match res {
+                TxnResult::Txn(tx) => return Ok(TxnResult::Txn(tx)),
+                TxnResult::Res(hash) => hashes.push((wasm.clone(), hash)),
+            }
+        }
+        Ok(TxnResult::Res(hashes))
     }
 }
 
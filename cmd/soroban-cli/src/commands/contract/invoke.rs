@@ -6,17 +6,19 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::{fmt::Debug, fs, io};
 
-use clap::{arg, command, value_parser, Parser};
+use clap::{arg, command, value_parser, Parser, ValueEnum};
 use ed25519_dalek::SigningKey;
 use heck::ToKebabCase;
 
 use soroban_env_host::{
     xdr::{
-        self, AccountEntry, AccountEntryExt, AccountId, Hash, HostFunction, InvokeContractArgs,
-        InvokeHostFunctionOp, LedgerEntryData, Limits, Memo, MuxedAccount, Operation,
-        OperationBody, Preconditions, PublicKey, ScAddress, ScSpecEntry, ScSpecFunctionV0,
-        ScSpecTypeDef, ScVal, ScVec, SequenceNumber, String32, StringM, Thresholds, Transaction,
-        TransactionExt, Uint256, VecM, WriteXdr,
+        self, AccountEntry, AccountEntryExt, AccountId, ContractEventType, DiagnosticEvent, Hash,
+        HostFunction, InvokeContractArgs, InvokeHostFunctionOp, LedgerEntryData, LedgerFootprint,
+        Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ReadXdr,
+        ScAddress, ScError, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScVal, ScVec,
+        SequenceNumber, SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanCredentials,
+        SorobanTransactionData, String32, StringM, Thresholds, Transaction, TransactionExt,
+        Uint256, VecM, WriteXdr,
     },
     HostError,
 };
@@ -32,7 +34,7 @@ use crate::commands::NetworkRunnable;
 use crate::get_spec::{self, get_remote_contract_spec};
 use crate::{
     commands::{config::data, global, network},
-    rpc, Pwd,
+    rpc, wait, Pwd,
 };
 use soroban_spec_tools::{contract, Spec};
 
@@ -49,6 +51,50 @@ pub struct Cmd {
     /// View the result simulating and do not sign and submit transaction
     #[arg(long, env = "STELLAR_INVOKE_VIEW")]
     pub is_view: bool,
+    /// Whether to sign and send the transaction after simulating it. --dry-run, --sim-only, and
+    /// --build-only never send, whatever this is set to
+    #[arg(long, value_enum, default_value_t, conflicts_with = "is_view")]
+    pub send: Send,
+    /// Exit with an error if the contract returns an error value, instead of printing it
+    #[arg(long)]
+    pub require_success: bool,
+    /// Exit with an error, showing both values, if the function's return value as JSON doesn't
+    /// equal this JSON value. Numbers also match numeric strings, e.g. `42` matches `"42"`
+    #[arg(long, value_parser = parse_json)]
+    pub expect: Option<serde_json::Value>,
+    /// Number of decimal places of i128 and u128 arguments, which are scaled by them, e.g. with
+    /// `--decimals 7` the argument `1.5` is passed as `15000000` and `2` as `20000000`
+    #[arg(long)]
+    pub decimals: Option<u32>,
+    /// Only simulate the invocation, printing a JSON summary of the return value, resource
+    /// estimates, and footprint. Nothing is signed or submitted
+    #[arg(long, conflicts_with_all = ["build_only", "sim_only", "build_only_json"])]
+    pub dry_run: bool,
+    /// Read function arguments from a JSON file containing an object that maps parameter names
+    /// to values. Arguments passed on the command line take precedence over the file
+    #[arg(long)]
+    pub args_file: Option<PathBuf>,
+    /// Function arguments by parameter name, for library use, see [`crate::SorobanClient`].
+    /// Arguments from --args-file or the command line take precedence
+    #[arg(skip)]
+    pub args: serde_json::Map<String, serde_json::Value>,
+    /// Function to call, for library use, see [`crate::SorobanClient`]. When set, the command
+    /// line isn't parsed and the arguments come only from `args` and --args-file
+    #[arg(skip)]
+    pub function: Option<String>,
+    /// Simulate the invocation as if it were sent by this account, without needing its secret
+    /// key. Only allowed when the transaction isn't submitted, i.e. with --is-view, --send=no,
+    /// --dry-run, --sim-only, or --build-only
+    #[arg(long)]
+    pub impersonate: Option<stellar_strkey::ed25519::PublicKey>,
+    /// Read a ledger footprint, as base64 XDR or JSON, and merge it into the simulated footprint.
+    /// An escape hatch for entries that simulation misses; resources are still estimated by
+    /// simulation
+    #[arg(long)]
+    pub footprint_file: Option<PathBuf>,
+    /// Format to print the function's return value in
+    #[arg(long, value_enum, default_value = "json")]
+    pub output: Output,
     /// Function name as subcommand, then arguments for that function as `--arg-name value`
     #[arg(last = true, id = "CONTRACT_FN_AND_ARGS")]
     pub slop: Vec<OsString>,
@@ -56,6 +102,33 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub wait: crate::wait::Args,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Send {
+    /// Send the transaction if simulation shows it writes to the ledger, publishes contract
+    /// events, or needs authorization, otherwise print the simulated result
+    #[default]
+    Default,
+    /// Only simulate and print the result, like --is-view
+    No,
+    /// Always send the transaction, even if it looks read-only
+    Yes,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Output {
+    /// Compact JSON
+    #[default]
+    Json,
+    /// Pretty-printed JSON
+    JsonPretty,
+    /// Base64-encoded XDR
+    Xdr,
+    /// Nothing, only the exit code indicates success
+    Quiet,
 }
 
 impl FromStr for Cmd {
@@ -88,6 +161,16 @@ pub enum Error {
     Host(#[from] HostError),
     #[error("reading file {0:?}: {1}")]
     CannotReadContractFile(PathBuf, io::Error),
+    #[error("reading args file {0:?}: {1}")]
+    CannotReadArgsFile(PathBuf, io::Error),
+    #[error("args file {0:?} must contain a JSON object mapping parameter names to values")]
+    InvalidArgsFile(PathBuf),
+    #[error("reading footprint file {0:?}: {1}")]
+    CannotReadFootprintFile(PathBuf, io::Error),
+    #[error("footprint file {0:?} must contain a base64 LedgerFootprint or its JSON form")]
+    InvalidFootprintFile(PathBuf),
+    #[error("--impersonate can't be used to submit a transaction, add --is-view, --dry-run, --sim-only, or --build-only")]
+    ImpersonateWithoutSimulation,
     #[error("committing file {filepath}: {error}")]
     CannotCommitEventsFile {
         filepath: std::path::PathBuf,
@@ -112,6 +195,8 @@ pub enum Error {
     ParseIntError(#[from] ParseIntError),
     #[error(transparent)]
     Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Wait(#[from] wait::Error),
     #[error("unexpected contract code data type: {0:?}")]
     UnexpectedContractCodeDataType(LedgerEntryData),
     #[error("missing operation result")]
@@ -126,12 +211,21 @@ pub enum Error {
     UnexpectedSimulateTransactionResultSize { length: usize },
     #[error("Missing argument {0}")]
     MissingArgument(String),
+    #[error("arguments don't match the contract spec: expected {expected}, got {got}")]
+    ArgMismatch { expected: String, got: String },
     #[error(transparent)]
     Clap(#[from] clap::Error),
     #[error(transparent)]
     Locator(#[from] locator::Error),
     #[error("Contract Error\n{0}: {1}")]
     ContractInvoke(String, String),
+    #[error("return value doesn't match --expect\n- expected: {expected}\n+ got:      {got}")]
+    UnexpectedResult {
+        expected: serde_json::Value,
+        got: serde_json::Value,
+    },
+    #[error("contract returned an error: {0:?}")]
+    ContractReturnedError(ScError),
     #[error(transparent)]
     StrKey(#[from] stellar_strkey::DecodeError),
     #[error(transparent)]
@@ -146,6 +240,45 @@ pub enum Error {
     Network(#[from] network::Error),
     #[error(transparent)]
     GetSpecError(#[from] get_spec::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Wait(e) => e.exit_code(),
+            Error::Config(e) => e.exit_code(),
+            Error::Network(e) => e.exit_code(),
+            Error::Fee(e) => e.exit_code(),
+            Error::GetSpecError(e) => e.exit_code(),
+            Error::Host(_)
+            | Error::ContractInvoke(..)
+            | Error::ContractReturnedError(_)
+            | Error::UnexpectedResult { .. } => crate::exit_code::CONTRACT,
+            Error::CannotParseArg { .. }
+            | Error::CannotReadContractFile(..)
+            | Error::CannotReadArgsFile(..)
+            | Error::InvalidArgsFile(_)
+            | Error::CannotReadFootprintFile(..)
+            | Error::InvalidFootprintFile(_)
+            | Error::ImpersonateWithoutSimulation
+            | Error::FunctionNotFoundInContractSpec(_)
+            | Error::FunctionNameTooLong(_)
+            | Error::MaxNumberOfArgumentsReached { .. }
+            | Error::MissingArgument(_)
+            | Error::ArgMismatch { .. }
+            | Error::Clap(_)
+            | Error::Locator(_)
+            | Error::StrKey(_)
+            | Error::MissingFileArg(_) => crate::exit_code::USAGE,
+            _ => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 impl From<Infallible> for Error {
@@ -156,7 +289,7 @@ impl From<Infallible> for Error {
 
 impl Cmd {
     fn is_view(&self) -> bool {
-        self.is_view ||
+        self.is_view || self.send == Send::No ||
             // TODO: Remove at next major release. Was added to retain backwards
             // compatibility when this env var used to be used for the --is-view
             // option.
@@ -183,10 +316,26 @@ impl Cmd {
         let mut matches_ = cmd.get_matches_from(&self.slop);
         let Some((function, matches_)) = &matches_.remove_subcommand() else {
             println!("{long_help}");
-            std::process::exit(1);
+            std::process::exit(crate::exit_code::USAGE);
         };
 
         let func = spec.find_function(function)?;
+        let file_args = self.read_args_file()?;
+        let missing = func
+            .inputs
+            .iter()
+            .filter(|i| !matches!(i.type_, ScSpecTypeDef::Option(_)))
+            .map(|i| i.name.to_utf8_string_lossy())
+            .filter(|name| {
+                matches_.get_raw(name).is_none()
+                    && !file_args.contains_key(name)
+                    && !matches_.contains_id(&fmt_arg_file_name(name))
+                    && !matches_.contains_id(&fmt_arg_xdr_name(name))
+            })
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(Error::MissingArgument(missing.join(", ")));
+        }
         // create parsed_args in same order as the inputs to func
         let mut signers: Vec<SigningKey> = vec![];
         let parsed_args = func
@@ -194,22 +343,50 @@ impl Cmd {
             .iter()
             .map(|i| {
                 let name = i.name.to_utf8_string()?;
-                if let Some(mut val) = matches_.get_raw(&name) {
-                    let mut s = val.next().unwrap().to_string_lossy().to_string();
-                    if matches!(i.type_, ScSpecTypeDef::Address) {
-                        let cmd = crate::commands::keys::address::Cmd {
-                            name: s.clone(),
-                            hd_path: Some(0),
-                            locator: config.locator.clone(),
-                        };
-                        if let Ok(address) = cmd.public_key() {
-                            s = address.to_string();
-                        }
-                        if let Ok(key) = cmd.private_key() {
-                            signers.push(key);
+                let from_command_line = matches_.value_source(&name)
+                    == Some(clap::parser::ValueSource::CommandLine)
+                    || matches_.contains_id(&fmt_arg_file_name(&name))
+                    || matches_.contains_id(&fmt_arg_xdr_name(&name));
+                let file_arg = file_args.get(&name).filter(|_| !from_command_line);
+                if let Some(xdr) = matches_.get_one::<String>(&fmt_arg_xdr_name(&name)) {
+                    ScVal::from_xdr_base64(xdr, Limits::none()).map_err(|error| {
+                        Error::CannotParseArg {
+                            arg: name,
+                            error: soroban_spec_tools::Error::Xdr(error),
                         }
+                    })
+                } else if let Some(value) = file_arg {
+                    self.json_arg(&spec, &i.type_, value, config, &mut signers)
+                        .map_err(|error| Error::CannotParseArg { arg: name, error })
+                } else if let Some(val) = matches_.get_raw(&name) {
+                    let values = val
+                        .map(|v| v.to_string_lossy().to_string())
+                        .collect::<Vec<_>>();
+                    if let (ScSpecTypeDef::Vec(vec), Some(elements)) =
+                        (&i.type_, split_vec_arg(&values, &i.type_))
+                    {
+                        let elements = elements
+                            .into_iter()
+                            .map(|s| {
+                                let s = if matches!(*vec.element_type, ScSpecTypeDef::Address) {
+                                    resolve_address(s, config, &mut signers)
+                                } else {
+                                    s.to_string()
+                                };
+                                spec.from_string_with_decimals(&s, &vec.element_type, self.decimals)
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                            .map_err(|error| Error::CannotParseArg {
+                                arg: name.clone(),
+                                error,
+                            })?;
+                        return Ok(ScVal::Vec(Some(ScVec(elements.try_into()?))));
+                    }
+                    let mut s = values[0].clone();
+                    if matches!(i.type_, ScSpecTypeDef::Address) {
+                        s = resolve_address(&s, config, &mut signers);
                     }
-                    spec.from_string(&s, &i.type_)
+                    spec.from_string_with_decimals(&s, &i.type_, self.decimals)
                         .map_err(|error| Error::CannotParseArg { arg: name, error })
                 } else if matches!(i.type_, ScSpecTypeDef::Option(_)) {
                     Ok(ScVal::Void)
@@ -233,7 +410,7 @@ impl Cmd {
                             i.type_,
                             file_contents.len()
                         );
-                        spec.from_string(&file_contents, &i.type_)
+                        spec.from_string_with_decimals(&file_contents, &i.type_, self.decimals)
                             .map_err(|error| Error::CannotParseArg { arg: name, error })
                     }
                 } else {
@@ -241,34 +418,99 @@ impl Cmd {
                 }
             })
             .collect::<Result<Vec<_>, Error>>()?;
+        check_args(func, &parsed_args)?;
+        let invoke_args = invoke_contract_args(contract_id, function, parsed_args)?;
+        Ok((function.clone(), spec, invoke_args, signers))
+    }
 
-        let contract_address_arg = ScAddress::Contract(Hash(contract_id));
-        let function_symbol_arg = function
-            .try_into()
-            .map_err(|()| Error::FunctionNameTooLong(function.clone()))?;
-
-        let final_args =
-            parsed_args
-                .clone()
-                .try_into()
-                .map_err(|_| Error::MaxNumberOfArgumentsReached {
-                    current: parsed_args.len(),
-                    maximum: ScVec::default().max_len(),
-                })?;
-
-        let invoke_args = InvokeContractArgs {
-            contract_address: contract_address_arg,
-            function_name: function_symbol_arg,
-            args: final_args,
-        };
+    /// Build the call to `function` from the arguments in `args` and --args-file alone, without
+    /// parsing a command line.
+    fn build_host_function_parameters_from_json(
+        &self,
+        contract_id: [u8; 32],
+        spec_entries: &[ScSpecEntry],
+        function: &str,
+        config: &config::Args,
+    ) -> Result<(String, Spec, InvokeContractArgs, Vec<SigningKey>), Error> {
+        let spec = Spec(Some(spec_entries.to_vec()));
+        let func = spec
+            .find_function(function)
+            .map_err(|_| Error::FunctionNotFoundInContractSpec(function.to_string()))?;
+        let args = self.read_args_file()?;
+        let missing = func
+            .inputs
+            .iter()
+            .filter(|i| !matches!(i.type_, ScSpecTypeDef::Option(_)))
+            .map(|i| i.name.to_utf8_string_lossy())
+            .filter(|name| !args.contains_key(name))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(Error::MissingArgument(missing.join(", ")));
+        }
+        let mut signers: Vec<SigningKey> = vec![];
+        let parsed_args = func
+            .inputs
+            .iter()
+            .map(|i| {
+                let name = i.name.to_utf8_string()?;
+                match args.get(&name) {
+                    Some(value) => self
+                        .json_arg(&spec, &i.type_, value, config, &mut signers)
+                        .map_err(|error| Error::CannotParseArg { arg: name, error }),
+                    None => Ok(ScVal::Void),
+                }
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        check_args(func, &parsed_args)?;
+        let invoke_args = invoke_contract_args(contract_id, function, parsed_args)?;
+        Ok((function.to_string(), spec, invoke_args, signers))
+    }
 
-        Ok((function.clone(), spec, invoke_args, signers))
+    /// Build the call from `function` and `args` when a function is set, or else from the
+    /// command line.
+    fn host_function_parameters(
+        &self,
+        contract_id: [u8; 32],
+        spec_entries: &[ScSpecEntry],
+        config: &config::Args,
+    ) -> Result<(String, Spec, InvokeContractArgs, Vec<SigningKey>), Error> {
+        if let Some(function) = &self.function {
+            self.build_host_function_parameters_from_json(
+                contract_id,
+                spec_entries,
+                function,
+                config,
+            )
+        } else {
+            self.build_host_function_parameters(contract_id, spec_entries, config)
+        }
+    }
+
+    /// Parse a JSON argument value, resolving identity names given for addresses.
+    fn json_arg(
+        &self,
+        spec: &Spec,
+        type_: &ScSpecTypeDef,
+        value: &serde_json::Value,
+        config: &config::Args,
+        signers: &mut Vec<SigningKey>,
+    ) -> Result<ScVal, soroban_spec_tools::Error> {
+        let value = match value {
+            serde_json::Value::String(s) if matches!(type_, ScSpecTypeDef::Address) => {
+                serde_json::Value::String(resolve_address(s, config, signers))
+            }
+            _ => value.clone(),
+        };
+        spec.from_json_with_decimals(&value, type_, self.decimals)
     }
 
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         let res = self.invoke(global_args).await?.to_envelope();
+        if self.output == Output::Quiet {
+            return Ok(());
+        }
         match res {
-            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", tx.to_xdr_base64(Limits::none())?),
+            TxnEnvelopeResult::TxnEnvelope(tx) => println!("{}", self.fee.tx_output(&tx)?),
             TxnEnvelopeResult::Res(output) => {
                 println!("{output}");
             }
@@ -280,6 +522,37 @@ impl Cmd {
         self.run_against_rpc_server(Some(global_args), None).await
     }
 
+    /// Read `--args-file` over the arguments in `args`, returning only those when it isn't given.
+    fn read_args_file(&self) -> Result<serde_json::Map<String, serde_json::Value>, Error> {
+        let mut args = self.args.clone();
+        let Some(path) = &self.args_file else {
+            return Ok(args);
+        };
+        let contents =
+            fs::read_to_string(path).map_err(|e| Error::CannotReadArgsFile(path.clone(), e))?;
+        match serde_json::from_str(&contents) {
+            Ok(serde_json::Value::Object(file_args)) => {
+                args.extend(file_args);
+                Ok(args)
+            }
+            _ => Err(Error::InvalidArgsFile(path.clone())),
+        }
+    }
+
+    /// Read `--footprint-file`, returning no footprint when it isn't given.
+    fn read_footprint_file(&self) -> Result<Option<LedgerFootprint>, Error> {
+        let Some(path) = &self.footprint_file else {
+            return Ok(None);
+        };
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::CannotReadFootprintFile(path.clone(), e))?;
+        LedgerFootprint::from_xdr_base64(contents.trim(), Limits::none())
+            .ok()
+            .or_else(|| serde_json::from_str(&contents).ok())
+            .map(Some)
+            .ok_or_else(|| Error::InvalidFootprintFile(path.clone()))
+    }
+
     pub fn read_wasm(&self) -> Result<Option<Vec<u8>>, Error> {
         Ok(if let Some(wasm) = self.wasm.as_ref() {
             Some(fs::read(wasm).map_err(|e| Error::CannotReadContractFile(wasm.clone(), e))?)
@@ -307,6 +580,12 @@ impl NetworkRunnable for Cmd {
         global_args: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<TxnResult<String>, Error> {
+        if self.impersonate.is_some()
+            && !(self.is_view() || self.dry_run || self.fee.sim_only || self.fee.is_build_only())
+        {
+            return Err(Error::ImpersonateWithoutSimulation);
+        }
+        let footprint = self.read_footprint_file()?;
         let config = config.unwrap_or(&self.config);
         let network = config.get_network()?;
         tracing::trace!(?network);
@@ -318,15 +597,14 @@ impl NetworkRunnable for Cmd {
         let spec_entries = self.spec_entries()?;
         if let Some(spec_entries) = &spec_entries {
             // For testing wasm arg parsing
-            let _ = self.build_host_function_parameters(contract_id, spec_entries, config)?;
+            let _ = self.host_function_parameters(contract_id, spec_entries, config)?;
         }
-        let client = rpc::Client::new(&network.rpc_url)?;
-        let account_details = if self.is_view {
+        let client = config.rpc_client(&network).await?;
+        let account_details = if let Some(address) = &self.impersonate {
+            client.get_account(&address.to_string()).await?
+        } else if self.is_view || self.send == Send::No {
             default_account_entry()
         } else {
-            client
-                .verify_network_passphrase(Some(&network.network_passphrase))
-                .await?;
             let key = config.key_pair()?;
 
             // Get the account sequence number
@@ -349,32 +627,73 @@ impl NetworkRunnable for Cmd {
 
         // Get the ledger footprint
         let (function, spec, host_function_params, signers) =
-            self.build_host_function_parameters(contract_id, &spec_entries, config)?;
-        let tx = build_invoke_contract_tx(
+            self.host_function_parameters(contract_id, &spec_entries, config)?;
+        let mut tx = build_invoke_contract_tx(
             host_function_params.clone(),
             sequence + 1,
             self.fee.fee,
             account_id,
         )?;
-        if self.fee.build_only {
+        tx.memo = self.fee.memo();
+        tx.cond = self.fee.preconditions(&client).await?;
+        if !(self.is_view || self.send == Send::No) && self.impersonate.is_none() {
+            config.apply_fee_source(&mut tx, &client).await?;
+        }
+        if self.fee.is_build_only() {
             return Ok(TxnResult::Txn(tx));
         }
         let txn = client.simulate_and_assemble_transaction(&tx).await?;
         let txn = self.fee.apply_to_assembled_txn(txn);
+        if !(self.is_view || self.send == Send::No) && self.impersonate.is_none() {
+            warn_on_third_party_auth(txn.transaction(), &signers);
+        }
+        let mut assembled = txn.transaction().clone();
+        if let Some(footprint) = &footprint {
+            let added = merge_footprint(&mut assembled, footprint)?;
+            if added > 0 {
+                tracing::warn!(
+                    "--footprint-file added {added} ledger entries that simulation didn't find; \
+                     the simulated resource limits may be too low for them"
+                );
+            }
+        }
         if self.fee.sim_only {
-            return Ok(TxnResult::Txn(txn.transaction().clone()));
+            return Ok(TxnResult::Txn(assembled));
         }
         let sim_res = txn.sim_response();
+        if self.fee.cost {
+            if let TransactionExt::V1(data) = &txn.transaction().ext {
+                eprint!("{}", crate::log::cost_table(&sim_res.cost, data));
+            }
+        }
+        if self.dry_run {
+            let return_value = return_value_to_json(&spec, &sim_res.results()?[0].xdr, &function)?;
+            let summary = dry_run_summary(return_value, &sim_res.cost, &assembled)?;
+            return Ok(TxnResult::Res(summary.to_string()));
+        }
         if global_args.map_or(true, |a| !a.no_cache) {
             data::write(sim_res.clone().into(), &network.rpc_uri()?)?;
         }
-        let (return_value, events) = if self.is_view() {
-            // log_auth_cost_and_footprint(Some(&sim_res.transaction_data()?.resources));
-            (sim_res.results()?[0].xdr.clone(), sim_res.events()?)
-        } else {
-            let global::Args { no_cache, .. } = global_args.cloned().unwrap_or_default();
+        let send = match self.send {
+            Send::Default if !self.is_view() => {
+                let send = changes_state(&assembled, &sim_res.events()?);
+                if !send {
+                    tracing::warn!(
+                        "Simulation shows the call is read-only, so it wasn't sent. \
+                         Use --send=yes to send it anyway"
+                    );
+                }
+                send
+            }
+            Send::Yes => true,
+            Send::Default | Send::No => false,
+        };
+        let (return_value, events) = if send {
+            let global::Args {
+                no_cache, quiet, ..
+            } = global_args.cloned().unwrap_or_default();
             // Need to sign all auth entries
-            let mut txn = txn.transaction().clone();
+            let mut txn = assembled;
             // let auth = auth_entries(&txn);
             // crate::log::auth(&[auth]);
 
@@ -382,17 +701,43 @@ impl NetworkRunnable for Cmd {
                 txn = tx;
             }
             // log_auth_cost_and_footprint(resources(&txn));
-            let res = client
-                .send_transaction_polling(&config.sign_with_local_key(txn).await?)
-                .await?;
+            let res = match self
+                .wait
+                .submit(
+                    &client,
+                    &config.sign_with_local_key(txn).await?,
+                    &network.network_passphrase,
+                    quiet,
+                )
+                .await?
+            {
+                wait::Submitted::Pending(hash) => return Ok(TxnResult::Res(hash.to_string())),
+                wait::Submitted::Done(res) => *res,
+            };
             if !no_cache {
                 data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
             }
             (res.return_value()?, res.contract_events()?)
+        } else {
+            // log_auth_cost_and_footprint(Some(&sim_res.transaction_data()?.resources));
+            (sim_res.results()?[0].xdr.clone(), sim_res.events()?)
         };
 
-        crate::log::diagnostic_events(&events, tracing::Level::INFO);
-        output_to_string(&spec, &return_value, &function)
+        crate::log::diagnostic_events(&events, tracing::Level::WARN);
+        if self.require_success {
+            check_success(&spec, &return_value)?;
+        }
+        if let Some(expected) = &self.expect {
+            let got = return_value_to_json(&spec, &return_value, &function)?
+                .unwrap_or(serde_json::Value::Null);
+            if !json_matches(expected, &got) {
+                return Err(Error::UnexpectedResult {
+                    expected: expected.clone(),
+                    got,
+                });
+            }
+        }
+        output_to_string(&spec, &return_value, &function, self.output)
     }
 }
 
@@ -426,6 +771,68 @@ const DEFAULT_ACCOUNT_ID: AccountId = AccountId(PublicKey::PublicKeyTypeEd25519(
 //         .unwrap_or_default()
 // }
 
+/// Warn about auth entries recorded during simulation that must be signed by an address other
+/// than the source account and for which no signing key was provided, since these would
+/// otherwise only surface as an error when signing.
+fn warn_on_third_party_auth(tx: &Transaction, signers: &[SigningKey]) {
+    let MuxedAccount::Ed25519(source) = &tx.source_account else {
+        return;
+    };
+    let source_strkey = stellar_strkey::ed25519::PublicKey(source.0).to_string();
+    for address in third_party_auth_addresses(tx, source, signers) {
+        match address {
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(a)))) => {
+                let address = stellar_strkey::ed25519::PublicKey(a);
+                tracing::warn!(
+                    "this call requires authorization from {address}; you are submitting as {source_strkey}. \
+                     Pass an identity or secret key for {address} as the argument to sign as that address"
+                );
+            }
+            ScAddress::Contract(Hash(c)) => {
+                let address = stellar_strkey::Contract(c);
+                tracing::warn!(
+                    "this call requires authorization from the contract {address}, which is checked \
+                     by the contract's own __check_auth function. The CLI can't sign for contracts, \
+                     so sign the auth entry with that contract's scheme before sending"
+                );
+            }
+        }
+    }
+}
+
+fn third_party_auth_addresses(
+    tx: &Transaction,
+    source: &Uint256,
+    signers: &[SigningKey],
+) -> Vec<ScAddress> {
+    let Some(Operation {
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp { auth, .. }),
+        ..
+    }) = tx.operations.first()
+    else {
+        return vec![];
+    };
+    auth.iter()
+        .filter_map(|entry| {
+            let SorobanAuthorizationEntry {
+                credentials: SorobanCredentials::Address(SorobanAddressCredentials { address, .. }),
+                ..
+            } = entry
+            else {
+                return None;
+            };
+            match address {
+                ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(a)))) => {
+                    let has_signer =
+                        a == &source.0 || signers.iter().any(|s| a == s.verifying_key().as_bytes());
+                    (!has_signer).then(|| address.clone())
+                }
+                ScAddress::Contract(_) => Some(address.clone()),
+            }
+        })
+        .collect()
+}
+
 fn default_account_entry() -> AccountEntry {
     AccountEntry {
         account_id: DEFAULT_ACCOUNT_ID,
@@ -445,20 +852,239 @@ pub fn output_to_string(
     spec: &Spec,
     res: &ScVal,
     function: &str,
+    output: Output,
 ) -> Result<TxnResult<String>, Error> {
-    let mut res_str = String::new();
-    if let Some(output) = spec.find_function(function)?.outputs.first() {
-        res_str = spec
-            .xdr_to_json(res, output)
-            .map_err(|e| Error::CannotPrintResult {
-                result: res.clone(),
-                error: e,
-            })?
-            .to_string();
-    }
+    let res_str = match output {
+        Output::Json => return_value_to_json(spec, res, function)?
+            .map(|v| v.to_string())
+            .unwrap_or_default(),
+        Output::JsonPretty => return_value_to_json(spec, res, function)?
+            .map(|v| serde_json::to_string_pretty(&v))
+            .transpose()?
+            .unwrap_or_default(),
+        Output::Xdr => res.to_xdr_base64(Limits::none())?,
+        Output::Quiet => String::new(),
+    };
     Ok(TxnResult::Res(res_str))
 }
 
+/// Convert the return value to JSON using the function's output type, if it has one.
+fn return_value_to_json(
+    spec: &Spec,
+    res: &ScVal,
+    function: &str,
+) -> Result<Option<serde_json::Value>, Error> {
+    spec.find_function(function)?
+        .outputs
+        .first()
+        .map(|output| {
+            spec.xdr_to_json(res, output)
+                .map_err(|e| Error::CannotPrintResult {
+                    result: res.clone(),
+                    error: e,
+                })
+        })
+        .transpose()
+}
+
+fn parse_json(s: &str) -> Result<serde_json::Value, serde_json::Error> {
+    serde_json::from_str(s)
+}
+
+/// Compare JSON values for `--expect`. Large integers are printed as strings, so a number also
+/// matches a string holding the same number.
+fn json_matches(expected: &serde_json::Value, got: &serde_json::Value) -> bool {
+    use serde_json::Value;
+    match (expected, got) {
+        (Value::Number(n), Value::String(s)) | (Value::String(s), Value::Number(n)) => {
+            n.to_string() == *s
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_matches(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, a)| b.get(k).is_some_and(|b| json_matches(a, b)))
+        }
+        _ => expected == got,
+    }
+}
+
+/// Summarize a simulated invocation: its return value, the cost reported by simulation, and the
+/// resources and footprint assembled into the transaction.
+fn dry_run_summary(
+    return_value: Option<serde_json::Value>,
+    cost: &rpc::Cost,
+    tx: &Transaction,
+) -> Result<serde_json::Value, Error> {
+    let mut summary = serde_json::json!({
+        "return_value": return_value,
+        "cost": {
+            "cpu_instructions": cost.cpu_insns,
+            "memory_bytes": cost.mem_bytes,
+        },
+    });
+    if let TransactionExt::V1(SorobanTransactionData {
+        resources,
+        resource_fee,
+        ..
+    }) = &tx.ext
+    {
+        summary["resources"] = serde_json::json!({
+            "instructions": resources.instructions,
+            "read_bytes": resources.read_bytes,
+            "write_bytes": resources.write_bytes,
+            "resource_fee": resource_fee,
+        });
+        summary["footprint"] = serde_json::json!({
+            "read_only": serde_json::to_value(&resources.footprint.read_only)?,
+            "read_write": serde_json::to_value(&resources.footprint.read_write)?,
+        });
+    }
+    Ok(summary)
+}
+
+/// Merge the keys of `extra` into the footprint of `tx`, returning how many keys weren't in it
+/// already. A key that is read-write in either footprint is read-write in the result.
+fn merge_footprint(tx: &mut Transaction, extra: &LedgerFootprint) -> Result<usize, Error> {
+    let TransactionExt::V1(data) = &mut tx.ext else {
+        return Ok(0);
+    };
+    let footprint = &mut data.resources.footprint;
+    let mut read_only = footprint.read_only.to_vec();
+    let mut read_write = footprint.read_write.to_vec();
+    let before = read_only.len() + read_write.len();
+    for key in extra.read_write.iter() {
+        read_only.retain(|k| k != key);
+        if !read_write.contains(key) {
+            read_write.push(key.clone());
+        }
+    }
+    for key in extra.read_only.iter() {
+        if !read_only.contains(key) && !read_write.contains(key) {
+            read_only.push(key.clone());
+        }
+    }
+    let added = read_only.len() + read_write.len() - before;
+    footprint.read_only = read_only.try_into()?;
+    footprint.read_write = read_write.try_into()?;
+    Ok(added)
+}
+
+/// Whether sending `tx` would change anything: it writes to the ledger, needs authorization, or
+/// publishes contract events in simulation.
+fn changes_state(tx: &Transaction, events: &[DiagnosticEvent]) -> bool {
+    let writes = match &tx.ext {
+        TransactionExt::V1(data) => !data.resources.footprint.read_write.is_empty(),
+        TransactionExt::V0 => false,
+    };
+    let needs_auth = tx.operations.iter().any(
+        |op| matches!(&op.body, OperationBody::InvokeHostFunction(body) if !body.auth.is_empty()),
+    );
+    let publishes_events = events
+        .iter()
+        .any(|e| e.in_successful_contract_call && e.event.type_ == ContractEventType::Contract);
+    writes || needs_auth || publishes_events
+}
+
+/// Fail when the contract returned an error value rather than trapping, describing contract errors
+/// using the cases of the contract's `Error` enum where possible.
+fn check_success(spec: &Spec, res: &ScVal) -> Result<(), Error> {
+    let ScVal::Error(e) = res else {
+        return Ok(());
+    };
+    if let ScError::Contract(code) = e {
+        if let Ok(case) = spec.find_error_type(*code) {
+            return Err(Error::ContractInvoke(
+                case.name.to_utf8_string_lossy(),
+                case.doc.to_utf8_string_lossy(),
+            ));
+        }
+    }
+    Err(Error::ContractReturnedError(e.clone()))
+}
+
+/// The call of `function` on the contract `contract_id` with `args`.
+fn invoke_contract_args(
+    contract_id: [u8; 32],
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<InvokeContractArgs, Error> {
+    let function_name = function
+        .try_into()
+        .map_err(|()| Error::FunctionNameTooLong(function.to_string()))?;
+    let current = args.len();
+    let args = args
+        .try_into()
+        .map_err(|_| Error::MaxNumberOfArgumentsReached {
+            current,
+            maximum: ScVec::default().max_len(),
+        })?;
+    Ok(InvokeContractArgs {
+        contract_address: ScAddress::Contract(Hash(contract_id)),
+        function_name,
+        args,
+    })
+}
+
+/// Check that `args` match the count and types of the function's inputs, so a mismatch, such as
+/// a value passed as XDR that isn't of the parameter's type, fails before anything is sent
+/// instead of as a host error. Types are only checked at the top level.
+fn check_args(func: &ScSpecFunctionV0, args: &[ScVal]) -> Result<(), Error> {
+    if func.inputs.len() != args.len() {
+        return Err(Error::ArgMismatch {
+            expected: format!("{} arguments", func.inputs.len()),
+            got: format!("{} arguments", args.len()),
+        });
+    }
+    for (input, arg) in func.inputs.iter().zip(args) {
+        if !matches_type(arg, &input.type_) {
+            return Err(Error::ArgMismatch {
+                expected: format!(
+                    "{} as {}",
+                    input.name.to_utf8_string_lossy(),
+                    input.type_.name()
+                ),
+                got: arg.name().to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Whether `val` can be a value of `type_`, looking inside options and results but not other
+/// containers. User defined types match the values they are represented as.
+fn matches_type(val: &ScVal, type_: &ScSpecTypeDef) -> bool {
+    match (val, type_) {
+        (_, ScSpecTypeDef::Val)
+        | (ScVal::Void, ScSpecTypeDef::Option(_) | ScSpecTypeDef::Void)
+        | (ScVal::Bool(_), ScSpecTypeDef::Bool)
+        | (ScVal::Error(_), ScSpecTypeDef::Error)
+        | (ScVal::U32(_), ScSpecTypeDef::U32)
+        | (ScVal::I32(_), ScSpecTypeDef::I32)
+        | (ScVal::U64(_), ScSpecTypeDef::U64)
+        | (ScVal::I64(_), ScSpecTypeDef::I64)
+        | (ScVal::Timepoint(_), ScSpecTypeDef::Timepoint)
+        | (ScVal::Duration(_), ScSpecTypeDef::Duration)
+        | (ScVal::U128(_), ScSpecTypeDef::U128)
+        | (ScVal::I128(_), ScSpecTypeDef::I128)
+        | (ScVal::U256(_), ScSpecTypeDef::U256)
+        | (ScVal::I256(_), ScSpecTypeDef::I256)
+        | (ScVal::Bytes(_), ScSpecTypeDef::Bytes)
+        | (ScVal::String(_), ScSpecTypeDef::String)
+        | (ScVal::Symbol(_), ScSpecTypeDef::Symbol)
+        | (ScVal::Address(_), ScSpecTypeDef::Address)
+        | (ScVal::Vec(_), ScSpecTypeDef::Vec(_) | ScSpecTypeDef::Tuple(_))
+        | (ScVal::Map(_), ScSpecTypeDef::Map(_))
+        | (ScVal::Map(_) | ScVal::Vec(_) | ScVal::U32(_), ScSpecTypeDef::Udt(_)) => true,
+        (ScVal::Bytes(bytes), ScSpecTypeDef::BytesN(n)) => bytes.len() == n.n as usize,
+        (val, ScSpecTypeDef::Option(option)) => matches_type(val, &option.value_type),
+        (val, ScSpecTypeDef::Result(result)) => matches_type(val, &result.ok_type),
+        _ => false,
+    }
+}
+
 fn build_invoke_contract_tx(
     parameters: InvokeContractArgs,
     sequence: i64,
@@ -511,6 +1137,7 @@ fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
         let mut arg = clap::Arg::new(name);
         let file_arg_name = fmt_arg_file_name(name);
         let mut file_arg = clap::Arg::new(&file_arg_name);
+        let xdr_arg_name = fmt_arg_xdr_name(name);
         arg = arg
             .long(name)
             .alias(name.to_kebab_case())
@@ -526,6 +1153,14 @@ fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
             .value_parser(value_parser!(PathBuf))
             .conflicts_with(name);
 
+        let xdr_arg = clap::Arg::new(&xdr_arg_name)
+            .long(&xdr_arg_name)
+            .alias(xdr_arg_name.to_kebab_case())
+            .num_args(1)
+            .hide(true)
+            .value_parser(clap::builder::NonEmptyStringValueParser::new())
+            .conflicts_with_all([name, &file_arg_name]);
+
         if let Some(value_name) = spec.arg_value_name(type_, 0) {
             let value_name: &'static str = Box::leak(value_name.into_boxed_str());
             arg = arg.value_name(value_name);
@@ -539,6 +1174,7 @@ fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
                 .default_value("false")
                 .num_args(0..=1),
             ScSpecTypeDef::Option(_val) => arg.required(false),
+            ScSpecTypeDef::Vec(_) => arg.action(clap::ArgAction::Append),
             ScSpecTypeDef::I256 | ScSpecTypeDef::I128 | ScSpecTypeDef::I64 | ScSpecTypeDef::I32 => {
                 arg.allow_hyphen_values(true)
             }
@@ -547,19 +1183,365 @@ fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
 
         cmd = cmd.arg(arg);
         cmd = cmd.arg(file_arg);
+        cmd = cmd.arg(xdr_arg);
     }
     Ok(cmd)
 }
 
+/// Split the values given for a `Vec` argument into its elements, which can be given by repeating
+/// the argument, and for scalar elements as a comma separated list, e.g. `--nums 1,2 --nums 3`.
+/// Values that start like JSON, with `{`, `[` or `"`, are never split. Returns `None` for a single
+/// JSON array, which is parsed as a whole so that nested and complex elements still work.
+fn split_vec_arg<'a>(values: &'a [String], type_: &ScSpecTypeDef) -> Option<Vec<&'a str>> {
+    if let [value] = values {
+        if value.trim_start().starts_with('[') {
+            return None;
+        }
+    }
+    let split = match type_ {
+        ScSpecTypeDef::Vec(vec) => is_scalar(&vec.element_type),
+        _ => false,
+    };
+    let mut elements = Vec::new();
+    for value in values.iter().map(|v| v.trim()) {
+        if split && !value.starts_with(['{', '[', '"']) {
+            elements.extend(value.split(',').map(str::trim));
+        } else {
+            elements.push(value);
+        }
+    }
+    Some(elements)
+}
+
+/// Whether values of `type_` are written as a single token without commas, so that a list of them
+/// can be split on commas.
+fn is_scalar(type_: &ScSpecTypeDef) -> bool {
+    matches!(
+        type_,
+        ScSpecTypeDef::Bool
+            | ScSpecTypeDef::U32
+            | ScSpecTypeDef::I32
+            | ScSpecTypeDef::U64
+            | ScSpecTypeDef::I64
+            | ScSpecTypeDef::U128
+            | ScSpecTypeDef::I128
+            | ScSpecTypeDef::U256
+            | ScSpecTypeDef::I256
+            | ScSpecTypeDef::Timepoint
+            | ScSpecTypeDef::Duration
+            | ScSpecTypeDef::Address
+            | ScSpecTypeDef::Symbol
+            | ScSpecTypeDef::Bytes
+            | ScSpecTypeDef::BytesN(_)
+    )
+}
+
+/// Resolve an identity name to its address, collecting its key to sign auth entries with. Other
+/// values, such as addresses, are returned unchanged.
+fn resolve_address(s: &str, config: &config::Args, signers: &mut Vec<SigningKey>) -> String {
+    let cmd = crate::commands::keys::address::Args {
+        name: s.to_string(),
+        hd_path: Some(0),
+        locator: config.locator.clone(),
+    };
+    if let Ok(key) = cmd.private_key() {
+        signers.push(key);
+    }
+    cmd.public_key()
+        .map_or_else(|_| s.to_string(), |address| address.to_string())
+}
+
 fn fmt_arg_file_name(name: &str) -> String {
     format!("{name}-file-path")
 }
 
+fn fmt_arg_xdr_name(name: &str) -> String {
+    format!("{name}-xdr")
+}
+
 fn arg_file_help(docs: &str) -> String {
     format!(
         r#"{docs}
 Usage Notes:
 Each arg has a corresponding --<arg_name>-file-path which is a path to a file containing the corresponding JSON argument.
-Note: The only types which aren't JSON are Bytes and BytesN, which are raw bytes"#
+Note: The only types which aren't JSON are Bytes and BytesN, which are raw bytes
+Each arg also has a corresponding --<arg_name>-xdr which takes the argument as a base64-encoded XDR ScVal, used as is."#
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ScSpecUdtErrorEnumCaseV0, ScSpecUdtErrorEnumV0};
+
+    fn spec() -> Spec {
+        Spec::new(vec![ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "Error".try_into().unwrap(),
+            cases: vec![ScSpecUdtErrorEnumCaseV0 {
+                doc: "Please provide an odd number".try_into().unwrap(),
+                name: "NumberMustBeOdd".try_into().unwrap(),
+                value: 1,
+            }]
+            .try_into()
+            .unwrap(),
+        })])
+    }
+
+    #[test]
+    fn check_args_rejects_wrong_count_and_types() {
+        let func = ScSpecFunctionV0 {
+            doc: StringM::default(),
+            name: "transfer".try_into().unwrap(),
+            inputs: vec![xdr::ScSpecFunctionInputV0 {
+                doc: StringM::default(),
+                name: "amount".try_into().unwrap(),
+                type_: ScSpecTypeDef::I128,
+            }]
+            .try_into()
+            .unwrap(),
+            outputs: VecM::default(),
+        };
+        let amount = ScVal::I128(xdr::Int128Parts { hi: 0, lo: 1 });
+        assert!(check_args(&func, std::slice::from_ref(&amount)).is_ok());
+        assert!(matches!(
+            check_args(&func, &[amount.clone(), amount]),
+            Err(Error::ArgMismatch { .. })
+        ));
+        let Err(Error::ArgMismatch { expected, got }) = check_args(&func, &[ScVal::U32(1)]) else {
+            panic!("expected a type mismatch");
+        };
+        assert_eq!(expected, "amount as I128");
+        assert_eq!(got, "U32");
+    }
+
+    #[test]
+    fn require_success_allows_ok_values() {
+        assert!(check_success(&spec(), &ScVal::U32(1)).is_ok());
+    }
+
+    #[test]
+    fn require_success_fails_on_contract_error() {
+        let res = check_success(&spec(), &ScVal::Error(ScError::Contract(1)));
+        let Err(Error::ContractInvoke(name, doc)) = res else {
+            panic!("expected contract error, got {res:?}");
+        };
+        assert_eq!(name, "NumberMustBeOdd");
+        assert_eq!(doc, "Please provide an odd number");
+    }
+
+    #[test]
+    fn require_success_fails_on_unknown_error() {
+        let res = check_success(&spec(), &ScVal::Error(ScError::Contract(7)));
+        assert!(matches!(
+            res,
+            Err(Error::ContractReturnedError(ScError::Contract(7)))
+        ));
+    }
+
+    #[test]
+    fn dry_run_summary_includes_resources() {
+        use soroban_env_host::xdr::{LedgerFootprint, ScSymbol, SorobanResources};
+        let mut tx = build_invoke_contract_tx(
+            InvokeContractArgs {
+                contract_address: ScAddress::Contract(Hash([0; 32])),
+                function_name: ScSymbol("hello".try_into().unwrap()),
+                args: VecM::default(),
+            },
+            1,
+            100,
+            Uint256([0; 32]),
+        )
+        .unwrap();
+        tx.ext = TransactionExt::V1(SorobanTransactionData {
+            ext: xdr::ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 10,
+                read_bytes: 20,
+                write_bytes: 30,
+            },
+            resource_fee: 40,
+        });
+        let cost = rpc::Cost {
+            cpu_insns: 1,
+            mem_bytes: 2,
+        };
+        let summary = dry_run_summary(Some(serde_json::json!("hi")), &cost, &tx).unwrap();
+        assert_eq!(
+            summary,
+            serde_json::json!({
+                "return_value": "hi",
+                "cost": { "cpu_instructions": 1, "memory_bytes": 2 },
+                "resources": {
+                    "instructions": 10,
+                    "read_bytes": 20,
+                    "write_bytes": 30,
+                    "resource_fee": 40,
+                },
+                "footprint": { "read_only": [], "read_write": [] },
+            })
+        );
+    }
+
+    #[test]
+    fn expect_compares_values_not_strings() {
+        use serde_json::json;
+        assert!(json_matches(&json!(42), &json!("42")));
+        assert!(json_matches(
+            &json!({ "a": [1, "x"], "b": null }),
+            &json!({ "b": null, "a": ["1", "x"] })
+        ));
+        assert!(!json_matches(&json!(42), &json!("43")));
+        assert!(!json_matches(&json!([1]), &json!([1, 2])));
+        assert!(!json_matches(&json!("Hello"), &json!("hello")));
+    }
+
+    #[test]
+    fn splits_vec_args_unless_given_as_json() {
+        use soroban_env_host::xdr::ScSpecTypeVec;
+        let values = |v: &[&str]| v.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let vec_of = |element_type| {
+            ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                element_type: Box::new(element_type),
+            }))
+        };
+        let nums = vec_of(ScSpecTypeDef::U32);
+        assert_eq!(
+            split_vec_arg(&values(&["1, 2", "3"]), &nums),
+            Some(vec!["1", "2", "3"])
+        );
+        assert_eq!(split_vec_arg(&values(&["1"]), &nums), Some(vec!["1"]));
+        assert_eq!(split_vec_arg(&values(&["[[1,2],[3]]"]), &nums), None);
+
+        let strings = vec_of(ScSpecTypeDef::String);
+        assert_eq!(
+            split_vec_arg(&values(&["a, b", "c"]), &strings),
+            Some(vec!["a, b", "c"])
+        );
+    }
+
+    #[test]
+    fn keeps_repeated_struct_elements_whole() {
+        use soroban_env_host::xdr::{ScSpecTypeUdt, ScSpecTypeVec};
+        let values = |v: &[&str]| v.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let points = ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+            element_type: Box::new(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                name: "Point".try_into().unwrap(),
+            })),
+        }));
+        assert_eq!(
+            split_vec_arg(&values(&[r#"{"x":1,"y":2}"#, r#"{"x":3,"y":4}"#]), &points),
+            Some(vec![r#"{"x":1,"y":2}"#, r#"{"x":3,"y":4}"#])
+        );
+        let addresses = ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+            element_type: Box::new(ScSpecTypeDef::Address),
+        }));
+        assert_eq!(
+            split_vec_arg(&values(&[r#""alice""#, "bob,carol"]), &addresses),
+            Some(vec![r#""alice""#, "bob", "carol"])
+        );
+    }
+
+    #[test]
+    fn merge_footprint_adds_missing_keys() {
+        use soroban_env_host::xdr::{LedgerKey, LedgerKeyContractCode, SorobanResources};
+        let key = |n: u8| {
+            LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: Hash([n; 32]),
+            })
+        };
+        let footprint = |read_only: Vec<LedgerKey>, read_write: Vec<LedgerKey>| LedgerFootprint {
+            read_only: read_only.try_into().unwrap(),
+            read_write: read_write.try_into().unwrap(),
+        };
+        let mut tx = build_invoke_contract_tx(
+            InvokeContractArgs {
+                contract_address: ScAddress::Contract(Hash([0; 32])),
+                function_name: xdr::ScSymbol("hello".try_into().unwrap()),
+                args: VecM::default(),
+            },
+            1,
+            100,
+            Uint256([0; 32]),
+        )
+        .unwrap();
+        tx.ext = TransactionExt::V1(SorobanTransactionData {
+            ext: xdr::ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: footprint(vec![key(1), key(2)], vec![key(3)]),
+                instructions: 0,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        });
+        let extra = footprint(vec![key(1), key(3), key(4)], vec![key(2), key(5)]);
+        assert_eq!(merge_footprint(&mut tx, &extra).unwrap(), 2);
+        let TransactionExt::V1(data) = &tx.ext else {
+            panic!("expected soroban transaction data");
+        };
+        assert_eq!(
+            data.resources.footprint,
+            footprint(vec![key(1), key(4)], vec![key(3), key(2), key(5)])
+        );
+    }
+
+    #[test]
+    fn read_only_calls_dont_change_state() {
+        use soroban_env_host::xdr::{
+            ContractEvent, ContractEventBody, ContractEventV0, ExtensionPoint, LedgerKey,
+            LedgerKeyContractCode, SorobanResources,
+        };
+        let mut tx = build_invoke_contract_tx(
+            InvokeContractArgs {
+                contract_address: ScAddress::Contract(Hash([0; 32])),
+                function_name: xdr::ScSymbol("hello".try_into().unwrap()),
+                args: VecM::default(),
+            },
+            1,
+            100,
+            Uint256([0; 32]),
+        )
+        .unwrap();
+        assert!(!changes_state(&tx, &[]));
+
+        let event = |type_| DiagnosticEvent {
+            in_successful_contract_call: true,
+            event: ContractEvent {
+                ext: ExtensionPoint::V0,
+                contract_id: None,
+                type_,
+                body: ContractEventBody::V0(ContractEventV0 {
+                    topics: VecM::default(),
+                    data: ScVal::Void,
+                }),
+            },
+        };
+        assert!(!changes_state(&tx, &[event(ContractEventType::Diagnostic)]));
+        assert!(changes_state(&tx, &[event(ContractEventType::Contract)]));
+
+        tx.ext = TransactionExt::V1(SorobanTransactionData {
+            ext: ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: vec![LedgerKey::ContractCode(LedgerKeyContractCode {
+                        hash: Hash([1; 32]),
+                    })]
+                    .try_into()
+                    .unwrap(),
+                },
+                instructions: 0,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        });
+        assert!(changes_state(&tx, &[]));
+    }
+}
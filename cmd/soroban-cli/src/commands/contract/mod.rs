@@ -2,6 +2,7 @@ pub mod asset;
 pub mod bindings;
 pub mod build;
 pub mod deploy;
+pub mod diff;
 pub mod extend;
 pub mod fetch;
 pub mod id;
@@ -29,11 +30,15 @@ pub enum Cmd {
     /// Extend the time to live ledger of a contract-data ledger entry.
     ///
     /// If no keys are specified the contract itself is extended.
+    #[command(alias = "bump")]
     Extend(extend::Cmd),
 
     /// Deploy a wasm contract
     Deploy(deploy::wasm::Cmd),
 
+    /// Compare the functions of two contracts' specs, failing if any were removed or changed
+    Diff(diff::Cmd),
+
     /// Fetch a contract's Wasm binary
     Fetch(fetch::Cmd),
 
@@ -44,7 +49,8 @@ pub enum Cmd {
     /// Initialize a Soroban project with an example contract
     Init(init::Cmd),
 
-    /// Inspect a WASM file listing contract functions, meta, etc
+    /// Inspect a WASM file listing contract functions, meta, etc. Use `--wasm -` to read the
+    /// WASM from stdin
     Inspect(inspect::Cmd),
 
     /// Install a WASM file to the ledger without creating a contract instance
@@ -68,7 +74,9 @@ pub enum Cmd {
 
     /// Restore an evicted value for a contract-data legder entry.
     ///
-    /// If no keys are specificed the contract itself is restored.
+    /// If no keys are specificed the contract itself is restored. Only the entries that the RPC
+    /// server reports as expired are restored, and the restored keys and fee charged are printed
+    /// to stderr.
     Restore(restore::Cmd),
 }
 
@@ -89,6 +97,9 @@ pub enum Error {
     #[error(transparent)]
     Deploy(#[from] deploy::wasm::Error),
 
+    #[error(transparent)]
+    Diff(#[from] diff::Error),
+
     #[error(transparent)]
     Fetch(#[from] fetch::Error),
 
@@ -117,6 +128,29 @@ pub enum Error {
     Restore(#[from] restore::Error),
 }
 
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Asset(e) => e.exit_code(),
+            Error::Extend(e) => e.exit_code(),
+            Error::Deploy(e) => e.exit_code(),
+            Error::Fetch(e) => e.exit_code(),
+            Error::Install(e) => e.exit_code(),
+            Error::Invoke(e) => e.exit_code(),
+            Error::Read(e) => e.exit_code(),
+            Error::Restore(e) => e.exit_code(),
+            Error::Bindings(_)
+            | Error::Build(_)
+            | Error::Diff(_)
+            | Error::Init(_)
+            | Error::Id(_)
+            | Error::Inspect(_)
+            | Error::Optimize(_) => crate::exit_code::FAILURE,
+        }
+    }
+}
+
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self {
@@ -125,6 +159,7 @@ impl Cmd {
             Cmd::Build(build) => build.run()?,
             Cmd::Extend(extend) => extend.run().await?,
             Cmd::Deploy(deploy) => deploy.run().await?,
+            Cmd::Diff(diff) => diff.run()?,
             Cmd::Id(id) => id.run()?,
             Cmd::Init(init) => init.run()?,
             Cmd::Inspect(inspect) => inspect.run()?,
@@ -164,4 +199,6 @@ pub enum SpecOutput {
     XdrBase64Array,
     /// Pretty print of contract spec entries
     Docs,
+    /// JSON array of contract spec entries, the same as `contract bindings json`
+    Json,
 }
@@ -19,6 +19,11 @@ pub struct Cmd {
 pub enum Error {
     #[error(transparent)]
     Wasm(#[from] wasm::Error),
+    #[error("writing file {path:?}: {error}")]
+    CannotWriteWasm {
+        path: std::path::PathBuf,
+        error: std::io::Error,
+    },
     #[cfg(feature = "opt")]
     #[error("optimization error: {0}")]
     OptimizationError(OptimizationError),
@@ -67,6 +72,15 @@ impl Cmd {
             .run(&self.wasm.wasm, &wasm_out)
             .map_err(Error::OptimizationError)?;
 
+        // wasm-opt keeps custom sections, such as debug info, that the network doesn't need.
+        // Remove them, except for the sections the contract's spec and metadata live in.
+        let optimized = wasm::Args::from(&wasm_out).read()?;
+        let stripped = wasm::strip_custom_sections(&optimized, &wasm::CONTRACT_SECTIONS)?;
+        std::fs::write(&wasm_out, stripped).map_err(|error| Error::CannotWriteWasm {
+            path: wasm_out.clone(),
+            error,
+        })?;
+
         let wasm_out_size = wasm::len(&wasm_out)?;
         println!(
             "Optimized: {} ({} bytes)",
@@ -12,13 +12,16 @@ use soroban_env_host::{
     HostError,
 };
 
+use soroban_spec_tools::Spec;
+
 use crate::{
     commands::{
         config::{self, locator},
-        global, NetworkRunnable,
+        events, global, NetworkRunnable,
     },
+    get_spec::{self, get_remote_contract_spec},
     key,
-    rpc::{self, Client, FullLedgerEntries, FullLedgerEntry},
+    rpc::{self, FullLedgerEntries, FullLedgerEntry},
 };
 
 #[derive(Parser, Debug, Clone)]
@@ -41,6 +44,9 @@ pub enum Output {
     Json,
     /// XDR
     Xdr,
+    /// JSON, with keys and values that match a struct or enum in the contract's spec decoded as
+    /// that type
+    Decoded,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -91,15 +97,58 @@ pub enum Error {
     OnlyDataAllowed,
     #[error(transparent)]
     Locator(#[from] locator::Error),
+    #[error(transparent)]
+    GetSpec(#[from] get_spec::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Config(e) => e.exit_code(),
+            _ => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         let entries = self.run_against_rpc_server(None, None).await?;
-        self.output_entries(&entries)
+        let spec = if self.output == Output::Decoded {
+            Some(self.spec().await?)
+        } else {
+            None
+        };
+        self.output_entries(&entries, spec.as_ref())
+    }
+
+    /// The spec of the contract that owns the entries, to decode them with
+    async fn spec(&self) -> Result<Spec, Error> {
+        let Some(contract_id) = &self.key.contract_id else {
+            return Err(Error::OnlyDataAllowed);
+        };
+        let network = self.config.get_network()?;
+        let contract = self
+            .config
+            .locator
+            .resolve_contract_id(contract_id, &network.network_passphrase)?;
+        let entries = get_remote_contract_spec(
+            &contract.0,
+            &self.config.locator,
+            &self.config.network,
+            None,
+            Some(&self.config),
+        )
+        .await?;
+        Ok(Spec::new(entries))
     }
 
-    fn output_entries(&self, entries: &FullLedgerEntries) -> Result<(), Error> {
+    fn output_entries(
+        &self,
+        entries: &FullLedgerEntries,
+        spec: Option<&Spec>,
+    ) -> Result<(), Error> {
         if entries.entries.is_empty() {
             return Err(Error::NoContractDataEntryFoundForContractID);
         }
@@ -164,6 +213,12 @@ impl Cmd {
                     last_modified_ledger.to_xdr_base64(Limits::none())?,
                     live_until_ledger_seq.to_xdr_base64(Limits::none())?,
                 ],
+                Output::Decoded => [
+                    decoded_json(spec, key)?,
+                    decoded_json(spec, val)?,
+                    last_modified_ledger.to_string(),
+                    live_until_ledger_seq.to_string(),
+                ],
             };
             out.write_record(output)
                 .map_err(|e| Error::CannotPrintAsCsv { error: e })?;
@@ -174,6 +229,17 @@ impl Cmd {
     }
 }
 
+fn decoded_json(spec: Option<&Spec>, val: &ScVal) -> Result<String, Error> {
+    let json = events::decode_value(spec, val).map_err(|error| Error::CannotPrintResult {
+        result: val.clone(),
+        error,
+    })?;
+    serde_json::to_string_pretty(&json).map_err(|error| Error::CannotPrintJsonResult {
+        result: val.clone(),
+        error,
+    })
+}
+
 #[async_trait::async_trait]
 impl NetworkRunnable for Cmd {
     type Error = Error;
@@ -187,7 +253,7 @@ impl NetworkRunnable for Cmd {
         let config = config.unwrap_or(&self.config);
         let network = config.get_network()?;
         tracing::trace!(?network);
-        let client = Client::new(&network.rpc_url)?;
+        let client = config.rpc_client(&network).await?;
         let contract = config.locator.resolve_contract_id(
             self.key.contract_id.as_ref().unwrap(),
             &network.network_passphrase,
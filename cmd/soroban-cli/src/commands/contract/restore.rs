@@ -3,9 +3,10 @@ use std::{fmt::Debug, path::Path, str::FromStr};
 use clap::{command, Parser};
 use soroban_env_host::xdr::{
     Error as XdrError, ExtensionPoint, LedgerEntry, LedgerEntryChange, LedgerEntryData,
-    LedgerFootprint, Limits, Memo, MuxedAccount, Operation, OperationBody, OperationMeta,
-    Preconditions, RestoreFootprintOp, SequenceNumber, SorobanResources, SorobanTransactionData,
-    Transaction, TransactionExt, TransactionMeta, TransactionMetaV3, TtlEntry, Uint256, WriteXdr,
+    LedgerFootprint, LedgerKey, LedgerKeyContractCode, LedgerKeyContractData, Limits, MuxedAccount,
+    Operation, OperationBody, OperationMeta, RestoreFootprintOp, SequenceNumber, SorobanResources,
+    SorobanTransactionData, Transaction, TransactionExt, TransactionMeta, TransactionMetaV3,
+    TtlEntry, Uint256, WriteXdr,
 };
 use stellar_strkey::DecodeError;
 
@@ -18,7 +19,8 @@ use crate::{
         NetworkRunnable,
     },
     key,
-    rpc::{self, Client},
+    rpc::{self, FullLedgerEntries},
+    utils::rpc::RetryingClient,
     wasm, Pwd,
 };
 
@@ -73,6 +75,8 @@ pub enum Error {
     Xdr(#[from] XdrError),
     #[error("Ledger entry not found")]
     LedgerEntryNotFound,
+    #[error("none of the ledger entries have expired, there is nothing to restore")]
+    NoExpiredEntries,
     #[error(transparent)]
     Locator(#[from] locator::Error),
     #[error("missing operation result")]
@@ -89,6 +93,21 @@ pub enum Error {
     Data(#[from] data::Error),
     #[error(transparent)]
     Network(#[from] network::Error),
+    #[error(transparent)]
+    Fee(#[from] crate::fee::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Config(e) => e.exit_code(),
+            Error::Network(e) => e.exit_code(),
+            Error::Fee(e) => e.exit_code(),
+            _ => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 impl Cmd {
@@ -97,7 +116,7 @@ impl Cmd {
         let res = self.run_against_rpc_server(None, None).await?.to_envelope();
         let expiration_ledger_seq = match res {
             TxnEnvelopeResult::TxnEnvelope(tx) => {
-                println!("{}", tx.to_xdr_base64(Limits::none())?);
+                println!("{}", self.fee.tx_output(&tx)?);
                 return Ok(());
             }
             TxnEnvelopeResult::Res(res) => res,
@@ -137,8 +156,8 @@ impl NetworkRunnable for Cmd {
             self.key.contract_id.as_ref().unwrap(),
             &network.network_passphrase,
         )?;
-        let entry_keys = self.key.parse_keys(contract)?;
-        let client = Client::new(&network.rpc_url)?;
+        let client = config.rpc_client(&network).await?;
+        let entry_keys = expired_keys(&client, self.key.parse_keys(contract)?).await?;
         let key = config.key_pair()?;
 
         // Get the account sequence number
@@ -147,12 +166,12 @@ impl NetworkRunnable for Cmd {
         let account_details = client.get_account(&public_strkey).await?;
         let sequence: i64 = account_details.seq_num.into();
 
-        let tx = Transaction {
+        let mut tx = Transaction {
             source_account: MuxedAccount::Ed25519(Uint256(key.verifying_key().to_bytes())),
             fee: self.fee.fee,
             seq_num: SequenceNumber(sequence + 1),
-            cond: Preconditions::None,
-            memo: Memo::None,
+            cond: self.fee.preconditions(&client).await?,
+            memo: self.fee.memo(),
             operations: vec![Operation {
                 source_account: None,
                 body: OperationBody::RestoreFootprint(RestoreFootprintOp {
@@ -165,7 +184,7 @@ impl NetworkRunnable for Cmd {
                 resources: SorobanResources {
                     footprint: LedgerFootprint {
                         read_only: vec![].try_into()?,
-                        read_write: entry_keys.try_into()?,
+                        read_write: entry_keys.clone().try_into()?,
                     },
                     instructions: self.fee.instructions.unwrap_or_default(),
                     read_bytes: 0,
@@ -174,7 +193,8 @@ impl NetworkRunnable for Cmd {
                 resource_fee: 0,
             }),
         };
-        if self.fee.build_only {
+        config.apply_fee_source(&mut tx, &client).await?;
+        if self.fee.is_build_only() {
             return Ok(TxnResult::Txn(tx));
         }
         let res = client
@@ -187,10 +207,16 @@ impl NetworkRunnable for Cmd {
             .result_meta
             .as_ref()
             .ok_or(Error::MissingOperationResult)?;
+        for key in &entry_keys {
+            eprintln!("Restored {}", describe_key(key)?);
+        }
+        if let Some(result) = &res.result {
+            eprintln!("Fee charged: {} stroops", result.fee_charged);
+        }
         let events = res.events()?;
         tracing::trace!(?meta);
         if !events.is_empty() {
-            tracing::info!("Events:\n {events:#?}");
+            tracing::warn!("Events:\n {events:#?}");
         }
 
         // The transaction from core will succeed regardless of whether it actually found &
@@ -218,6 +244,45 @@ impl NetworkRunnable for Cmd {
     }
 }
 
+/// Keep the keys of entries whose TTL has passed, using the RPC server's view of the ledger, as
+/// only those can be restored.
+async fn expired_keys(
+    client: &RetryingClient,
+    keys: Vec<LedgerKey>,
+) -> Result<Vec<LedgerKey>, Error> {
+    let FullLedgerEntries {
+        entries,
+        latest_ledger,
+    } = client.get_full_ledger_entries(&keys).await?;
+    let expired = entries
+        .into_iter()
+        .filter(|entry| i64::from(entry.live_until_ledger_seq) < latest_ledger)
+        .map(|entry| entry.key)
+        .collect::<Vec<_>>();
+    if expired.is_empty() {
+        return Err(Error::NoExpiredEntries);
+    }
+    Ok(expired)
+}
+
+/// A readable name for a restored entry: the storage key as JSON, or the hash of contract code.
+fn describe_key(key: &LedgerKey) -> Result<String, Error> {
+    Ok(match key {
+        LedgerKey::ContractData(LedgerKeyContractData { key, .. }) => {
+            soroban_spec_tools::to_json(key)
+                .map_err(|error| Error::CannotParseKey {
+                    key: format!("{key:?}"),
+                    error,
+                })?
+                .to_string()
+        }
+        LedgerKey::ContractCode(LedgerKeyContractCode { hash }) => {
+            format!("contract code {}", hex::encode(hash.0))
+        }
+        _ => key.to_xdr_base64(Limits::none())?,
+    })
+}
+
 fn parse_operations(ops: &[OperationMeta]) -> Option<u32> {
     ops.first().and_then(|op| {
         op.changes.iter().find_map(|entry| match entry {
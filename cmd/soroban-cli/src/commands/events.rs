@@ -1,34 +1,67 @@
 use clap::{arg, command, Parser};
-use std::io;
+use std::{collections::HashMap, fs, io, path::PathBuf};
+use termcolor::{Color, ColorChoice, ColorSpec, NoColor, StandardStream, WriteColor};
 
-use soroban_env_host::xdr::{self, Limits, ReadXdr};
+use soroban_env_host::xdr::{
+    self, Limits, ReadXdr, ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtUnionCaseV0,
+    StringM, WriteXdr,
+};
+use soroban_spec_tools::Spec;
 
 use super::{
     config::{self, locator},
     global, network, NetworkRunnable,
 };
-use crate::rpc;
+use crate::{get_spec::get_remote_contract_spec, rpc, utils::rpc::RetryingClient};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     /// The first ledger sequence number in the range to pull events
     /// https://developers.stellar.org/docs/encyclopedia/ledger-headers#ledger-sequence
-    #[arg(long, conflicts_with = "cursor", required_unless_present = "cursor")]
+    #[arg(
+        long,
+        alias = "from-ledger",
+        conflicts_with = "cursor",
+        required_unless_present_any = ["cursor", "last_ledgers"]
+    )]
     start_ledger: Option<u32>,
+    /// The last ledger sequence number in the range to pull events, inclusive. Events in later
+    /// ledgers are not shown, and paging stops once they are reached.
+    #[arg(long)]
+    to_ledger: Option<u32>,
     /// The cursor corresponding to the start of the event range.
     #[arg(
         long,
         conflicts_with = "start_ledger",
-        required_unless_present = "start_ledger"
+        required_unless_present_any = ["start_ledger", "last_ledgers"]
     )]
     cursor: Option<String>,
+    /// Start this many ledgers before the latest ledger, instead of at a given ledger or cursor
+    #[arg(long, conflicts_with_all = ["start_ledger", "cursor"])]
+    last_ledgers: Option<u32>,
     /// Output formatting options for event stream
     #[arg(long, value_enum, default_value = "pretty")]
     output: OutputFormat,
-    /// The maximum number of events to display (defer to the server-defined limit).
+    /// The maximum number of events to display. Pages through the RPC
+    /// server's results until this many events are found or there are no
+    /// more events. Use 0 to defer to the server-defined limit for a single page.
     #[arg(short, long, default_value = "10")]
     count: usize,
+    /// After the events, print the paging token of the last event so it can be passed back
+    /// as `--cursor` to continue from there. Written to stderr, or as a final `{"cursor": ...}`
+    /// object on stdout with `--output json`.
+    #[arg(long)]
+    emit_cursor: bool,
+    /// Show each event's topics and value as JSON. When a topic or the value matches a struct
+    /// or union in the contract's spec, from --wasm or fetched from the network by contract ID,
+    /// it is shown as that type.
+    #[arg(long)]
+    decode_value: bool,
+    /// Path to the wasm of the contract whose spec is used to decode event topics and values.
+    /// Implies --decode-value
+    #[arg(long)]
+    wasm: Option<PathBuf>,
     /// A set of (up to 5) contract IDs to filter events on. This parameter can
     /// be passed multiple times, e.g. `--id C123.. --id C456..`, or passed with
     /// multiple parameters, e.g. `--id C123 C456`.
@@ -45,6 +78,8 @@ pub struct Cmd {
     /// A set of (up to 4) topic filters to filter event topics on. A single
     /// topic filter can contain 1-4 different segment filters, separated by
     /// commas, with an asterisk (* character) indicating a wildcard segment.
+    /// A segment is either a base64-encoded XDR `ScVal` or a JSON value, where a
+    /// JSON string is a symbol and other values are `ScVal`s as JSON.
     ///
     /// For example, this is one topic filter with two segments:
     ///
@@ -54,6 +89,10 @@ pub struct Cmd {
     ///
     ///     --topic "AAAABQAAAAdDT1VOVEVSAA==" --topic '*,*'
     ///
+    /// And this is one topic filter with two JSON segments:
+    ///
+    ///     --topic '"transfer",{"u32":5}'
+    ///
     /// Note that all of these topic filters are combined with the contract IDs
     /// into a single filter (i.e. combination of type, IDs, and topics).
     #[arg(
@@ -102,8 +141,15 @@ pub enum Error {
         debug: String,
         error: serde_json::Error,
     },
+    #[error("cannot print as csv: {error}")]
+    CannotPrintAsCsv { error: csv::Error },
     #[error("invalid timestamp in event: {ts}")]
     InvalidTimestamp { ts: String },
+    #[error(
+        "--last-ledgers {last_ledgers} reaches back past the RPC server's oldest ledger, it only \
+         keeps the last {retention} ledgers"
+    )]
+    BeyondRetentionWindow { last_ledgers: u32, retention: u32 },
     #[error("missing start_ledger and cursor")]
     MissingStartLedgerAndCursor,
     #[error("missing target")]
@@ -124,6 +170,30 @@ pub enum Error {
     Locator(#[from] locator::Error),
     #[error(transparent)]
     Config(#[from] config::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Network(e) => e.exit_code(),
+            Error::Config(e) => e.exit_code(),
+            Error::InvalidCursor
+            | Error::InvalidFile { .. }
+            | Error::CannotReadFile { .. }
+            | Error::InvalidTopicFilter { .. }
+            | Error::InvalidSegment { .. }
+            | Error::InvalidContractId { .. }
+            | Error::BeyondRetentionWindow { .. }
+            | Error::MissingStartLedgerAndCursor
+            | Error::MissingTarget
+            | Error::Locator(_) => crate::exit_code::USAGE,
+            _ => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum)]
@@ -134,42 +204,73 @@ pub enum OutputFormat {
     Plain,
     /// JSONified console output
     Json,
+    /// One compact JSON object per line (NDJSON), for streaming consumers
+    JsonStream,
+    /// A header row, then one row per event with its ledger, ledger close time, contract, type,
+    /// topics joined with `;`, and value. Topics and the value are decoded
+    Csv,
 }
 
+/// Columns of the CSV output format.
+const CSV_HEADER: [&str; 6] = [
+    "ledger",
+    "ledger_closed_at",
+    "contract_id",
+    "event_type",
+    "topics",
+    "value",
+];
+
 impl Cmd {
     pub async fn run(&mut self) -> Result<(), Error> {
-        // Validate that topics are made up of segments.
-        for topic in &self.topic_filters {
-            for (i, segment) in topic.split(',').enumerate() {
-                if i > 4 {
-                    return Err(Error::InvalidTopicFilter {
-                        topic: topic.to_string(),
-                    });
-                }
-
-                if segment != "*" {
-                    if let Err(e) = xdr::ScVal::from_xdr_base64(segment, Limits::none()) {
-                        return Err(Error::InvalidSegment {
-                            topic: topic.to_string(),
-                            segment: segment.to_string(),
-                            error: e,
-                        });
-                    }
-                }
-            }
-        }
-
         let response = self.run_against_rpc_server(None, None).await?;
+        let wasm_spec = self.wasm_spec()?;
+        let mut fetched_specs = HashMap::new();
+        let mut csv = csv::Writer::from_writer(io::stdout());
+        if self.output == OutputFormat::Csv {
+            csv.write_record(CSV_HEADER)
+                .map_err(|error| Error::CannotPrintAsCsv { error })?;
+        }
 
         for event in &response.events {
+            let decoded = if self.decode_value || wasm_spec.is_some() {
+                let spec = if wasm_spec.is_some() {
+                    wasm_spec.as_ref()
+                } else {
+                    self.fetch_spec(&mut fetched_specs, &event.contract_id)
+                        .await
+                };
+                match Decoded::new(spec, event) {
+                    Ok(decoded) => Some(decoded),
+                    Err(e) => {
+                        tracing::warn!("couldn't decode event {}: {e}", event.paging_token);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
             match self.output {
                 // Should we pretty-print the JSON like we're doing here or just
                 // dump an event in raw JSON on each line? The latter is easier
                 // to consume programmatically.
                 OutputFormat::Json => {
+                    let json = event_json(event, decoded)?;
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&json).map_err(|e| {
+                            Error::InvalidJson {
+                                debug: format!("{event:#?}"),
+                                error: e,
+                            }
+                        })?,
+                    );
+                }
+                OutputFormat::JsonStream => {
+                    let json = event_json(event, decoded)?;
                     println!(
                         "{}",
-                        serde_json::to_string_pretty(&event).map_err(|e| {
+                        serde_json::to_string(&json).map_err(|e| {
                             Error::InvalidJson {
                                 debug: format!("{event:#?}"),
                                 error: e,
@@ -177,19 +278,127 @@ impl Cmd {
                         })?,
                     );
                 }
-                OutputFormat::Plain => println!("{event}"),
-                OutputFormat::Pretty => event.pretty_print()?,
+                OutputFormat::Plain => {
+                    write_event(&mut NoColor::new(io::stdout()), event, decoded.as_ref())?;
+                }
+                OutputFormat::Pretty => {
+                    write_event(
+                        &mut StandardStream::stdout(ColorChoice::Auto),
+                        event,
+                        decoded.as_ref(),
+                    )?;
+                }
+                OutputFormat::Csv => {
+                    csv.write_record(event_csv_record(event, decoded.as_ref())?)
+                        .map_err(|error| Error::CannotPrintAsCsv { error })?;
+                }
+            }
+        }
+        csv.flush()?;
+        if matches!(self.output, OutputFormat::JsonStream | OutputFormat::Csv) {
+            // Keep stdout to one JSON object per line, or to CSV rows
+            eprintln!("Latest Ledger: {}", response.latest_ledger);
+        } else {
+            println!("Latest Ledger: {}", response.latest_ledger);
+        }
+
+        if self.emit_cursor {
+            if let Some(last) = response.events.last() {
+                let cursor = serde_json::json!({ "cursor": last.paging_token });
+                match self.output {
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&cursor)?),
+                    OutputFormat::Plain
+                    | OutputFormat::Pretty
+                    | OutputFormat::JsonStream
+                    | OutputFormat::Csv => {
+                        eprintln!("Cursor: {}", last.paging_token);
+                    }
+                }
             }
         }
-        println!("Latest Ledger: {}", response.latest_ledger);
 
         Ok(())
     }
 
-    fn start(&self) -> Result<rpc::EventStart, Error> {
-        let start = match (self.start_ledger, self.cursor.clone()) {
-            (Some(start), _) => rpc::EventStart::Ledger(start),
-            (_, Some(c)) => rpc::EventStart::Cursor(c),
+    /// Resolve the network to query, preferring an explicitly passed config,
+    /// then `--network` from the config dir, then `--rpc-url` and `--network-passphrase`.
+    pub fn network(&self, config: Option<&config::Args>) -> Result<network::Network, Error> {
+        Ok(if let Some(config) = config {
+            config.get_network()?
+        } else {
+            self.network.get(&self.locator)?
+        })
+    }
+
+    /// The spec from --wasm, used for every event instead of fetching each contract's spec.
+    fn wasm_spec(&self) -> Result<Option<Spec>, Error> {
+        let Some(wasm) = &self.wasm else {
+            return Ok(None);
+        };
+        let bytes = fs::read(wasm).map_err(|e| Error::CannotReadFile {
+            path: wasm.display().to_string(),
+            error: e.to_string(),
+        })?;
+        Ok(Some(Spec::from_wasm(&bytes)?))
+    }
+
+    /// The spec of `contract_id` fetched from the network, once per contract. A spec that can't
+    /// be fetched, such as for system events which have no contract, is treated as missing so
+    /// values fall back to the generic JSON form.
+    async fn fetch_spec<'a>(
+        &self,
+        specs: &'a mut HashMap<String, Option<Spec>>,
+        contract_id: &str,
+    ) -> Option<&'a Spec> {
+        if !specs.contains_key(contract_id) {
+            let spec = match stellar_strkey::Contract::from_string(contract_id) {
+                Ok(id) => get_remote_contract_spec(&id.0, &self.locator, &self.network, None, None)
+                    .await
+                    .map(Spec::new)
+                    .map_err(|e| tracing::debug!("cannot fetch spec of {contract_id}: {e}"))
+                    .ok(),
+                Err(_) => None,
+            };
+            specs.insert(contract_id.to_string(), spec);
+        }
+        specs[contract_id].as_ref()
+    }
+
+    /// Topic filters with every segment as base64 XDR, the form the RPC server expects.
+    fn topic_filters(&self) -> Result<Vec<String>, Error> {
+        self.topic_filters
+            .iter()
+            .map(|topic| {
+                let segments = split_segments(topic);
+                if segments.len() > 4 {
+                    return Err(Error::InvalidTopicFilter {
+                        topic: topic.clone(),
+                    });
+                }
+                Ok(segments
+                    .into_iter()
+                    .map(|segment| segment_to_xdr(topic, segment))
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .join(","))
+            })
+            .collect()
+    }
+
+    async fn start(&self, client: &RetryingClient) -> Result<rpc::EventStart, Error> {
+        let start = match (self.start_ledger, self.cursor.clone(), self.last_ledgers) {
+            (Some(start), _, _) => rpc::EventStart::Ledger(start),
+            (_, Some(c), _) => rpc::EventStart::Cursor(c),
+            (_, _, Some(last_ledgers)) => {
+                let latest = client.get_latest_ledger().await?.sequence;
+                // Older servers don't report their oldest ledger, in which case the server
+                // reports going out of range itself
+                let oldest = client
+                    .get_health()
+                    .await
+                    .ok()
+                    .and_then(|health| health.oldest_ledger);
+                rpc::EventStart::Ledger(look_back(latest, oldest, last_ledgers)?)
+            }
             // should never happen because of required_unless_present flags
             _ => return Err(Error::MissingStartLedgerAndCursor),
         };
@@ -207,17 +416,11 @@ impl NetworkRunnable for Cmd {
         _args: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<rpc::GetEventsResponse, Error> {
-        let start = self.start()?;
-        let network = if let Some(config) = config {
-            Ok(config.get_network()?)
-        } else {
-            self.network.get(&self.locator)
-        }?;
-
-        let client = rpc::Client::new(&network.rpc_url)?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
-            .await?;
+        let network = self.network(config)?;
+        let options =
+            config.map_or_else(|| self.network.rpc_options(), |c| c.network.rpc_options());
+        let client = network.rpc_client(&options).await?;
+        let start = self.start(&client).await?;
 
         let contract_ids: Vec<String> = self
             .contract_ids
@@ -229,16 +432,624 @@ impl NetworkRunnable for Cmd {
                     .to_string())
             })
             .collect::<Result<Vec<_>, Error>>()?;
+        let topic_filters = self.topic_filters()?;
 
-        Ok(client
-            .get_events(
+        if self.count == 0 {
+            let mut response = client
+                .get_events(
+                    start,
+                    Some(self.event_type),
+                    &contract_ids,
+                    &topic_filters,
+                    None,
+                )
+                .await?;
+            response
+                .events
+                .retain(|event| before_end(event, self.to_ledger));
+            return Ok(response);
+        }
+        paginate(start, self.count, self.to_ledger, |start, limit| {
+            client.get_events(
                 start,
                 Some(self.event_type),
                 &contract_ids,
-                &self.topic_filters,
-                Some(self.count),
+                &topic_filters,
+                Some(limit),
             )
-            .await
-            .map_err(Error::Rpc)?)
+        })
+        .await
+    }
+}
+
+/// The ledger `last_ledgers` before `latest`, failing if it's older than the `oldest` ledger
+/// the RPC server keeps.
+fn look_back(latest: u32, oldest: Option<u32>, last_ledgers: u32) -> Result<u32, Error> {
+    let start = latest.saturating_sub(last_ledgers);
+    match oldest {
+        Some(oldest) if start < oldest => Err(Error::BeyondRetentionWindow {
+            last_ledgers,
+            retention: latest - oldest,
+        }),
+        _ => Ok(start),
+    }
+}
+
+/// Request pages of events, continuing from the last event's paging token,
+/// until `count` events are collected, a page comes back empty, or a page
+/// reaches past the `end` ledger. The latest ledger is taken from the last
+/// response.
+async fn paginate<F, Fut>(
+    mut start: rpc::EventStart,
+    count: usize,
+    end: Option<u32>,
+    mut get_events: F,
+) -> Result<rpc::GetEventsResponse, Error>
+where
+    F: FnMut(rpc::EventStart, usize) -> Fut,
+    Fut: std::future::Future<Output = Result<rpc::GetEventsResponse, rpc::Error>>,
+{
+    let mut events = Vec::with_capacity(count);
+    loop {
+        let response = get_events(start, count - events.len()).await?;
+        let Some(last) = response.events.last() else {
+            return Ok(rpc::GetEventsResponse {
+                events,
+                latest_ledger: response.latest_ledger,
+            });
+        };
+        start = rpc::EventStart::Cursor(last.paging_token.clone());
+        let past_end = !before_end(last, end);
+        events.extend(
+            response
+                .events
+                .into_iter()
+                .take_while(|event| before_end(event, end))
+                .take(count - events.len()),
+        );
+        if events.len() == count || past_end {
+            return Ok(rpc::GetEventsResponse {
+                events,
+                latest_ledger: response.latest_ledger,
+            });
+        }
+    }
+}
+
+/// Write an event in the plain or pretty format with the RPC client's formatter, in color if
+/// `out` supports it, followed by its `decoded` topics and value when given.
+fn write_event(
+    out: &mut impl WriteColor,
+    event: &rpc::Event,
+    decoded: Option<&Decoded>,
+) -> Result<(), Error> {
+    let color = out.supports_color();
+    if color {
+        // Writes to stdout itself, which is where `out` writes when it supports color
+        event.pretty_print()?;
+    } else {
+        write!(out, "{event}")?;
+    }
+    if let Some(decoded) = decoded {
+        let mut highlight = ColorSpec::new();
+        highlight.set_fg(Some(Color::Green));
+        writeln!(out, "  Decoded topics:")?;
+        for topic in &decoded.topics {
+            write!(out, "            ")?;
+            out.set_color(&highlight)?;
+            write!(out, "{}", decoded_topic_to_string(topic))?;
+            out.reset()?;
+            writeln!(out)?;
+        }
+        write!(out, "  Decoded value:  ")?;
+        out.set_color(&highlight)?;
+        write!(out, "{}", decoded.value)?;
+        out.reset()?;
+        writeln!(out)?;
+    }
+    if !color {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// An event as a CSV record with the columns of [`CSV_HEADER`]. Topics are joined with `;`, and
+/// the topics and value are written as `decoded` JSON when given, or else decoded generically.
+fn event_csv_record(event: &rpc::Event, decoded: Option<&Decoded>) -> Result<[String; 6], Error> {
+    let topics = if let Some(decoded) = decoded {
+        decoded.topics.iter().map(decoded_topic_to_string).collect()
+    } else {
+        event
+            .topic
+            .iter()
+            .map(|topic| {
+                let topic = xdr::ScVal::from_xdr_base64(topic, Limits::none())?;
+                Ok(soroban_spec_tools::to_string(&topic)?)
+            })
+            .collect::<Result<Vec<_>, Error>>()?
+    }
+    .join(";");
+    let value = if let Some(decoded) = decoded {
+        decoded.value.to_string()
+    } else {
+        let value = xdr::ScVal::from_xdr_base64(&event.value, Limits::none())?;
+        soroban_spec_tools::to_string(&value)?
+    };
+    Ok([
+        event.ledger.to_string(),
+        event.ledger_closed_at.clone(),
+        event.contract_id.clone(),
+        event.event_type.clone(),
+        topics,
+        value,
+    ])
+}
+
+/// An event as JSON, with the `decoded` topics and value added as `decodedTopics` and
+/// `decodedValue` when given.
+fn event_json(event: &rpc::Event, decoded: Option<Decoded>) -> Result<serde_json::Value, Error> {
+    let mut json = serde_json::to_value(event)?;
+    if let (Some(decoded), Some(object)) = (decoded, json.as_object_mut()) {
+        object.insert("decodedTopics".to_string(), decoded.topics.into());
+        object.insert("decodedValue".to_string(), decoded.value);
+    }
+    Ok(json)
+}
+
+/// An event's topics and value decoded to JSON, for `--decode-value` or `--wasm`.
+struct Decoded {
+    topics: Vec<serde_json::Value>,
+    value: serde_json::Value,
+}
+
+impl Decoded {
+    /// Decode each of `event`'s topics and its value with [`decode_value`]. Contract specs don't
+    /// describe events, so each one is matched against the spec's types on its own, and falls
+    /// back to generic JSON when none matches.
+    fn new(spec: Option<&Spec>, event: &rpc::Event) -> Result<Self, Error> {
+        let topics = event
+            .topic
+            .iter()
+            .map(|topic| {
+                let topic = xdr::ScVal::from_xdr_base64(topic, Limits::none())?;
+                Ok(decode_value(spec, &topic)?)
+            })
+            .collect::<Result<_, Error>>()?;
+        let value = xdr::ScVal::from_xdr_base64(&event.value, Limits::none())?;
+        Ok(Self {
+            topics,
+            value: decode_value(spec, &value)?,
+        })
+    }
+}
+
+/// Decode a value of unknown type, such as an event value or a stored value, to JSON. Contracts
+/// don't declare the types of these values, so a type from `spec` is used only when the value's
+/// shape matches one of its structs or unions; otherwise, or if the value turns out not to fit
+/// that type, the value is converted to JSON generically.
+pub(crate) fn decode_value(
+    spec: Option<&Spec>,
+    value: &xdr::ScVal,
+) -> Result<serde_json::Value, soroban_spec_tools::Error> {
+    if let Some((spec, name)) = spec.and_then(|spec| Some((spec, matching_udt(spec, value)?))) {
+        match spec.xdr_to_json(value, &ScSpecTypeDef::Udt(ScSpecTypeUdt { name })) {
+            Ok(json) => return Ok(json),
+            Err(e) => tracing::debug!("decoding {value:?} with the contract spec: {e}"),
+        }
+    }
+    soroban_spec_tools::to_json(value)
+}
+
+/// The name of the struct in `spec` with exactly the fields of a map `value`, or of the union in
+/// `spec` with the case named by the symbol starting a vec `value`.
+fn matching_udt(spec: &Spec, value: &xdr::ScVal) -> Option<StringM<60>> {
+    spec.0
+        .as_ref()?
+        .iter()
+        .find_map(|entry| match (entry, value) {
+            (ScSpecEntry::UdtStructV0(strukt), xdr::ScVal::Map(Some(map))) => {
+                let same_fields = strukt.fields.len() == map.len()
+                    && strukt.fields.iter().zip(map.iter()).all(|(field, entry)| {
+                        match &entry.key {
+                            xdr::ScVal::Symbol(key) => key.as_vec() == field.name.as_vec(),
+                            _ => false,
+                        }
+                    });
+                same_fields.then(|| strukt.name.clone())
+            }
+            (ScSpecEntry::UdtUnionV0(union), xdr::ScVal::Vec(Some(vec))) => {
+                let Some(xdr::ScVal::Symbol(case_name)) = vec.first() else {
+                    return None;
+                };
+                union
+                    .cases
+                    .iter()
+                    .any(|case| {
+                        let name = match case {
+                            ScSpecUdtUnionCaseV0::VoidV0(v) => &v.name,
+                            ScSpecUdtUnionCaseV0::TupleV0(v) => &v.name,
+                        };
+                        name.as_vec() == case_name.as_vec()
+                    })
+                    .then(|| union.name.clone())
+            }
+            _ => None,
+        })
+}
+
+/// A decoded topic as text: strings, such as symbol topics, without their quotes, and any other
+/// JSON as is.
+fn decoded_topic_to_string(topic: &serde_json::Value) -> String {
+    match topic {
+        serde_json::Value::String(string) => string.clone(),
+        _ => topic.to_string(),
+    }
+}
+
+/// Whether `event` is in a ledger no later than `end`, if there is one. The RPC server has no
+/// end ledger parameter, so the bound is enforced here.
+fn before_end(event: &rpc::Event, end: Option<u32>) -> bool {
+    end.map_or(true, |end| event.ledger <= end)
+}
+
+/// Split a topic filter into segments on the commas that aren't inside a JSON segment.
+fn split_segments(topic: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    let (mut depth, mut in_string, mut escaped, mut start) = (0, false, false, 0);
+    for (i, c) in topic.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' | '[' if !in_string => depth += 1,
+            '}' | ']' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                segments.push(&topic[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&topic[start..]);
+    segments
+}
+
+/// Convert a topic segment to base64 XDR, trying it as JSON first and falling back to base64.
+fn segment_to_xdr(topic: &str, segment: &str) -> Result<String, Error> {
+    if segment == "*" {
+        return Ok(segment.to_string());
+    }
+    if let Some(val) = json_segment(segment) {
+        return Ok(val.to_xdr_base64(Limits::none())?);
+    }
+    xdr::ScVal::from_xdr_base64(segment, Limits::none()).map_err(|error| {
+        Error::InvalidSegment {
+            topic: topic.to_string(),
+            segment: segment.to_string(),
+            error,
+        }
+    })?;
+    Ok(segment.to_string())
+}
+
+fn json_segment(segment: &str) -> Option<xdr::ScVal> {
+    let value: serde_json::Value = serde_json::from_str(segment).ok()?;
+    let type_ = if value.is_string() {
+        ScSpecTypeDef::Symbol
+    } else {
+        ScSpecTypeDef::Val
+    };
+    soroban_spec_tools::Spec::default()
+        .from_json(&value, &type_)
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(ledger: u32) -> rpc::Event {
+        rpc::Event {
+            event_type: "contract".to_string(),
+            ledger,
+            ledger_closed_at: String::new(),
+            id: ledger.to_string(),
+            paging_token: ledger.to_string(),
+            contract_id: String::new(),
+            topic: vec![],
+            value: String::new(),
+        }
+    }
+
+    /// Serves events from `available`, at most `page_size` at a time.
+    #[allow(clippy::unused_async)]
+    async fn get_events(
+        available: &[rpc::Event],
+        page_size: usize,
+        start: rpc::EventStart,
+        limit: usize,
+    ) -> Result<rpc::GetEventsResponse, rpc::Error> {
+        let skip = match start {
+            rpc::EventStart::Ledger(_) => 0,
+            rpc::EventStart::Cursor(c) => {
+                available.iter().position(|e| e.paging_token == c).unwrap() + 1
+            }
+        };
+        Ok(rpc::GetEventsResponse {
+            events: available
+                .iter()
+                .skip(skip)
+                .take(limit.min(page_size))
+                .cloned()
+                .collect(),
+            latest_ledger: 100,
+        })
+    }
+
+    #[test]
+    fn look_back_stays_in_retention_window() {
+        assert_eq!(look_back(1000, Some(900), 50).unwrap(), 950);
+        assert_eq!(look_back(1000, None, 5000).unwrap(), 0);
+        assert!(matches!(
+            look_back(1000, Some(900), 200),
+            Err(Error::BeyondRetentionWindow {
+                last_ledgers: 200,
+                retention: 100
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn count_pages_until_reached() {
+        let available = (1..=25).map(event).collect::<Vec<_>>();
+        let response = paginate(rpc::EventStart::Ledger(1), 12, None, |start, limit| {
+            get_events(&available, 5, start, limit)
+        })
+        .await
+        .unwrap();
+        assert_eq!(response.events.len(), 12);
+        assert_eq!(response.events.last().unwrap().ledger, 12);
+        assert_eq!(response.latest_ledger, 100);
+    }
+
+    #[tokio::test]
+    async fn count_stops_when_exhausted() {
+        let available = (1..=7).map(event).collect::<Vec<_>>();
+        let response = paginate(rpc::EventStart::Ledger(1), 10, None, |start, limit| {
+            get_events(&available, 5, start, limit)
+        })
+        .await
+        .unwrap();
+        assert_eq!(response.events.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn count_stops_at_end_ledger() {
+        let available = (1..=25).map(event).collect::<Vec<_>>();
+        let mut pages = 0;
+        let response = paginate(rpc::EventStart::Ledger(1), 20, Some(8), |start, limit| {
+            pages += 1;
+            get_events(&available, 5, start, limit)
+        })
+        .await
+        .unwrap();
+        assert_eq!(response.events.len(), 8);
+        assert_eq!(response.events.last().unwrap().ledger, 8);
+        assert_eq!(pages, 2);
+    }
+
+    #[test]
+    fn plain_output_uses_rpc_event_format() {
+        let event = rpc::Event {
+            topic: vec![
+                xdr::ScVal::Symbol("transfer".try_into().unwrap())
+                    .to_xdr_base64(Limits::none())
+                    .unwrap(),
+                xdr::ScVal::U32(5).to_xdr_base64(Limits::none()).unwrap(),
+            ],
+            value: xdr::ScVal::Void.to_xdr_base64(Limits::none()).unwrap(),
+            contract_id: "C123".to_string(),
+            ..event(7)
+        };
+        let mut out = NoColor::new(Vec::new());
+        write_event(&mut out, &event, None).unwrap();
+        assert_eq!(
+            String::from_utf8(out.into_inner()).unwrap(),
+            format!("{event}\n")
+        );
+    }
+
+    #[test]
+    fn csv_output_decodes_topics_and_quotes_fields() {
+        let event = rpc::Event {
+            topic: vec![
+                xdr::ScVal::Symbol("transfer".try_into().unwrap())
+                    .to_xdr_base64(Limits::none())
+                    .unwrap(),
+                xdr::ScVal::U32(5).to_xdr_base64(Limits::none()).unwrap(),
+            ],
+            value: xdr::ScVal::Vec(Some(
+                vec![xdr::ScVal::U32(1), xdr::ScVal::U32(2)]
+                    .try_into()
+                    .unwrap(),
+            ))
+            .to_xdr_base64(Limits::none())
+            .unwrap(),
+            contract_id: "C123".to_string(),
+            ..event(7)
+        };
+        let mut csv = csv::Writer::from_writer(Vec::new());
+        csv.write_record(CSV_HEADER).unwrap();
+        csv.write_record(event_csv_record(&event, None).unwrap())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(csv.into_inner().unwrap()).unwrap(),
+            "\
+ledger,ledger_closed_at,contract_id,event_type,topics,value
+7,,C123,contract,transfer;5,\"[1,2]\"
+"
+        );
+    }
+
+    #[test]
+    fn decodes_topics_with_spec() {
+        let spec = Spec::new(vec![ScSpecEntry::UdtUnionV0(xdr::ScSpecUdtUnionV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "DataKey".try_into().unwrap(),
+            cases: vec![ScSpecUdtUnionCaseV0::TupleV0(
+                xdr::ScSpecUdtUnionCaseTupleV0 {
+                    doc: StringM::default(),
+                    name: "Balance".try_into().unwrap(),
+                    type_: vec![ScSpecTypeDef::U32].try_into().unwrap(),
+                },
+            )]
+            .try_into()
+            .unwrap(),
+        })]);
+        let event = rpc::Event {
+            topic: vec![
+                xdr::ScVal::Symbol("transfer".try_into().unwrap())
+                    .to_xdr_base64(Limits::none())
+                    .unwrap(),
+                xdr::ScVal::Vec(Some(
+                    vec![
+                        xdr::ScVal::Symbol("Balance".try_into().unwrap()),
+                        xdr::ScVal::U32(5),
+                    ]
+                    .try_into()
+                    .unwrap(),
+                ))
+                .to_xdr_base64(Limits::none())
+                .unwrap(),
+            ],
+            value: xdr::ScVal::U32(1).to_xdr_base64(Limits::none()).unwrap(),
+            contract_id: "C123".to_string(),
+            ..event(7)
+        };
+        let decoded = Decoded::new(Some(&spec), &event).unwrap();
+        assert_eq!(
+            decoded.topics,
+            vec![
+                serde_json::json!("transfer"),
+                serde_json::json!({ "Balance": 5 })
+            ]
+        );
+        let mut out = NoColor::new(Vec::new());
+        write_event(&mut out, &event, Some(&decoded)).unwrap();
+        assert_eq!(
+            String::from_utf8(out.into_inner()).unwrap(),
+            format!(
+                "{event}  Decoded topics:
+            transfer
+            {{\"Balance\":5}}
+  Decoded value:  1
+
+"
+            )
+        );
+    }
+
+    #[test]
+    fn decodes_value_as_matching_struct() {
+        let spec = Spec::new(vec![ScSpecEntry::UdtStructV0(xdr::ScSpecUdtStructV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "Transfer".try_into().unwrap(),
+            fields: vec![xdr::ScSpecUdtStructFieldV0 {
+                doc: StringM::default(),
+                name: "amount".try_into().unwrap(),
+                type_: ScSpecTypeDef::I128,
+            }]
+            .try_into()
+            .unwrap(),
+        })]);
+        let value = xdr::ScVal::Map(Some(
+            vec![xdr::ScMapEntry {
+                key: xdr::ScVal::Symbol("amount".try_into().unwrap()),
+                val: xdr::ScVal::I128(xdr::Int128Parts { hi: 0, lo: 5 }),
+            }]
+            .try_into()
+            .unwrap(),
+        ));
+        assert_eq!(
+            matching_udt(&spec, &value),
+            Some("Transfer".try_into().unwrap())
+        );
+        assert_eq!(
+            decode_value(Some(&spec), &value).unwrap(),
+            serde_json::json!({ "amount": "5" })
+        );
+        let other = xdr::ScVal::U32(5);
+        assert_eq!(matching_udt(&spec, &other), None);
+        assert_eq!(
+            decode_value(Some(&spec), &other).unwrap(),
+            serde_json::json!(5)
+        );
+    }
+
+    #[test]
+    fn decodes_value_generically_when_matching_udt_doesnt_fit() {
+        let spec = Spec::new(vec![ScSpecEntry::UdtStructV0(xdr::ScSpecUdtStructV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "Transfer".try_into().unwrap(),
+            fields: vec![xdr::ScSpecUdtStructFieldV0 {
+                doc: StringM::default(),
+                name: "amount".try_into().unwrap(),
+                type_: ScSpecTypeDef::I128,
+            }]
+            .try_into()
+            .unwrap(),
+        })]);
+        let value = xdr::ScVal::Map(Some(
+            vec![xdr::ScMapEntry {
+                key: xdr::ScVal::Symbol("amount".try_into().unwrap()),
+                val: xdr::ScVal::Bool(true),
+            }]
+            .try_into()
+            .unwrap(),
+        ));
+        assert!(matching_udt(&spec, &value).is_some());
+        assert_eq!(
+            decode_value(Some(&spec), &value).unwrap(),
+            soroban_spec_tools::to_json(&value).unwrap()
+        );
+    }
+
+    fn with_topics(topics: &[&str]) -> Cmd {
+        let mut args = vec!["events", "--start-ledger", "1", "--network", "local"];
+        for topic in topics {
+            args.extend(["--topic", topic]);
+        }
+        Cmd::parse_from(args)
+    }
+
+    #[test]
+    fn converts_json_topic_segments() {
+        let cmd = with_topics(&[r#""transfer",{"u32":5},*"#, "AAAADwAAAAdDT1VOVEVSAA=="]);
+        let transfer = xdr::ScVal::Symbol("transfer".try_into().unwrap())
+            .to_xdr_base64(Limits::none())
+            .unwrap();
+        let five = xdr::ScVal::U32(5).to_xdr_base64(Limits::none()).unwrap();
+        assert_eq!(
+            cmd.topic_filters().unwrap(),
+            [
+                format!("{transfer},{five},*"),
+                "AAAADwAAAAdDT1VOVEVSAA==".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_topic_filters_with_more_than_four_segments() {
+        assert!(matches!(
+            with_topics(&["*,*,*,*,*"]).topic_filters(),
+            Err(Error::InvalidTopicFilter { .. })
+        ));
+        assert!(matches!(
+            with_topics(&["not-xdr"]).topic_filters(),
+            Err(Error::InvalidSegment { .. })
+        ));
     }
 }
@@ -14,13 +14,14 @@ pub struct Args {
     #[arg(long, short = 'f')]
     pub filter_logs: Vec<String>,
 
-    /// Do not write logs to stderr including `INFO`
-    #[arg(long, short = 'q')]
+    /// Do not write logs to stderr, not even warnings
+    #[arg(long, short = 'q', conflicts_with_all = ["verbose", "very_verbose"])]
     pub quiet: bool,
 
-    /// Log DEBUG events
-    #[arg(long, short = 'v')]
-    pub verbose: bool,
+    /// Log INFO and DEBUG events as well as warnings. Repeat, e.g. `-vv`, to also log TRACE
+    /// events
+    #[arg(long, short = 'v', action = clap::ArgAction::Count)]
+    pub verbose: u8,
 
     /// Log DEBUG and TRACE events
     #[arg(long, visible_alias = "vv")]
@@ -33,6 +34,15 @@ pub struct Args {
     /// Do not cache your simulations and transactions
     #[arg(long, env = "STELLAR_NO_CACHE")]
     pub no_cache: bool,
+
+    /// Fetch contract specs from the network even if they are cached, replacing the cached copies
+    #[arg(long)]
+    pub refresh_cache: bool,
+
+    /// Load `KEY=VALUE` environment variables from a file before resolving other arguments.
+    /// Variables from the file take precedence over the current environment, explicit flags take precedence over both
+    #[arg(long, value_name = "PATH", global = true)]
+    pub env_file: Option<PathBuf>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -54,12 +64,44 @@ impl Args {
     pub fn log_level(&self) -> Option<tracing::Level> {
         if self.quiet {
             None
-        } else if self.very_verbose {
+        } else if self.very_verbose || self.verbose >= 2 {
             Some(tracing::Level::TRACE)
-        } else if self.verbose {
+        } else if self.verbose == 1 {
             Some(tracing::Level::DEBUG)
         } else {
-            Some(tracing::Level::INFO)
+            Some(tracing::Level::WARN)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cmd {
+        #[command(flatten)]
+        global: Args,
+    }
+
+    fn log_level(args: &[&str]) -> Option<tracing::Level> {
+        Cmd::parse_from(std::iter::once("stellar").chain(args.iter().copied()))
+            .global
+            .log_level()
+    }
+
+    #[test]
+    fn verbosity_flags_set_log_level() {
+        assert_eq!(log_level(&[]), Some(tracing::Level::WARN));
+        assert_eq!(log_level(&["-q"]), None);
+        assert_eq!(log_level(&["-v"]), Some(tracing::Level::DEBUG));
+        assert_eq!(log_level(&["-vv"]), Some(tracing::Level::TRACE));
+        assert_eq!(
+            log_level(&["--verbose", "--verbose"]),
+            Some(tracing::Level::TRACE)
+        );
+        assert_eq!(log_level(&["--very-verbose"]), Some(tracing::Level::TRACE));
+        assert!(Cmd::try_parse_from(["stellar", "-q", "-v"]).is_err());
+    }
+}
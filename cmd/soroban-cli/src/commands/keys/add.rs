@@ -1,6 +1,7 @@
 use clap::command;
 
 use super::super::config::{locator, secret};
+use crate::signer::ledger;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -9,6 +10,9 @@ pub enum Error {
 
     #[error(transparent)]
     Config(#[from] locator::Error),
+
+    #[error(transparent)]
+    Ledger(#[from] ledger::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -25,9 +29,14 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        Ok(self
-            .config_locator
-            .write_identity(&self.name, &self.secrets.read_secret()?)?)
+    pub async fn run(&self) -> Result<(), Error> {
+        let secret = if self.secrets.ledger {
+            secret::Secret::Ledger {
+                ledger: ledger::key(self.secrets.hd_path.unwrap_or_default()).await?,
+            }
+        } else {
+            self.secrets.read_secret()?
+        };
+        Ok(self.config_locator.write_identity(&self.name, &secret)?)
     }
 }
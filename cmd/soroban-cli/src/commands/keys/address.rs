@@ -1,4 +1,9 @@
-use crate::commands::config::secret;
+use std::ops::Range;
+
+use soroban_env_host::xdr::AccountEntry;
+
+use crate::commands::{config::secret, network};
+use crate::rpc;
 
 use super::super::config::locator;
 use clap::arg;
@@ -13,11 +18,36 @@ pub enum Error {
 
     #[error(transparent)]
     StrKey(#[from] stellar_strkey::DecodeError),
+
+    #[error(transparent)]
+    Network(#[from] network::Error),
+
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
 #[group(skip)]
 pub struct Cmd {
+    #[command(flatten)]
+    pub address: Args,
+
+    /// Print the addresses for a range of hd paths of a seed phrase identity, one per line,
+    /// e.g. `0..5` for paths 0 to 4
+    #[arg(long, value_parser = parse_range, conflicts_with_all = ["hd_path", "verify_on_network"])]
+    pub range: Option<Range<usize>>,
+
+    /// Also check the account on the network, printing whether it exists and its sequence number
+    #[arg(long)]
+    pub verify_on_network: bool,
+
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Args {
     /// Name of identity to lookup, default test identity used if not provided
     pub name: String,
 
@@ -30,11 +60,34 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        println!("{}", self.public_key()?);
+    pub async fn run(&self) -> Result<(), Error> {
+        if let Some(range) = &self.range {
+            for hd_path in range.clone() {
+                let args = Args {
+                    hd_path: Some(hd_path),
+                    ..self.address.clone()
+                };
+                println!("{}", args.public_key()?);
+            }
+            return Ok(());
+        }
+        let address = self.address.public_key()?.to_string();
+        println!("{address}");
+        if self.verify_on_network {
+            println!("{}", self.account_status(&address).await?);
+        }
         Ok(())
     }
 
+    /// Look up `address` on the network, describing whether the account exists.
+    async fn account_status(&self, address: &str) -> Result<String, Error> {
+        let network = self.network.get(&self.address.locator)?;
+        let client = network.rpc_client(&self.network.rpc_options()).await?;
+        account_status(client.get_account(address).await)
+    }
+}
+
+impl Args {
     pub fn private_key(&self) -> Result<ed25519_dalek::SigningKey, Error> {
         Ok(self
             .locator
@@ -52,3 +105,118 @@ impl Cmd {
         }
     }
 }
+
+/// Describe the result of `getAccount`, treating a missing account as a valid answer rather
+/// than an error.
+fn account_status(account: Result<AccountEntry, rpc::Error>) -> Result<String, Error> {
+    match account {
+        Ok(account) => Ok(format!(
+            "account exists with sequence number {}",
+            account.seq_num.0
+        )),
+        Err(rpc::Error::NotFound(..)) => Ok("account does not exist on the network".to_string()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Parse `start..end`, an exclusive range of hd paths.
+fn parse_range(s: &str) -> Result<Range<usize>, String> {
+    let (start, end) = s
+        .split_once("..")
+        .ok_or_else(|| format!("expected a range like 0..5, got {s}"))?;
+    let start = start.parse::<usize>().map_err(|e| e.to_string())?;
+    let end = end.parse::<usize>().map_err(|e| e.to_string())?;
+    if start > end {
+        return Err(format!("range start {start} is greater than end {end}"));
+    }
+    Ok(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::network::LOCAL_NETWORK_PASSPHRASE;
+    use crate::utils::rpc::tests::mock_server;
+    use clap::Parser;
+    use serde_json::{json, Value};
+    use soroban_env_host::xdr::{
+        AccountEntryExt, AccountId, LedgerEntryData, Limits, PublicKey, SequenceNumber, String32,
+        Thresholds, Uint256, VecM, WriteXdr,
+    };
+
+    fn account(seq_num: i64) -> AccountEntry {
+        AccountEntry {
+            account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([0; 32]))),
+            balance: 0,
+            seq_num: SequenceNumber(seq_num),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: String32::default(),
+            thresholds: Thresholds([0; 4]),
+            signers: VecM::default(),
+            ext: AccountEntryExt::V0,
+        }
+    }
+
+    #[test]
+    fn parses_hd_path_range() {
+        assert_eq!(parse_range("0..5").unwrap(), 0..5);
+        assert_eq!(parse_range("3..3").unwrap(), 3..3);
+        assert!(parse_range("5..0").is_err());
+        assert!(parse_range("5").is_err());
+        assert!(parse_range("a..b").is_err());
+    }
+
+    /// Answer `getNetwork` for the local network, and `getLedgerEntries` with `entries`.
+    fn rpc_response(call: &Value, entries: Value) -> Value {
+        match call["method"].as_str() {
+            Some("getNetwork") => json!({
+                "result": { "passphrase": LOCAL_NETWORK_PASSPHRASE, "protocolVersion": 21 }
+            }),
+            _ => json!({ "result": { "entries": entries, "latestLedger": 1 } }),
+        }
+    }
+
+    async fn verify_on_network(rpc_url: &str) -> String {
+        let address = stellar_strkey::ed25519::PublicKey([0; 32]).to_string();
+        let cmd = Cmd::try_parse_from([
+            "address",
+            &address,
+            "--verify-on-network",
+            "--rpc-url",
+            rpc_url,
+            "--network-passphrase",
+            LOCAL_NETWORK_PASSPHRASE,
+        ])
+        .unwrap();
+        cmd.account_status(&address).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn existing_account_prints_sequence() {
+        let url = mock_server(|call| {
+            let xdr = LedgerEntryData::Account(account(42))
+                .to_xdr_base64(Limits::none())
+                .unwrap();
+            rpc_response(
+                call,
+                json!([{ "key": "", "xdr": xdr, "lastModifiedLedgerSeq": 1 }]),
+            )
+        })
+        .await;
+        assert_eq!(
+            verify_on_network(&url).await,
+            "account exists with sequence number 42"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_account_is_not_an_error() {
+        let url = mock_server(|call| rpc_response(call, json!([]))).await;
+        assert_eq!(
+            verify_on_network(&url).await,
+            "account does not exist on the network"
+        );
+    }
+}
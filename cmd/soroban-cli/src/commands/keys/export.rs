@@ -0,0 +1,42 @@
+use clap::command;
+
+use super::super::config::{locator, secret};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+
+    #[error("failed to read password")]
+    PasswordRead,
+
+    #[error("passwords do not match")]
+    PasswordMismatch,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity to export
+    pub name: String,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let secret = self.config_locator.read_identity(&self.name)?;
+        let password = rpassword::prompt_password("Password: ").map_err(|_| Error::PasswordRead)?;
+        let confirm =
+            rpassword::prompt_password("Confirm password: ").map_err(|_| Error::PasswordRead)?;
+        if password != confirm {
+            return Err(Error::PasswordMismatch);
+        }
+        println!("{}", secret.encrypt(&password)?);
+        Ok(())
+    }
+}
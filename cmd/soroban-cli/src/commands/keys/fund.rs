@@ -12,6 +12,16 @@ pub enum Error {
     Network(#[from] network::Error),
 }
 
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Address(_) => crate::exit_code::USAGE,
+            Error::Network(e) => e.exit_code(),
+        }
+    }
+}
+
 #[derive(Debug, clap::Parser, Clone)]
 #[group(skip)]
 pub struct Cmd {
@@ -19,7 +29,7 @@ pub struct Cmd {
     pub network: network::Args,
     /// Address to fund
     #[command(flatten)]
-    pub address: address::Cmd,
+    pub address: address::Args,
 }
 
 impl Cmd {
@@ -27,7 +37,7 @@ impl Cmd {
         let addr = self.address.public_key()?;
         self.network
             .get(&self.address.locator)?
-            .fund_address(&addr)
+            .fund_address(&addr, &self.network.rpc_options())
             .await?;
         Ok(())
     }
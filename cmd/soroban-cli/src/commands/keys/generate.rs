@@ -67,7 +67,7 @@ impl Cmd {
             let addr = secret.public_key(self.hd_path)?;
             let network = self.network.get(&self.config_locator)?;
             network
-                .fund_address(&addr)
+                .fund_address(&addr, &self.network.rpc_options())
                 .await
                 .map_err(|e| {
                     tracing::warn!("fund_address failed: {e}");
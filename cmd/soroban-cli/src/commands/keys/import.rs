@@ -0,0 +1,55 @@
+use std::io::Read;
+
+use clap::command;
+
+use super::super::config::{
+    locator,
+    secret::{self, Secret},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+
+    #[error("failed to read encrypted identity from stdin: {0}")]
+    StdinRead(std::io::Error),
+
+    #[error("failed to read password")]
+    PasswordRead,
+
+    #[error("identity {0} already exists, use --overwrite to replace it")]
+    IdentityExists(String),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Name of the imported identity
+    pub name: String,
+
+    /// Replace the identity if one with this name already exists
+    #[arg(long)]
+    pub overwrite: bool,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        if !self.overwrite && self.config_locator.read_identity(&self.name).is_ok() {
+            return Err(Error::IdentityExists(self.name.clone()));
+        }
+        let mut encrypted = String::new();
+        std::io::stdin()
+            .read_to_string(&mut encrypted)
+            .map_err(Error::StdinRead)?;
+        let password = rpassword::prompt_password("Password: ").map_err(|_| Error::PasswordRead)?;
+        let secret = Secret::decrypt(&encrypted, &password)?;
+        Ok(self.config_locator.write_identity(&self.name, &secret)?)
+    }
+}
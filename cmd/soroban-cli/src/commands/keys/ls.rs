@@ -1,11 +1,24 @@
 use clap::command;
 
-use super::super::config::locator;
+use super::super::config::{locator, secret};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Config(#[from] locator::Error),
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// One identity name per line
+    #[default]
+    Plain,
+    /// An array of objects with each identity's name and public key
+    Json,
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -16,11 +29,34 @@ pub struct Cmd {
 
     #[arg(long, short = 'l')]
     pub long: bool,
+
+    /// Format of the list
+    #[arg(long, value_enum, default_value_t, conflicts_with = "long")]
+    pub format: OutputFormat,
+
+    /// Print each identity's public key after its name, with the names padded to line up
+    #[arg(long, conflicts_with = "long")]
+    pub show_address: bool,
+
+    /// If an identity is a seed phrase use this hd path for its public key, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
-        let res = if self.long { self.ls_l() } else { self.ls() }?.join("\n");
+        if self.format == OutputFormat::Json {
+            println!("{}", serde_json::to_string_pretty(&self.ls_json()?)?);
+            return Ok(());
+        }
+        let res = if self.long {
+            self.ls_l()
+        } else if self.show_address {
+            self.ls_with_address()
+        } else {
+            self.ls()
+        }?
+        .join("\n");
         println!("{res}");
         Ok(())
     }
@@ -38,4 +74,33 @@ impl Cmd {
             .map(|(name, location)| format!("{location}\nName: {name}\n"))
             .collect::<Vec<String>>())
     }
+
+    pub fn ls_with_address(&self) -> Result<Vec<String>, Error> {
+        let names = self.ls()?;
+        let width = names.iter().map(String::len).max().unwrap_or_default();
+        names
+            .iter()
+            .map(|name| Ok(format!("{name:width$}  {}", self.public_key(name)?)))
+            .collect()
+    }
+
+    pub fn ls_json(&self) -> Result<Vec<serde_json::Value>, Error> {
+        self.ls()?
+            .into_iter()
+            .map(|name| {
+                let public_key = self.public_key(&name)?;
+                Ok(serde_json::json!({
+                    "name": name,
+                    "public_key": public_key.to_string(),
+                }))
+            })
+            .collect()
+    }
+
+    fn public_key(&self, name: &str) -> Result<stellar_strkey::ed25519::PublicKey, Error> {
+        Ok(self
+            .config_locator
+            .read_identity(name)?
+            .public_key(self.hd_path)?)
+    }
 }
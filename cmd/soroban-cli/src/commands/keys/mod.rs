@@ -2,9 +2,12 @@ use clap::Parser;
 
 pub mod add;
 pub mod address;
+pub mod export;
 pub mod fund;
 pub mod generate;
+pub mod import;
 pub mod ls;
+pub mod rename;
 pub mod rm;
 pub mod show;
 
@@ -14,15 +17,21 @@ pub enum Cmd {
     Add(add::Cmd),
     /// Given an identity return its address (public key)
     Address(address::Cmd),
+    /// Print an identity encrypted with a password, to back it up or copy it to another machine
+    Export(export::Cmd),
     /// Fund an identity on a test network
     Fund(fund::Cmd),
     /// Generate a new identity with a seed phrase, currently 12 words
     Generate(generate::Cmd),
+    /// Add an identity from the output of `keys export`, read from stdin
+    Import(import::Cmd),
     /// List identities
     Ls(ls::Cmd),
+    /// Rename an identity, keeping its secret as it is
+    Rename(rename::Cmd),
     /// Remove an identity
     Rm(rm::Cmd),
-    /// Given an identity return its private key
+    /// Given an identity return its private key, its public key, or its raw seed
     Show(show::Cmd),
 }
 
@@ -34,11 +43,17 @@ pub enum Error {
     #[error(transparent)]
     Address(#[from] address::Error),
     #[error(transparent)]
+    Export(#[from] export::Error),
+    #[error(transparent)]
     Fund(#[from] fund::Error),
 
     #[error(transparent)]
     Generate(#[from] generate::Error),
     #[error(transparent)]
+    Import(#[from] import::Error),
+    #[error(transparent)]
+    Rename(#[from] rename::Error),
+    #[error(transparent)]
     Rm(#[from] rm::Error),
     #[error(transparent)]
     Ls(#[from] ls::Error),
@@ -47,14 +62,27 @@ pub enum Error {
     Show(#[from] show::Error),
 }
 
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Fund(e) => e.exit_code(),
+            _ => crate::exit_code::USAGE,
+        }
+    }
+}
+
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         match self {
-            Cmd::Add(cmd) => cmd.run()?,
-            Cmd::Address(cmd) => cmd.run()?,
+            Cmd::Add(cmd) => cmd.run().await?,
+            Cmd::Address(cmd) => cmd.run().await?,
+            Cmd::Export(cmd) => cmd.run()?,
             Cmd::Fund(cmd) => cmd.run().await?,
             Cmd::Generate(cmd) => cmd.run().await?,
+            Cmd::Import(cmd) => cmd.run()?,
             Cmd::Ls(cmd) => cmd.run()?,
+            Cmd::Rename(cmd) => cmd.run()?,
             Cmd::Rm(cmd) => cmd.run()?,
             Cmd::Show(cmd) => cmd.run()?,
         };
@@ -0,0 +1,27 @@
+use clap::command;
+
+use super::super::config::locator;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity to rename
+    pub old: String,
+    /// New name of the identity, which must not already exist
+    pub new: String,
+
+    #[command(flatten)]
+    pub config: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        Ok(self.config.rename_identity(&self.old, &self.new)?)
+    }
+}
@@ -24,16 +24,37 @@ pub struct Cmd {
     #[arg(long)]
     pub hd_path: Option<usize>,
 
+    /// Print the public key (`G...`) instead of the secret key
+    #[arg(long, conflicts_with = "raw_seed")]
+    pub public: bool,
+
+    /// Print the secret key's raw 32-byte ed25519 seed as hex instead of as a strkey
+    #[arg(long)]
+    pub raw_seed: bool,
+
     #[command(flatten)]
     pub locator: locator::Args,
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
-        println!("{}", self.private_key()?.to_string());
+        if self.public {
+            println!("{}", self.public_key()?);
+        } else if self.raw_seed {
+            println!("{}", hex::encode(self.private_key()?.0));
+        } else {
+            println!("{}", self.private_key()?.to_string());
+        }
         Ok(())
     }
 
+    pub fn public_key(&self) -> Result<stellar_strkey::ed25519::PublicKey, Error> {
+        Ok(self
+            .locator
+            .read_identity(&self.name)?
+            .public_key(self.hd_path)?)
+    }
+
     pub fn private_key(&self) -> Result<stellar_strkey::ed25519::PrivateKey, Error> {
         Ok(self
             .locator
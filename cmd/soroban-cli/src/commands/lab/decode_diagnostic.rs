@@ -0,0 +1,168 @@
+use std::io::{self, BufRead};
+
+use clap::{arg, Parser};
+use soroban_env_host::xdr::{self, ContractEventBody, DiagnosticEvent, Limits, ReadXdr, ScVal};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base64 encoded `DiagnosticEvent`s to decode. When omitted, events are
+    /// read from stdin, one per line.
+    #[arg()]
+    pub events: Vec<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("cannot decode diagnostic event {event}: {error}")]
+    CannotDecode { event: String, error: xdr::Error },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// The human readable parts of a `DiagnosticEvent`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    pub in_successful_contract_call: bool,
+    pub contract_id: Option<String>,
+    pub topics: Vec<String>,
+    pub data: String,
+    /// Message of `log` and `error` events emitted by the host or the contract
+    pub message: Option<String>,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let events = if self.events.is_empty() {
+            io::stdin().lock().lines().collect::<Result<Vec<_>, _>>()?
+        } else {
+            self.events.clone()
+        };
+        for (i, event) in events
+            .iter()
+            .map(|e| e.trim())
+            .filter(|e| !e.is_empty())
+            .enumerate()
+        {
+            if i > 0 {
+                println!();
+            }
+            print!("{}", decode(event)?);
+        }
+        Ok(())
+    }
+}
+
+/// Decode a base64 encoded `DiagnosticEvent`.
+///
+/// # Errors
+///
+/// If the input is not a valid `DiagnosticEvent`.
+pub fn decode(event: &str) -> Result<Decoded, Error> {
+    let DiagnosticEvent {
+        in_successful_contract_call,
+        event,
+    } = DiagnosticEvent::from_xdr_base64(event, Limits::none()).map_err(|error| {
+        Error::CannotDecode {
+            event: event.to_string(),
+            error,
+        }
+    })?;
+    let ContractEventBody::V0(body) = event.body;
+    Ok(Decoded {
+        in_successful_contract_call,
+        contract_id: event
+            .contract_id
+            .map(|id| stellar_strkey::Contract(id.0).to_string()),
+        topics: body.topics.iter().map(display).collect(),
+        message: message(&body.topics, &body.data),
+        data: display(&body.data),
+    })
+}
+
+/// `log` and `error` events carry their message as the data, or as the first
+/// element of the data when they also carry arguments.
+fn message(topics: &[ScVal], data: &ScVal) -> Option<String> {
+    match topics.first() {
+        Some(ScVal::Symbol(s)) if s.0.as_slice() == b"log" || s.0.as_slice() == b"error" => {}
+        _ => return None,
+    }
+    match data {
+        ScVal::String(s) => Some(s.to_utf8_string_lossy()),
+        ScVal::Vec(Some(v)) => match v.first() {
+            Some(ScVal::String(s)) => Some(s.to_utf8_string_lossy()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn display(val: &ScVal) -> String {
+    soroban_spec_tools::to_string(val).unwrap_or_else(|_| format!("{val:?}"))
+}
+
+impl std::fmt::Display for Decoded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Contract: {}",
+            self.contract_id.as_deref().unwrap_or("none")
+        )?;
+        writeln!(
+            f,
+            "In successful contract call: {}",
+            self.in_successful_contract_call
+        )?;
+        writeln!(f, "Topics:")?;
+        for topic in &self.topics {
+            writeln!(f, "  {topic}")?;
+        }
+        writeln!(f, "Data: {}", self.data)?;
+        if let Some(message) = &self.message {
+            writeln!(f, "Message: {message}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{
+        ContractEvent, ContractEventType, ContractEventV0, ExtensionPoint, Hash, ScString,
+        ScSymbol, WriteXdr,
+    };
+
+    #[test]
+    fn decodes_log_message() {
+        let event = DiagnosticEvent {
+            in_successful_contract_call: false,
+            event: ContractEvent {
+                ext: ExtensionPoint::V0,
+                contract_id: Some(Hash([0; 32])),
+                type_: ContractEventType::Diagnostic,
+                body: ContractEventBody::V0(ContractEventV0 {
+                    topics: vec![ScVal::Symbol(ScSymbol("log".try_into().unwrap()))]
+                        .try_into()
+                        .unwrap(),
+                    data: ScVal::Vec(Some(
+                        vec![
+                            ScVal::String(ScString("balance is too low".try_into().unwrap())),
+                            ScVal::U32(7),
+                        ]
+                        .try_into()
+                        .unwrap(),
+                    )),
+                }),
+            },
+        };
+        let decoded = decode(&event.to_xdr_base64(Limits::none()).unwrap()).unwrap();
+        assert_eq!(decoded.message.as_deref(), Some("balance is too low"));
+        assert_eq!(decoded.topics, vec!["log".to_string()]);
+        assert_eq!(
+            decoded.contract_id.as_deref(),
+            Some("CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4")
+        );
+        assert!(!decoded.in_successful_contract_call);
+    }
+}
@@ -0,0 +1,31 @@
+use clap::Parser;
+
+pub mod decode_diagnostic;
+pub mod xdr;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Decode base64 `DiagnosticEvent` XDR, printing the contract, topics, data and any log message
+    DecodeDiagnostic(decode_diagnostic::Cmd),
+    /// Inspect XDR of an unknown type
+    #[command(subcommand)]
+    Xdr(xdr::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    DecodeDiagnostic(#[from] decode_diagnostic::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        match self {
+            Cmd::DecodeDiagnostic(cmd) => cmd.run()?,
+            Cmd::Xdr(cmd) => cmd.run()?,
+        };
+        Ok(())
+    }
+}
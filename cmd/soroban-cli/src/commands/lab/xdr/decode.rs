@@ -0,0 +1,250 @@
+use std::io::{self, BufRead};
+
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use clap::{arg, Parser, ValueEnum};
+use soroban_env_host::xdr::{self, Limits, Type, TypeVariant};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// XDR type to decode, e.g. `TransactionEnvelope` or `ScVal`
+    #[arg(long = "type", required_unless_present = "kind")]
+    pub type_: Option<TypeVariant>,
+    /// Kind of XDR to decode, a shorthand for the types of a submitted transaction's XDR that
+    /// can be given instead of --type
+    #[arg(long, value_enum, conflicts_with = "type_")]
+    pub kind: Option<Kind>,
+    /// Encoded XDR to decode. When omitted, XDR is read from stdin, one per line, and each line
+    /// is decoded on its own.
+    #[arg(long)]
+    pub xdr: Option<String>,
+    /// Encoding of the XDR
+    #[arg(long, value_enum, default_value_t)]
+    pub input_format: InputFormat,
+    /// Format of the output
+    #[arg(long, value_enum, default_value_t)]
+    pub output: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Kind {
+    /// `TransactionEnvelope`, e.g. a transaction's envelope XDR
+    Tx,
+    /// `TransactionResult`, e.g. a transaction's result XDR
+    Result,
+    /// `TransactionMeta`, e.g. a transaction's result meta XDR
+    Meta,
+    /// `ScVal`, e.g. a contract's return value, or an event's topic or data
+    Scval,
+}
+
+impl Kind {
+    /// The XDR type this kind is a shorthand for.
+    pub fn type_variant(self) -> TypeVariant {
+        match self {
+            Kind::Tx => TypeVariant::TransactionEnvelope,
+            Kind::Result => TypeVariant::TransactionResult,
+            Kind::Meta => TypeVariant::TransactionMeta,
+            Kind::Scval => TypeVariant::ScVal,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    /// Standard base64, as in transaction XDR from RPC and Horizon
+    #[default]
+    Base64,
+    /// URL-safe base64, with `-` and `_` instead of `+` and `/`, padded or not
+    Base64url,
+    /// Hex, e.g. from logs
+    Hex,
+}
+
+impl InputFormat {
+    /// The name of the format, as given to --input-format.
+    pub fn name(self) -> &'static str {
+        match self {
+            InputFormat::Base64 => "base64",
+            InputFormat::Base64url => "base64url",
+            InputFormat::Hex => "hex",
+        }
+    }
+
+    /// Decode `s` in this format to the XDR bytes.
+    fn decode(self, s: &str) -> Result<Vec<u8>, String> {
+        match self {
+            InputFormat::Base64 => STANDARD.decode(s).map_err(|e| e.to_string()),
+            InputFormat::Base64url => URL_SAFE_NO_PAD
+                .decode(s.trim_end_matches('='))
+                .map_err(|e| e.to_string()),
+            InputFormat::Hex => hex::decode(s).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Formatted JSON of each value, separated by a blank line
+    #[default]
+    Plain,
+    /// JSON array of all the values
+    Json,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("line {line} is not valid {format}: {error}")]
+    InvalidInput {
+        line: usize,
+        format: &'static str,
+        error: String,
+    },
+    #[error("cannot decode line {line} as {type_}: {error}")]
+    CannotDecode {
+        line: usize,
+        type_: &'static str,
+        error: xdr::Error,
+    },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let lines = if let Some(xdr) = &self.xdr {
+            vec![xdr.clone()]
+        } else {
+            io::stdin().lock().lines().collect::<Result<Vec<_>, _>>()?
+        };
+        let type_ = self
+            .kind
+            .map(Kind::type_variant)
+            .or(self.type_)
+            .expect("clap requires --type or --kind");
+        let values = decode(type_, self.input_format, &lines)?;
+        match self.output {
+            OutputFormat::Plain => {
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        println!();
+                    }
+                    println!("{}", serde_json::to_string_pretty(value)?);
+                }
+            }
+            OutputFormat::Json => println!("{}", serde_json::to_string(&values)?),
+        }
+        Ok(())
+    }
+}
+
+/// Decode each non-empty line of XDR encoded in `format` as `type_`.
+///
+/// # Errors
+///
+/// If a line is not valid `format`, or does not decode as `type_`. Lines are numbered from 1.
+pub fn decode(
+    type_: TypeVariant,
+    format: InputFormat,
+    lines: &[String],
+) -> Result<Vec<Type>, Error> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line, xdr)| {
+            let bytes = format.decode(xdr).map_err(|error| Error::InvalidInput {
+                line,
+                format: format.name(),
+                error,
+            })?;
+            Type::from_xdr(type_, bytes, Limits::none()).map_err(|error| Error::CannotDecode {
+                line,
+                type_: type_.name(),
+                error,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose::URL_SAFE;
+    use soroban_env_host::xdr::{ScVal, WriteXdr};
+
+    #[test]
+    fn decodes_each_line() {
+        let lines = [
+            ScVal::U32(1).to_xdr_base64(Limits::none()).unwrap(),
+            String::new(),
+            ScVal::Bool(true).to_xdr_base64(Limits::none()).unwrap(),
+        ];
+        let values = decode(TypeVariant::ScVal, InputFormat::Base64, &lines).unwrap();
+        assert_eq!(
+            values,
+            [
+                Type::ScVal(Box::new(ScVal::U32(1))),
+                Type::ScVal(Box::new(ScVal::Bool(true))),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_line_that_fails() {
+        let lines = [
+            ScVal::U32(1).to_xdr_base64(Limits::none()).unwrap(),
+            "AAAA".to_string(),
+        ];
+        let err = decode(TypeVariant::ScVal, InputFormat::Base64, &lines).unwrap_err();
+        assert!(matches!(err, Error::CannotDecode { line: 2, .. }));
+    }
+
+    #[test]
+    fn decodes_hex_and_base64url() {
+        // An ScVal::Bytes whose base64 has `/` in it and is padded
+        let val = ScVal::Bytes(vec![0xfb, 0xff, 0xfe, 0xfb, 0xff].try_into().unwrap());
+        let bytes = val.to_xdr(Limits::none()).unwrap();
+        let expected = [Type::ScVal(Box::new(val))];
+        assert_eq!(
+            decode(TypeVariant::ScVal, InputFormat::Hex, &[hex::encode(&bytes)]).unwrap(),
+            expected
+        );
+        for encoded in [URL_SAFE_NO_PAD.encode(&bytes), URL_SAFE.encode(&bytes)] {
+            assert_eq!(
+                decode(TypeVariant::ScVal, InputFormat::Base64url, &[encoded]).unwrap(),
+                expected
+            );
+        }
+        let err = decode(TypeVariant::ScVal, InputFormat::Hex, &["zz".to_string()]).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidInput {
+                line: 1,
+                format: "hex",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn kind_is_a_shorthand_for_type() {
+        let parse = |args: &[&str]| {
+            Cmd::try_parse_from(std::iter::once("decode").chain(args.iter().copied()))
+        };
+        let cmd = parse(&["--kind", "meta"]).unwrap();
+        assert_eq!(
+            cmd.kind.map(Kind::type_variant),
+            Some(TypeVariant::TransactionMeta)
+        );
+        assert!(parse(&["--type", "ScVal"]).is_ok());
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--kind", "tx", "--type", "ScVal"]).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::io::{self, Read};
+
+use clap::{arg, Parser, ValueEnum};
+use soroban_env_host::xdr::{self, Limits, Type, TypeVariant, WriteXdr};
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// XDR type to encode, e.g. `TransactionEnvelope` or `ScVal`
+    #[arg(long = "type", value_parser = parse_type)]
+    pub type_: TypeVariant,
+    /// Format of the JSON read from stdin
+    #[arg(long, value_enum, default_value_t)]
+    pub input: InputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    /// One or more JSON values, as printed by `lab xdr decode`
+    #[default]
+    Plain,
+    /// JSON array of values, as printed by `lab xdr decode --output json`
+    Json,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("cannot encode value {index} as {type_}: {error}")]
+    CannotEncode {
+        index: usize,
+        type_: &'static str,
+        error: xdr::Error,
+    },
+    #[error("reading JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let mut json = String::new();
+        io::stdin().read_to_string(&mut json)?;
+        for xdr in encode(self.type_, self.input, &json)? {
+            println!("{xdr}");
+        }
+        Ok(())
+    }
+}
+
+/// Parse the name of an XDR type, listing the names when it isn't one.
+fn parse_type(s: &str) -> Result<TypeVariant, String> {
+    s.parse().map_err(|_| {
+        format!(
+            "unknown type, choose one of {}",
+            TypeVariant::VARIANTS_STR.join(", ")
+        )
+    })
+}
+
+/// Encode each JSON value in `json`, given in `format`, as base64 XDR of `type_`. The JSON is
+/// what `lab xdr decode` prints, so that decoding and encoding again gives back the same XDR.
+///
+/// # Errors
+///
+/// If `json` isn't valid JSON in `format`, or a value doesn't match `type_`. Values are numbered
+/// from 1.
+pub fn encode(type_: TypeVariant, format: InputFormat, json: &str) -> Result<Vec<String>, Error> {
+    let values = match format {
+        InputFormat::Plain => serde_json::Deserializer::from_str(json)
+            .into_iter::<serde_json::Value>()
+            .collect::<Result<Vec<_>, _>>()?,
+        InputFormat::Json => serde_json::from_str(json)?,
+    };
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            Type::read_json(type_, value.to_string().as_bytes())
+                .and_then(|value| value.to_xdr_base64(Limits::none()))
+                .map_err(|error| Error::CannotEncode {
+                    index: i + 1,
+                    type_: type_.name(),
+                    error,
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::lab::xdr::decode::{self, InputFormat as XdrFormat};
+    use soroban_env_host::xdr::{
+        Hash, Memo, MuxedAccount, Preconditions, ScVal, SequenceNumber, Transaction,
+        TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256, VecM,
+    };
+
+    fn envelope() -> TransactionEnvelope {
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+                fee: 100,
+                seq_num: SequenceNumber(i64::MAX),
+                cond: Preconditions::None,
+                memo: Memo::Hash(Hash([2; 32])),
+                operations: VecM::default(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        })
+    }
+
+    #[test]
+    fn round_trips_decoded_json() {
+        let lines = [
+            envelope().to_xdr_base64(Limits::none()).unwrap(),
+            envelope().to_xdr_base64(Limits::none()).unwrap(),
+        ];
+        let values =
+            decode::decode(TypeVariant::TransactionEnvelope, XdrFormat::Base64, &lines).unwrap();
+
+        let plain = values
+            .iter()
+            .map(|v| serde_json::to_string_pretty(v).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let encoded = encode(TypeVariant::TransactionEnvelope, InputFormat::Plain, &plain);
+        assert_eq!(encoded.unwrap(), lines);
+
+        let json = serde_json::to_string(&values).unwrap();
+        let encoded = encode(TypeVariant::TransactionEnvelope, InputFormat::Json, &json);
+        assert_eq!(encoded.unwrap(), lines);
+    }
+
+    #[test]
+    fn reports_value_that_fails() {
+        let err = encode(
+            TypeVariant::ScVal,
+            InputFormat::Plain,
+            r#"{"u32":1} {"u32":"x"}"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::CannotEncode { index: 2, .. }));
+        assert_eq!(
+            encode(TypeVariant::ScVal, InputFormat::Plain, r#"{"u32":1}"#).unwrap(),
+            [ScVal::U32(1).to_xdr_base64(Limits::none()).unwrap()]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_types() {
+        assert_eq!(parse_type("ScVal").unwrap(), TypeVariant::ScVal);
+        assert!(parse_type("NoSuchType").unwrap_err().contains("ScVal"));
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    cmp::Reverse,
+    io::{self, Cursor, Read},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use clap::{arg, Parser};
+use soroban_env_host::xdr::{Limited, Limits, Type, TypeVariant};
+
+/// Depth limit used while trying each type, so that garbage input can't recurse without bound.
+const DEPTH_LIMIT: u32 = 500;
+
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base64 encoded XDR of unknown type. When omitted, it is read from stdin.
+    #[arg()]
+    pub xdr: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("cannot decode base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("input doesn't decode as any XDR type")]
+    NoMatch,
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// A type the input decodes as.
+#[derive(Debug)]
+pub struct Candidate {
+    pub value: Type,
+    /// Number of bytes of the input read while decoding
+    pub consumed: usize,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let input = if let Some(xdr) = &self.xdr {
+            xdr.clone()
+        } else {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        };
+        let bytes = STANDARD.decode(input.trim())?;
+        let candidates = guess(&bytes);
+        let Some(best) = candidates.first() else {
+            return Err(Error::NoMatch);
+        };
+        for candidate in &candidates {
+            println!(
+                "{} ({}/{} bytes)",
+                candidate.value.name(),
+                candidate.consumed,
+                bytes.len()
+            );
+        }
+        println!();
+        println!("{}", serde_json::to_string_pretty(&best.value)?);
+        Ok(())
+    }
+}
+
+/// Try decoding `bytes` as every XDR type, returning the types that decode ranked by how much
+/// of the input they read. Types that read the whole input come first.
+pub fn guess(bytes: &[u8]) -> Vec<Candidate> {
+    let mut candidates = TypeVariant::VARIANTS
+        .iter()
+        .filter_map(|v| {
+            let mut reader = Limited::new(
+                Cursor::new(bytes),
+                Limits {
+                    depth: DEPTH_LIMIT,
+                    len: bytes.len(),
+                },
+            );
+            let value = Type::read_xdr(*v, &mut reader).ok()?;
+            let consumed = usize::try_from(reader.inner.position()).ok()?;
+            Some(Candidate { value, consumed })
+        })
+        .collect::<Vec<_>>();
+    // Stable, so types that read the same amount keep the order of the XDR definitions.
+    candidates.sort_by_key(|c| Reverse(c.consumed));
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ScVal, WriteXdr};
+
+    #[test]
+    fn ranks_types_that_read_all_bytes_first() {
+        let bytes = ScVal::U64(7).to_xdr(Limits::none()).unwrap();
+        let candidates = guess(&bytes);
+        let scval = candidates
+            .iter()
+            .find(|c| c.value == Type::ScVal(Box::new(ScVal::U64(7))))
+            .unwrap();
+        assert_eq!(scval.consumed, bytes.len());
+        assert_eq!(candidates[0].consumed, bytes.len());
+        assert!(candidates
+            .windows(2)
+            .all(|w| w[0].consumed >= w[1].consumed));
+    }
+}
@@ -0,0 +1,38 @@
+use clap::Parser;
+
+pub mod decode;
+pub mod encode;
+pub mod guess;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Decode base64, base64url, or hex XDR of a given type to JSON, reading one XDR per line from
+    /// stdin when --xdr is omitted
+    Decode(decode::Cmd),
+    /// Encode JSON of a given type, as printed by decode, to base64 XDR, one per line
+    Encode(encode::Cmd),
+    /// Guess the type of base64 XDR, listing every type it decodes as, best match first, and
+    /// printing the best match as JSON
+    Guess(guess::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
+    #[error(transparent)]
+    Encode(#[from] encode::Error),
+    #[error(transparent)]
+    Guess(#[from] guess::Error),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        match self {
+            Cmd::Decode(cmd) => cmd.run()?,
+            Cmd::Encode(cmd) => cmd.run()?,
+            Cmd::Guess(cmd) => cmd.run()?,
+        };
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ pub mod contract;
 pub mod events;
 pub mod global;
 pub mod keys;
+pub mod lab;
 pub mod network;
 pub mod plugin;
 pub mod tx;
@@ -45,6 +46,8 @@ Anything after the `--` double dash (the \"slop\") is parsed as arguments to the
 
     stellar contract invoke --id CCR6QKTWZQYW6YUJ7UP7XXZRLWQPFRV6SWBLQS4ZQOSAF4BOUD77OTE2 --source alice --network testnet -- \
                             hello --to world
+
+When a command fails, its exit code tells scripts what kind of failure it was: 2 for a usage or configuration error, 3 for a network error such as an unreachable RPC server, 4 for a contract or host error such as a failed simulation or transaction, and 1 for anything else.
 ";
 
 #[derive(Parser, Debug)]
@@ -103,6 +106,7 @@ impl Root {
             Cmd::Keys(id) => id.run().await?,
             Cmd::Tx(tx) => tx.run(&self.global_args).await?,
             Cmd::Cache(data) => data.run()?,
+            Cmd::Lab(lab) => lab.run()?,
         };
         Ok(())
     }
@@ -125,6 +129,8 @@ pub enum Cmd {
     #[command(subcommand)]
     Contract(contract::Cmd),
     /// Watch the network for contract events
+    ///
+    /// Only events of successful transactions are returned. A failed transaction's events are rolled back with it, so the RPC server doesn't record them and can't be asked for them. When the network records diagnostic events, a failed transaction's are in its result meta, which can be decoded with `stellar lab xdr decode --kind meta`.
     Events(events::Cmd),
     /// Create and manage identities including keys and addresses
     #[command(subcommand)]
@@ -142,6 +148,9 @@ pub enum Cmd {
     /// Cache for transactions and contract specs
     #[command(subcommand)]
     Cache(cache::Cmd),
+    /// Experimental tools for inspecting network data
+    #[command(subcommand)]
+    Lab(lab::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -165,6 +174,24 @@ pub enum Error {
     Tx(#[from] tx::Error),
     #[error(transparent)]
     Cache(#[from] cache::Error),
+    #[error(transparent)]
+    Lab(#[from] lab::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Contract(e) => e.exit_code(),
+            Error::Events(e) => e.exit_code(),
+            Error::Keys(e) => e.exit_code(),
+            Error::Network(e) => e.exit_code(),
+            Error::Tx(e) => e.exit_code(),
+            Error::Clap(e) => e.exit_code(),
+            Error::Xdr(_) => crate::exit_code::USAGE,
+            Error::Plugin(_) | Error::Cache(_) | Error::Lab(_) => crate::exit_code::FAILURE,
+        }
+    }
 }
 
 #[async_trait]
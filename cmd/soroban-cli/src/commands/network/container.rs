@@ -10,7 +10,7 @@ pub type StopCmd = stop::Cmd;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
-    /// Tail logs of a running network container
+    /// Print the logs of a running network container, or follow them with --follow
     Logs(logs::Cmd),
     /// Start network
     ///
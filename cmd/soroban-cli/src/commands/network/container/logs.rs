@@ -20,6 +20,10 @@ pub struct Cmd {
 
     #[arg(short = 'd', long, help = DOCKER_HOST_HELP, env = "DOCKER_HOST")]
     pub docker_host: Option<String>,
+
+    /// Keep streaming new logs until interrupted with Ctrl+C, like `docker logs -f`
+    #[arg(short = 'f', long)]
+    pub follow: bool,
 }
 
 impl Cmd {
@@ -29,7 +33,7 @@ impl Cmd {
         let logs_stream = &mut docker.logs(
             &container_name,
             Some(bollard::container::LogsOptions {
-                follow: true,
+                follow: self.follow,
                 stdout: true,
                 stderr: true,
                 tail: "all",
@@ -37,9 +41,16 @@ impl Cmd {
             }),
         );
 
-        while let Some(log) = logs_stream.try_next().await? {
-            print!("{log}");
+        let print_logs = async {
+            while let Some(log) = logs_stream.try_next().await? {
+                print!("{log}");
+            }
+            Ok::<_, Error>(())
+        };
+        tokio::select! {
+            res = print_logs => res,
+            // Only this command's stream is stopped, the container keeps running
+            _ = tokio::signal::ctrl_c() => Ok(()),
         }
-        Ok(())
     }
 }
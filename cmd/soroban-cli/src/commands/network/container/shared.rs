@@ -15,6 +15,11 @@ pub const DEFAULT_DOCKER_HOST: &str = "unix:///var/run/docker.sock";
 #[cfg(windows)]
 pub const DEFAULT_DOCKER_HOST: &str = "npipe:////./pipe/docker_engine";
 
+// Docker Desktop on Windows serves its Linux engine on this pipe, which `docker_engine` doesn't
+// always point to, e.g. when Docker Desktop was switched between Linux and Windows containers
+#[cfg(windows)]
+const DOCKER_DESKTOP_PIPE: &str = "npipe:////./pipe/dockerDesktopLinuxEngine";
+
 // DEFAULT_TIMEOUT and API_DEFAULT_VERSION are from the bollard crate
 const DEFAULT_TIMEOUT: u64 = 120;
 const API_DEFAULT_VERSION: &ClientVersion = &ClientVersion {
@@ -90,8 +95,7 @@ pub async fn connect_to_docker(docker_host: &Option<String>) -> Result<Docker, E
         Ok(()) => Ok(connection),
         // If we aren't able to connect with the defaults, or with the provided docker_host
         // try to connect with the default docker desktop socket since that is a common use case for devs
-        #[allow(unused_variables)]
-        Err(e) => {
+        Err(_) => {
             // if on unix, try to connect to the default docker desktop socket
             #[cfg(unix)]
             {
@@ -102,9 +106,14 @@ pub async fn connect_to_docker(docker_host: &Option<String>) -> Result<Docker, E
                 }
             }
 
+            // if on windows, try to connect to the docker desktop pipe
             #[cfg(windows)]
             {
-                Err(e)?
+                let docker_desktop_connection = try_docker_desktop_pipe(&host)?;
+                match check_docker_connection(&docker_desktop_connection).await {
+                    Ok(()) => Ok(docker_desktop_connection),
+                    Err(err) => Err(err)?,
+                }
             }
         }
     }
@@ -123,6 +132,13 @@ fn try_docker_desktop_socket(host: &str) -> Result<Docker, bollard::errors::Erro
     )
 }
 
+#[cfg(windows)]
+fn try_docker_desktop_pipe(host: &str) -> Result<Docker, bollard::errors::Error> {
+    println!("Failed to connect to DOCKER_HOST: {host}.\nTrying to connect to the Docker Desktop pipe at {DOCKER_DESKTOP_PIPE}.");
+
+    Docker::connect_with_named_pipe(DOCKER_DESKTOP_PIPE, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+}
+
 // When bollard is not able to connect to the docker daemon, it returns a generic ConnectionRefused error
 // This method attempts to connect to the docker daemon and returns a more specific error message
 async fn check_docker_connection(docker: &Docker) -> Result<(), bollard::errors::Error> {
@@ -144,6 +160,10 @@ async fn check_docker_connection(docker: &Docker) -> Result<(), bollard::errors:
             println!(
                 "⛔️ Failed to connect to the Docker daemon at {client_addr:?}. Is the docker daemon running?\nℹ️  Running a local Stellar network requires a Docker-compatible container runtime.\nℹ️  Please note that if you are using Docker Desktop, you may need to utilize the `--docker-host` flag to pass in the location of the docker socket on your machine.\n"
             );
+            #[cfg(windows)]
+            println!(
+                "ℹ️  On Windows, install and start Docker Desktop with Linux containers, or run the CLI inside WSL 2 with Docker available there.\n"
+            );
             Err(err)
         }
     }
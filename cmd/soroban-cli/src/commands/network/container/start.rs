@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
 use bollard::{
     container::{Config, CreateContainerOptions, StartContainerOptions},
@@ -7,11 +10,17 @@ use bollard::{
 };
 use futures_util::TryStreamExt;
 
-use crate::commands::network::container::shared::{
-    connect_to_docker, Error as ConnectionError, Network, DOCKER_HOST_HELP,
+use crate::{
+    commands::network::container::shared::{
+        connect_to_docker, Error as ConnectionError, Network, DOCKER_HOST_HELP,
+    },
+    rpc,
+    utils::rpc::{ClientOptions, RetryingClient},
 };
 
 const DEFAULT_PORT_MAPPING: &str = "8000:8000";
+/// Port that RPC listens on inside the quickstart container.
+const RPC_CONTAINER_PORT: &str = "8000";
 const DOCKER_IMAGE: &str = "docker.io/stellar/quickstart";
 
 #[derive(thiserror::Error, Debug)]
@@ -21,6 +30,12 @@ pub enum Error {
 
     #[error("⛔ ️Failed to create container: {0}")]
     BollardErr(#[from] bollard::errors::Error),
+
+    #[error("⛔ ️RPC wasn't ready after {timeout}s, check the container's logs with `stellar network container logs {network}`")]
+    RpcNotReady { timeout: u64, network: Network },
+
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -46,12 +61,24 @@ pub struct Cmd {
     /// Optional argument to specify the protocol version for the local network only
     #[arg(short = 'v', long)]
     pub protocol_version: Option<String>,
+
+    /// Return as soon as the container is started, without waiting for its RPC to be ready
+    #[arg(long)]
+    pub no_wait: bool,
+
+    /// Number of seconds to wait for the container's RPC to be ready before failing
+    #[arg(long, default_value_t = 300, conflicts_with = "no_wait")]
+    pub wait_timeout: u64,
 }
 
 impl Cmd {
     pub async fn run(&self) -> Result<(), Error> {
         println!("ℹ️  Starting {} network", &self.network);
-        run_docker_command(self).await
+        run_docker_command(self).await?;
+        if !self.no_wait {
+            wait_for_rpc(self).await?;
+        }
+        Ok(())
     }
 }
 
@@ -119,6 +146,48 @@ async fn run_docker_command(cmd: &Cmd) -> Result<(), Error> {
     Ok(())
 }
 
+/// Poll the health of the container's RPC until it reports healthy or the wait times out.
+async fn wait_for_rpc(cmd: &Cmd) -> Result<(), Error> {
+    let Some(host_port) = get_rpc_host_port(cmd) else {
+        println!(
+            "⚠️  RPC's port {RPC_CONTAINER_PORT} isn't mapped to the host, not waiting for it"
+        );
+        return Ok(());
+    };
+    let rpc_url = format!("http://localhost:{host_port}/rpc");
+    println!("ℹ️  Waiting for RPC to be ready at {rpc_url}");
+    let options = ClientOptions {
+        timeout: Some(Duration::from_secs(5)),
+        ..ClientOptions::default()
+    };
+    let client = RetryingClient::new(&rpc_url, &options)?;
+    let start = Instant::now();
+    loop {
+        match client.get_health().await {
+            Ok(health) if health.status == "healthy" => {
+                println!("✅ RPC ready");
+                return Ok(());
+            }
+            res => tracing::debug!(?res, "RPC not ready yet"),
+        }
+        if start.elapsed() >= Duration::from_secs(cmd.wait_timeout) {
+            return Err(Error::RpcNotReady {
+                timeout: cmd.wait_timeout,
+                network: cmd.network.clone(),
+            });
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// The host port that the container's RPC port is mapped to, if any.
+fn get_rpc_host_port(cmd: &Cmd) -> Option<&str> {
+    cmd.ports_mapping.iter().find_map(|port_mapping| {
+        let (from_port, to_port) = port_mapping.split_once(':')?;
+        (to_port == RPC_CONTAINER_PORT).then_some(from_port)
+    })
+}
+
 fn get_container_args(cmd: &Cmd) -> Vec<String> {
     [
         format!("--{}", cmd.network),
@@ -0,0 +1,33 @@
+use super::locator;
+use clap::command;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Network to use by default
+    pub name: Option<String>,
+
+    #[command(flatten)]
+    pub config: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        if let Some(name) = &self.name {
+            // Make sure the network exists before making it the default
+            self.config.read_network(name)?;
+            self.config.write_default_network(name)?;
+        } else if let Some(name) = self.config.default_network()? {
+            println!("{name}");
+        } else {
+            eprintln!("No default network is set");
+        }
+        Ok(())
+    }
+}
@@ -7,6 +7,17 @@ use crate::commands::config::locator::Location;
 pub enum Error {
     #[error(transparent)]
     Config(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// One network name per line
+    #[default]
+    Plain,
+    /// An array of objects with each network's name, RPC URL, passphrase, and scope
+    Json,
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -17,28 +28,87 @@ pub struct Cmd {
     /// Get more info about the networks
     #[arg(long, short = 'l')]
     pub long: bool,
+    /// Format of the list
+    #[arg(long, value_enum, default_value_t, conflicts_with = "long")]
+    pub format: OutputFormat,
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
+        if self.format == OutputFormat::Json {
+            println!("{}", serde_json::to_string_pretty(&self.ls_json()?)?);
+            return Ok(());
+        }
         let res = if self.long { self.ls_l() } else { self.ls() }?.join("\n");
         println!("{res}");
         Ok(())
     }
 
+    /// Network names, with global networks that are shadowed by a local one of the same name
+    /// marked as such.
     pub fn ls(&self) -> Result<Vec<String>, Error> {
-        Ok(self.config_locator.list_networks()?)
+        let local = self.local_names()?;
+        Ok(self
+            .config_locator
+            .list_network_locations()?
+            .into_iter()
+            .map(|(name, location)| {
+                if shadowed(&name, &location, &local) {
+                    format!("{name} (global, shadowed by local)")
+                } else {
+                    name
+                }
+            })
+            .collect())
     }
 
     pub fn ls_l(&self) -> Result<Vec<String>, Error> {
+        let local = self.local_names()?;
         Ok(self
             .config_locator
             .list_networks_long()?
             .iter()
             .filter_map(|(name, network, location)| {
+                let note = if shadowed(name, location, &local) {
+                    "Shadowed by a local network of the same name\n"
+                } else {
+                    ""
+                };
                 (!self.config_locator.global || matches!(location, Location::Global(_)))
-                    .then(|| Some(format!("{location}\nName: {name}\n{network:#?}\n")))?
+                    .then(|| Some(format!("{location}\n{note}Name: {name}\n{network:#?}\n")))?
             })
             .collect())
     }
+
+    pub fn ls_json(&self) -> Result<Vec<serde_json::Value>, Error> {
+        let local = self.local_names()?;
+        Ok(self
+            .config_locator
+            .list_networks_long()?
+            .into_iter()
+            .map(|(name, network, location)| {
+                serde_json::json!({
+                    "name": name,
+                    "scope": location.scope(),
+                    "shadowed": shadowed(&name, &location, &local),
+                    "rpc_url": network.rpc_url,
+                    "network_passphrase": network.network_passphrase,
+                })
+            })
+            .collect())
+    }
+
+    fn local_names(&self) -> Result<Vec<String>, Error> {
+        Ok(self
+            .config_locator
+            .list_network_locations()?
+            .into_iter()
+            .filter_map(|(name, location)| matches!(location, Location::Local(_)).then_some(name))
+            .collect())
+    }
+}
+
+/// Whether a network is global and a local network with the same name takes precedence over it.
+fn shadowed(name: &str, location: &Location, local: &[String]) -> bool {
+    matches!(location, Location::Global(_)) && local.iter().any(|local| local == name)
 }
@@ -1,22 +1,30 @@
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
 use clap::{arg, Parser};
+use http::{HeaderName, HeaderValue, Uri};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use stellar_strkey::ed25519::PublicKey;
 
 use crate::{
     commands::HEADING_RPC,
-    rpc::{self, Client},
+    rpc,
+    utils::{
+        proxy::{self, https_connector},
+        rpc::{ClientOptions, RetryingClient, DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT},
+    },
 };
 
 use super::config::locator;
 
 pub const LOCAL_NETWORK_PASSPHRASE: &str = "Standalone Network ; February 2017";
+pub const PUBLIC_NETWORK_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
 
 pub mod add;
 pub mod container;
+pub mod default;
 pub mod ls;
+pub mod ping;
 pub mod rm;
 
 #[derive(Debug, Parser)]
@@ -27,6 +35,15 @@ pub enum Cmd {
     Rm(rm::Cmd),
     /// List networks
     Ls(ls::Cmd),
+    /// Check that the network's RPC server is reachable, printing its passphrase, protocol
+    /// version, latest ledger, and latency. Exits with an error if the RPC server is unreachable
+    Ping(ping::Cmd),
+    /// Set the default network, or print it if no name is given
+    ///
+    /// The network used by a command is taken from, in order: the --network or --rpc-url
+    /// options, the `STELLAR_NETWORK` or `STELLAR_RPC_URL` environment variables, and then the
+    /// default network.
+    Default(default::Cmd),
     /// ⚠️ Deprecated: use `stellar container start` instead
     ///
     /// Start network
@@ -59,6 +76,12 @@ pub enum Error {
     #[error(transparent)]
     Ls(#[from] ls::Error),
 
+    #[error(transparent)]
+    Ping(#[from] ping::Error),
+
+    #[error(transparent)]
+    Default(#[from] default::Error),
+
     // TODO: remove once `network start` is removed
     #[error(transparent)]
     Start(#[from] container::start::Error),
@@ -73,8 +96,12 @@ pub enum Error {
     #[error(transparent)]
     Config(#[from] locator::Error),
 
-    #[error("network arg or rpc url and network passphrase are required if using the network")]
+    #[error("network arg or rpc url and network passphrase are required if using the network, or set a default with `stellar network default`")]
     Network,
+    #[error("the configured network passphrase {configured:?} does not match the RPC server's network passphrase {actual:?}, use --skip-passphrase-check to ignore")]
+    NetworkPassphraseMismatch { configured: String, actual: String },
+    #[error("cannot start a runtime to fetch the network passphrase: {0}")]
+    CannotFetchPassphrase(std::io::Error),
     #[error(transparent)]
     Http(#[from] http::Error),
     #[error(transparent)]
@@ -83,12 +110,41 @@ pub enum Error {
     Hyper(#[from] hyper::Error),
     #[error("Failed to parse JSON from {0}, {1}")]
     FailedToParseJSON(String, serde_json::Error),
+    #[error(transparent)]
+    Tls(#[from] hyper_tls::native_tls::Error),
+    #[error("--insecure can't be used on the public network")]
+    InsecurePubnet,
     #[error("Invalid URL {0}")]
     InvalidUrl(String),
     #[error("Inproper response {0}")]
     InproperResponse(String),
-    #[error("Currently not supported on windows. Please visit:\n{0}")]
-    WindowsNotSupported(String),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Ping(e) => e.exit_code(),
+            Error::CannotFetchPassphrase(_)
+            | Error::Http(_)
+            | Error::Hyper(_)
+            | Error::FailedToParseJSON(..)
+            | Error::InproperResponse(_) => crate::exit_code::NETWORK,
+            Error::Add(_)
+            | Error::Rm(_)
+            | Error::Ls(_)
+            | Error::Default(_)
+            | Error::Config(_)
+            | Error::Network
+            | Error::NetworkPassphraseMismatch { .. }
+            | Error::InsecurePubnet
+            | Error::InvalidUrl(_) => crate::exit_code::USAGE,
+            Error::Start(_) | Error::Stop(_) | Error::Container(_) | Error::Tls(_) => {
+                crate::exit_code::FAILURE
+            }
+        }
+    }
 }
 
 impl Cmd {
@@ -97,6 +153,8 @@ impl Cmd {
             Cmd::Add(cmd) => cmd.run()?,
             Cmd::Rm(new) => new.run()?,
             Cmd::Ls(cmd) => cmd.run()?,
+            Cmd::Ping(cmd) => cmd.run().await?,
+            Cmd::Default(cmd) => cmd.run()?,
             Cmd::Container(cmd) => cmd.run().await?,
 
             // TODO Remove this once `network start` is removed
@@ -114,14 +172,12 @@ impl Cmd {
     }
 }
 
-#[derive(Debug, clap::Args, Clone, Default)]
+#[derive(Debug, clap::Args, Clone)]
 #[group(skip)]
 pub struct Args {
     /// RPC server endpoint
     #[arg(
         long = "rpc-url",
-        requires = "network_passphrase",
-        required_unless_present = "network",
         env = "STELLAR_RPC_URL",
         help_heading = HEADING_RPC,
     )]
@@ -130,7 +186,6 @@ pub struct Args {
     #[arg(
         long = "network-passphrase",
         requires = "rpc_url",
-        required_unless_present = "network",
         env = "STELLAR_NETWORK_PASSPHRASE",
         help_heading = HEADING_RPC,
     )]
@@ -138,33 +193,163 @@ pub struct Args {
     /// Name of network to use from config
     #[arg(
         long,
-        required_unless_present = "rpc_url",
         env = "STELLAR_NETWORK",
         help_heading = HEADING_RPC,
     )]
     pub network: Option<String>,
+    /// Number of times to retry RPC requests that fail with a transient error, such as a
+    /// dropped connection or a 5xx response, with exponential backoff
+    #[arg(
+        long,
+        default_value = "0",
+        env = "STELLAR_RPC_RETRIES",
+        help_heading = HEADING_RPC,
+    )]
+    pub rpc_retries: u32,
+    /// Number of seconds to wait for each RPC request, including each retry, before failing it.
+    /// Use 0 for no timeout
+    #[arg(
+        long,
+        default_value_t = DEFAULT_TIMEOUT.as_secs(),
+        env = "STELLAR_RPC_TIMEOUT",
+        help_heading = HEADING_RPC,
+    )]
+    pub rpc_timeout: u64,
+    /// Number of seconds to wait for each connection to the RPC server before failing the request.
+    /// Use 0 for no connect timeout
+    #[arg(
+        long,
+        default_value_t = DEFAULT_CONNECT_TIMEOUT.as_secs(),
+        env = "STELLAR_RPC_CONNECT_TIMEOUT",
+        help_heading = HEADING_RPC,
+    )]
+    pub rpc_connect_timeout: u64,
+    /// Header to send with every RPC request, as `Name: Value`, e.g. for an API key. Can be
+    /// repeated. Separate headers with newlines in `STELLAR_RPC_HEADERS`
+    #[arg(
+        long = "rpc-header",
+        env = "STELLAR_RPC_HEADERS",
+        value_parser = parse_header,
+        value_delimiter = '\n',
+        help_heading = HEADING_RPC,
+    )]
+    pub rpc_headers: Vec<(HeaderName, HeaderValue)>,
+    /// HTTP proxy to send RPC and friendbot requests through, e.g. `http://proxy.example:3128`.
+    /// Defaults to the `HTTPS_PROXY` or `ALL_PROXY` environment variable
+    #[arg(long, value_parser = proxy::parse_proxy, help_heading = HEADING_RPC)]
+    pub proxy: Option<Uri>,
+    /// Accept invalid TLS certificates from the RPC server and friendbot, e.g. a local one with a
+    /// self-signed certificate. Refused on the public network
+    #[arg(long, env = "STELLAR_RPC_INSECURE", help_heading = HEADING_RPC)]
+    pub insecure: bool,
+    /// Don't check that the RPC server's network passphrase matches the configured one
+    #[arg(long, env = "STELLAR_SKIP_PASSPHRASE_CHECK", help_heading = HEADING_RPC)]
+    pub skip_passphrase_check: bool,
+    /// Fetch the network passphrase from the RPC server given with --rpc-url when
+    /// --network-passphrase isn't set
+    #[arg(long, requires = "rpc_url", help_heading = HEADING_RPC)]
+    pub auto_passphrase: bool,
+}
+
+impl Default for Args {
+    /// The same defaults as on the command line.
+    fn default() -> Self {
+        Self {
+            rpc_url: None,
+            network_passphrase: None,
+            network: None,
+            rpc_retries: 0,
+            rpc_timeout: DEFAULT_TIMEOUT.as_secs(),
+            rpc_connect_timeout: DEFAULT_CONNECT_TIMEOUT.as_secs(),
+            rpc_headers: Vec::new(),
+            proxy: None,
+            insecure: false,
+            skip_passphrase_check: false,
+            auto_passphrase: false,
+        }
+    }
 }
 
 impl Args {
     pub fn get(&self, locator: &locator::Args) -> Result<Network, Error> {
+        let network = self.resolve(locator)?;
+        if self.insecure && network.network_passphrase == PUBLIC_NETWORK_PASSPHRASE {
+            return Err(Error::InsecurePubnet);
+        }
+        Ok(network)
+    }
+
+    fn resolve(&self, locator: &locator::Args) -> Result<Network, Error> {
+        if self.network.is_none() && self.rpc_url.is_none() {
+            if let Some(name) = locator.default_network()? {
+                return Ok(locator.read_network(&name)?);
+            }
+        }
         if let Some(name) = self.network.as_deref() {
             if let Ok(network) = locator.read_network(name) {
                 return Ok(network);
             }
         }
-        if let (Some(rpc_url), Some(network_passphrase)) =
-            (self.rpc_url.clone(), self.network_passphrase.clone())
-        {
-            Ok(Network {
-                rpc_url,
-                network_passphrase,
-            })
-        } else {
-            Err(Error::Network)
+        let (rpc_url, network_passphrase) = match (&self.rpc_url, &self.network_passphrase) {
+            (Some(rpc_url), Some(network_passphrase)) => {
+                (rpc_url.clone(), network_passphrase.clone())
+            }
+            (Some(rpc_url), None) if self.auto_passphrase => (
+                rpc_url.clone(),
+                fetch_network_passphrase(RetryingClient::new(
+                    rpc_url,
+                    &self.rpc_options().client,
+                )?)?,
+            ),
+            _ => return Err(Error::Network),
+        };
+        Ok(Network {
+            rpc_url,
+            network_passphrase,
+        })
+    }
+
+    /// Options for connecting to the network's RPC server and friendbot.
+    pub fn rpc_options(&self) -> RpcOptions {
+        let secs = |secs| (secs > 0).then(|| Duration::from_secs(secs));
+        RpcOptions {
+            client: ClientOptions {
+                retries: self.rpc_retries,
+                timeout: secs(self.rpc_timeout),
+                connect_timeout: secs(self.rpc_connect_timeout),
+                headers: self.rpc_headers.iter().cloned().collect(),
+                proxy: self.proxy.clone(),
+                insecure: self.insecure,
+            },
+            skip_passphrase_check: self.skip_passphrase_check,
         }
     }
 }
 
+/// Parse a header given as `Name: Value`.
+fn parse_header(header: &str) -> Result<(HeaderName, HeaderValue), String> {
+    let (name, value) = header
+        .split_once(':')
+        .ok_or_else(|| format!("expected `Name: Value`, got {header:?}"))?;
+    let name = HeaderName::from_str(name.trim()).map_err(|e| e.to_string())?;
+    let value = HeaderValue::from_str(value.trim()).map_err(|e| e.to_string())?;
+    Ok((name, value))
+}
+
+/// Fetch the network passphrase with `getNetwork`. [`Args::get`] isn't async, so the request is
+/// made on a thread with its own runtime, which works whether or not a runtime is running.
+fn fetch_network_passphrase(client: RetryingClient) -> Result<String, Error> {
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(Error::CannotFetchPassphrase)?;
+        Ok(runtime.block_on(client.get_network())?.passphrase)
+    })
+    .join()
+    .unwrap_or_else(|_| Err(Error::Network))
+}
+
 #[derive(Debug, clap::Args, Serialize, Deserialize, Clone)]
 #[group(skip)]
 pub struct Network {
@@ -184,8 +369,19 @@ pub struct Network {
     pub network_passphrase: String,
 }
 
+/// Options for connecting to a network's RPC server and friendbot, from [`Args`]. They're kept
+/// apart from [`Network`] as they aren't saved with it.
+#[derive(Debug, Clone, Default)]
+pub struct RpcOptions {
+    /// Options for the RPC client
+    pub client: ClientOptions,
+    /// Whether to skip checking the RPC server's network passphrase, see
+    /// [`Network::rpc_client`]
+    pub skip_passphrase_check: bool,
+}
+
 impl Network {
-    pub async fn helper_url(&self, addr: &str) -> Result<http::Uri, Error> {
+    pub async fn helper_url(&self, addr: &str, options: &RpcOptions) -> Result<http::Uri, Error> {
         use http::Uri;
         tracing::debug!("address {addr:?}");
         let rpc_uri = Uri::from_str(&self.rpc_url)
@@ -199,7 +395,7 @@ impl Network {
                 .path_and_query(format!("/friendbot?addr={addr}"))
                 .build()?)
         } else {
-            let client = Client::new(&self.rpc_url)?;
+            let client = self.rpc_client(options).await?;
             let network = client.get_network().await?;
             tracing::debug!("network {network:?}");
             let uri = client.friendbot_url().await?;
@@ -212,22 +408,21 @@ impl Network {
     }
 
     #[allow(clippy::similar_names)]
-    pub async fn fund_address(&self, addr: &PublicKey) -> Result<(), Error> {
-        let uri = self.helper_url(&addr.to_string()).await?;
+    pub async fn fund_address(&self, addr: &PublicKey, options: &RpcOptions) -> Result<(), Error> {
+        let uri = self.helper_url(&addr.to_string(), options).await?;
         tracing::debug!("URL {uri:?}");
-        let response = match uri.scheme_str() {
-            Some("http") => hyper::Client::new().get(uri.clone()).await?,
-            Some("https") => {
-                let https = hyper_tls::HttpsConnector::new();
-                hyper::Client::builder()
-                    .build::<_, hyper::Body>(https)
-                    .get(uri.clone())
-                    .await?
-            }
-            _ => {
-                return Err(Error::InvalidUrl(uri.to_string()));
-            }
-        };
+        if !matches!(uri.scheme_str(), Some("http" | "https")) {
+            return Err(Error::InvalidUrl(uri.to_string()));
+        }
+        let https = https_connector(
+            options.client.proxy.clone(),
+            options.client.insecure,
+            options.client.connect_timeout,
+        )?;
+        let response = hyper::Client::builder()
+            .build::<_, hyper::Body>(https)
+            .get(uri.clone())
+            .await?;
         let body = hyper::body::to_bytes(response.into_body()).await?;
         let res = serde_json::from_slice::<serde_json::Value>(&body)
             .map_err(|e| Error::FailedToParseJSON(uri.to_string(), e))?;
@@ -242,6 +437,23 @@ impl Network {
         Ok(())
     }
 
+    /// Create a client for the network's RPC server, checking that the server reports the
+    /// configured network passphrase unless `options` skip it, so that a transaction isn't signed
+    /// for a different network than the one it's sent to.
+    pub async fn rpc_client(&self, options: &RpcOptions) -> Result<RetryingClient, Error> {
+        let client = RetryingClient::new(&self.rpc_url, &options.client)?;
+        if !options.skip_passphrase_check {
+            let actual = client.get_network().await?.passphrase;
+            if actual != self.network_passphrase {
+                return Err(Error::NetworkPassphraseMismatch {
+                    configured: self.network_passphrase.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(client)
+    }
+
     pub fn rpc_uri(&self) -> Result<http::Uri, Error> {
         http::Uri::from_str(&self.rpc_url).map_err(|_| Error::InvalidUrl(self.rpc_url.to_string()))
     }
@@ -255,3 +467,56 @@ impl Network {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::rpc::tests::mock_server;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn rpc_client_checks_the_passphrase() {
+        let url = mock_server(
+            |_| json!({ "result": { "passphrase": "Other Network", "protocolVersion": 21 } }),
+        )
+        .await;
+        let network = Network {
+            rpc_url: url,
+            network_passphrase: LOCAL_NETWORK_PASSPHRASE.to_string(),
+        };
+        assert!(matches!(
+            network.rpc_client(&RpcOptions::default()).await,
+            Err(Error::NetworkPassphraseMismatch { actual, .. }) if actual == "Other Network"
+        ));
+        let options = RpcOptions {
+            skip_passphrase_check: true,
+            ..RpcOptions::default()
+        };
+        assert!(network.rpc_client(&options).await.is_ok());
+    }
+
+    #[test]
+    fn insecure_is_refused_on_pubnet() {
+        let args = |passphrase: &str| Args {
+            rpc_url: Some("https://localhost:8000".to_string()),
+            network_passphrase: Some(passphrase.to_string()),
+            insecure: true,
+            ..Args::default()
+        };
+        let locator = locator::Args::default();
+        assert!(matches!(
+            args(PUBLIC_NETWORK_PASSPHRASE).get(&locator),
+            Err(Error::InsecurePubnet)
+        ));
+        assert!(args(LOCAL_NETWORK_PASSPHRASE).get(&locator).is_ok());
+    }
+
+    #[test]
+    fn parses_rpc_headers() {
+        let (name, value) = parse_header("X-Api-Key:  secret ").unwrap();
+        assert_eq!(name, "x-api-key");
+        assert_eq!(value, "secret");
+        assert!(parse_header("X-Api-Key").is_err());
+        assert!(parse_header("Not A Name: value").is_err());
+    }
+}
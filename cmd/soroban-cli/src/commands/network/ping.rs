@@ -0,0 +1,57 @@
+use std::time::Instant;
+
+use crate::{rpc, utils::rpc::RetryingClient};
+
+use super::super::config::locator;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Network(#[from] Box<super::Error>),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error("RPC server at {0} is not healthy, it reported status {1:?}")]
+    Unhealthy(String, String),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Network(e) => e.exit_code(),
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Unhealthy(..) => crate::exit_code::NETWORK,
+        }
+    }
+}
+
+/// Check that the network's RPC server is reachable and healthy
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub network: super::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let network = self.network.get(&self.locator).map_err(Box::new)?;
+        // Not checking the passphrase, as the server's passphrase is printed below
+        let client = RetryingClient::new(&network.rpc_url, &self.network.rpc_options().client)?;
+        let start = Instant::now();
+        let health = client.get_health().await?;
+        let latency = start.elapsed();
+        if health.status != "healthy" {
+            return Err(Error::Unhealthy(network.rpc_url, health.status));
+        }
+        let info = client.get_network().await?;
+        let ledger = client.get_latest_ledger().await?;
+        println!("Network passphrase: {}", info.passphrase);
+        println!("Protocol version: {}", info.protocol_version);
+        println!("Latest ledger: {}", ledger.sequence);
+        println!("Latency: {} ms", latency.as_millis());
+        Ok(())
+    }
+}
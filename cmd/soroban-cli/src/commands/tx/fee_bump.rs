@@ -0,0 +1,150 @@
+use std::path::PathBuf;
+
+use ed25519_dalek::Signer;
+
+use crate::commands::config;
+use crate::utils;
+use crate::xdr::{
+    self, DecoratedSignature, FeeBumpTransaction, FeeBumpTransactionEnvelope,
+    FeeBumpTransactionExt, FeeBumpTransactionInnerTx, Limits, MuxedAccount, Signature,
+    SignatureHint, TransactionEnvelope, TransactionV1Envelope, Uint256, WriteXdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    TryFromSlice(#[from] std::array::TryFromSliceError),
+    #[error("fee bump can only wrap a transaction v1 envelope, not a {0} envelope")]
+    CannotWrap(&'static str),
+    #[error(
+        "fee of {fee} stroops is less than the inner transaction's fee of {inner_fee} stroops"
+    )]
+    FeeTooLow { fee: i64, inner_fee: u32 },
+}
+
+/// Command to wrap a transaction envelope from stdin or a file in a fee bump transaction, paid
+/// for and signed by the source account. The RPC server's network passphrase is checked against
+/// the configured one before signing, unless --skip-passphrase-check is given
+/// e.g. `cat file.txt | soroban tx fee-bump --fee 10000 --source alice`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Total fee the source account pays for the fee bump transaction, in stroops. Must be at
+    /// least the inner transaction's fee
+    #[arg(long)]
+    pub fee: i64,
+    /// Read the inner base64 transaction envelope from this file instead of stdin
+    #[arg(long)]
+    pub input_file: Option<PathBuf>,
+    #[command(flatten)]
+    pub config: config::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let tx_env = super::xdr::tx_envelope_from_input(self.input_file.as_deref())?;
+        let inner = match tx_env {
+            TransactionEnvelope::Tx(inner) => inner,
+            TransactionEnvelope::TxV0(_) => return Err(Error::CannotWrap("transaction v0")),
+            TransactionEnvelope::TxFeeBump(_) => return Err(Error::CannotWrap("fee bump")),
+        };
+        let key = self.config.key_pair()?;
+        let network = self.config.get_network()?;
+        self.config.rpc_client(&network).await?;
+        let tx_env = fee_bump(inner, self.fee, &key, &network.network_passphrase)?;
+        println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+        Ok(())
+    }
+}
+
+/// Wrap `inner` in a fee bump transaction paying `fee`, signed by `key` as the fee source.
+fn fee_bump(
+    inner: TransactionV1Envelope,
+    fee: i64,
+    key: &ed25519_dalek::SigningKey,
+    network_passphrase: &str,
+) -> Result<TransactionEnvelope, Error> {
+    if fee < i64::from(inner.tx.fee) {
+        return Err(Error::FeeTooLow {
+            fee,
+            inner_fee: inner.tx.fee,
+        });
+    }
+    let tx = FeeBumpTransaction {
+        fee_source: MuxedAccount::Ed25519(Uint256(key.verifying_key().to_bytes())),
+        fee,
+        inner_tx: FeeBumpTransactionInnerTx::Tx(inner),
+        ext: FeeBumpTransactionExt::V0,
+    };
+    let mut tx_env = FeeBumpTransactionEnvelope {
+        tx,
+        signatures: xdr::VecM::default(),
+    };
+    let hash = utils::hash_transaction_in_envelope(
+        &TransactionEnvelope::TxFeeBump(tx_env.clone()),
+        network_passphrase,
+    )?;
+    tx_env.signatures = vec![DecoratedSignature {
+        hint: SignatureHint(key.verifying_key().to_bytes()[28..].try_into()?),
+        signature: Signature(key.sign(&hash).to_bytes().try_into()?),
+    }]
+    .try_into()?;
+    Ok(TransactionEnvelope::TxFeeBump(tx_env))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{Memo, Preconditions, SequenceNumber, Transaction, TransactionExt};
+    use ed25519_dalek::SigningKey;
+
+    fn inner(fee: u32) -> TransactionV1Envelope {
+        TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+                fee,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: xdr::VecM::default(),
+                ext: TransactionExt::V0,
+            },
+            signatures: xdr::VecM::default(),
+        }
+    }
+
+    #[test]
+    fn signs_fee_bump_as_fee_source() {
+        let key = SigningKey::from_bytes(&[2; 32]);
+        let passphrase = "Test SDF Network ; September 2015";
+        let tx_env = fee_bump(inner(100), 1_000, &key, passphrase).unwrap();
+        let hash = utils::hash_transaction_in_envelope(&tx_env, passphrase).unwrap();
+        let TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, signatures }) = tx_env
+        else {
+            panic!("expected a fee bump envelope");
+        };
+        assert_eq!(tx.fee, 1_000);
+        assert_eq!(
+            tx.fee_source,
+            MuxedAccount::Ed25519(Uint256(key.verifying_key().to_bytes()))
+        );
+        let signature =
+            ed25519_dalek::Signature::from_slice(signatures[0].signature.as_slice()).unwrap();
+        assert!(key.verify(&hash, &signature).is_ok());
+    }
+
+    #[test]
+    fn rejects_fee_below_inner_fee() {
+        let key = SigningKey::from_bytes(&[2; 32]);
+        assert!(matches!(
+            fee_bump(inner(100), 99, &key, "passphrase"),
+            Err(Error::FeeTooLow { .. })
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::commands::{config::locator, network};
+use crate::{utils, xdr};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+/// Command to print the hash of a transaction envelope from stdin
+/// e.g. `cat file.txt | soroban tx hash`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Print the hash as a pre-authorized transaction strkey (`T...`) instead of hex
+    #[arg(long)]
+    pub strkey: bool,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let tx_env = super::xdr::tx_envelope_from_stdin()?;
+        let network = self.network.get(&self.locator)?;
+        let hash = utils::hash_transaction_in_envelope(&tx_env, &network.network_passphrase)?;
+        println!("{}", format_hash(hash, self.strkey));
+        Ok(())
+    }
+}
+
+fn format_hash(hash: [u8; 32], strkey: bool) -> String {
+    if strkey {
+        stellar_strkey::PreAuthTx(hash).to_string()
+    } else {
+        hex::encode(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_hash_as_hex_or_strkey() {
+        let hash = [0xab; 32];
+        assert_eq!(format_hash(hash, false), "ab".repeat(32));
+        let strkey = format_hash(hash, true);
+        assert!(strkey.starts_with('T'));
+        assert_eq!(
+            stellar_strkey::PreAuthTx::from_string(&strkey).unwrap().0,
+            hash
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::io::{self, Write};
+
+use crate::utils;
+
+use crate::xdr::{
+    self, FeeBumpTransactionInnerTx, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Memo,
+    MuxedAccount, OperationBody, ScAddress, Transaction, TransactionEnvelope, TransactionExt,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Command to decode a transaction envelope from stdin and print it in a readable form
+/// e.g. `cat file.txt | soroban tx inspect`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let tx_env = super::xdr::tx_envelope_from_stdin()?;
+        write_envelope(&mut io::stdout(), &tx_env)
+    }
+}
+
+fn write_envelope(out: &mut impl Write, tx_env: &TransactionEnvelope) -> Result<(), Error> {
+    match tx_env {
+        TransactionEnvelope::TxV0(env) => {
+            write_transaction(out, &utils::transaction_from_v0(&env.tx))?;
+            writeln!(out, "Signatures: {}", env.signatures.len())?;
+        }
+        TransactionEnvelope::Tx(env) => {
+            write_transaction(out, &env.tx)?;
+            writeln!(out, "Signatures: {}", env.signatures.len())?;
+        }
+        TransactionEnvelope::TxFeeBump(env) => {
+            writeln!(
+                out,
+                "Fee bump source: {}",
+                muxed_account(&env.tx.fee_source)
+            )?;
+            writeln!(out, "Fee bump fee: {} stroops", env.tx.fee)?;
+            writeln!(out, "Fee bump signatures: {}", env.signatures.len())?;
+            let FeeBumpTransactionInnerTx::Tx(inner) = &env.tx.inner_tx;
+            writeln!(out, "Inner transaction:")?;
+            write_transaction(out, &inner.tx)?;
+            writeln!(out, "Signatures: {}", inner.signatures.len())?;
+        }
+    }
+    Ok(())
+}
+
+fn write_transaction(out: &mut impl Write, tx: &Transaction) -> Result<(), Error> {
+    writeln!(out, "Source account: {}", muxed_account(&tx.source_account))?;
+    writeln!(out, "Fee: {} stroops", tx.fee)?;
+    writeln!(out, "Sequence number: {}", tx.seq_num.0)?;
+    match &tx.memo {
+        Memo::None => {}
+        Memo::Text(text) => writeln!(out, "Memo (text): {}", text.to_utf8_string_lossy())?,
+        Memo::Id(id) => writeln!(out, "Memo (id): {id}")?,
+        Memo::Hash(hash) => writeln!(out, "Memo (hash): {}", hex::encode(hash.0))?,
+        Memo::Return(hash) => writeln!(out, "Memo (return): {}", hex::encode(hash.0))?,
+    }
+    writeln!(out, "Operations: {}", tx.operations.len())?;
+    for (i, op) in tx.operations.iter().enumerate() {
+        writeln!(out, "  {i}: {}", op.body.name())?;
+        if let Some(source) = &op.source_account {
+            writeln!(out, "    Source account: {}", muxed_account(source))?;
+        }
+        if let OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function,
+            auth,
+        }) = &op.body
+        {
+            writeln!(out, "    Host function: {}", host_function.name())?;
+            if let HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address,
+                function_name,
+                args,
+            }) = host_function
+            {
+                writeln!(out, "    Contract: {}", sc_address(contract_address))?;
+                writeln!(
+                    out,
+                    "    Function: {}",
+                    function_name.to_utf8_string_lossy()
+                )?;
+                for arg in args.iter() {
+                    writeln!(out, "    Arg: {}", soroban_spec_tools::to_json(arg)?)?;
+                }
+            }
+            writeln!(out, "    Auth entries: {}", auth.len())?;
+        }
+    }
+    if let TransactionExt::V1(data) = &tx.ext {
+        let resources = &data.resources;
+        writeln!(out, "Soroban resources:")?;
+        writeln!(out, "  Instructions: {}", resources.instructions)?;
+        writeln!(out, "  Read bytes: {}", resources.read_bytes)?;
+        writeln!(out, "  Write bytes: {}", resources.write_bytes)?;
+        writeln!(out, "  Resource fee: {} stroops", data.resource_fee)?;
+        writeln!(
+            out,
+            "  Footprint: {} read-only, {} read-write",
+            resources.footprint.read_only.len(),
+            resources.footprint.read_write.len()
+        )?;
+    }
+    Ok(())
+}
+
+fn muxed_account(account: &MuxedAccount) -> String {
+    match account {
+        MuxedAccount::Ed25519(key) => stellar_strkey::ed25519::PublicKey(key.0).to_string(),
+        MuxedAccount::MuxedEd25519(muxed) => stellar_strkey::ed25519::MuxedAccount {
+            ed25519: muxed.ed25519.0,
+            id: muxed.id,
+        }
+        .to_string(),
+    }
+}
+
+fn sc_address(address: &ScAddress) -> String {
+    match address {
+        ScAddress::Account(xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(key))) => {
+            stellar_strkey::ed25519::PublicKey(key.0).to_string()
+        }
+        ScAddress::Contract(hash) => stellar_strkey::Contract(hash.0).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        Hash, Operation, Preconditions, ScSymbol, ScVal, SequenceNumber, TransactionV1Envelope,
+        Uint256,
+    };
+
+    #[test]
+    fn prints_invoke_contract_details() {
+        let tx = Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(5),
+            cond: Preconditions::None,
+            memo: Memo::Id(7),
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                    host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                        contract_address: ScAddress::Contract(Hash([0; 32])),
+                        function_name: ScSymbol("hello".try_into().unwrap()),
+                        args: vec![ScVal::U32(3)].try_into().unwrap(),
+                    }),
+                    auth: vec![].try_into().unwrap(),
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        };
+        let tx_env = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: vec![].try_into().unwrap(),
+        });
+        let mut out = Vec::new();
+        write_envelope(&mut out, &tx_env).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\
+Source account: GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF
+Fee: 100 stroops
+Sequence number: 5
+Memo (id): 7
+Operations: 1
+  0: InvokeHostFunction
+    Host function: InvokeContract
+    Contract: CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4
+    Function: hello
+    Arg: 3
+    Auth entries: 0
+Signatures: 0
+"
+        );
+    }
+}
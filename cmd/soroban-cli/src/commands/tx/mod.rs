@@ -2,25 +2,68 @@ use clap::Parser;
 
 use super::global;
 
+pub mod fee_bump;
+pub mod hash;
+pub mod inspect;
+pub mod sign;
 pub mod simulate;
 pub mod xdr;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
+    /// Wrap a transaction envelope from stdin or a file in a fee bump transaction paid for and
+    /// signed by the source account
+    FeeBump(fee_bump::Cmd),
+    /// Print the hash of a transaction envelope from stdin, for the configured network
+    Hash(hash::Cmd),
+    /// Decode a transaction envelope from stdin and print its fields
+    Inspect(inspect::Cmd),
+    /// Sign a transaction envelope from stdin or a file with the source account, which can be a
+    /// Ledger identity added with `keys add --ledger`, or print its hash
+    Sign(sign::Cmd),
     /// Simulate a transaction envelope from stdin
     Simulate(simulate::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    /// An error during fee bumping
+    #[error(transparent)]
+    FeeBump(#[from] fee_bump::Error),
+    /// An error during hashing
+    #[error(transparent)]
+    Hash(#[from] hash::Error),
+    /// An error during inspection
+    #[error(transparent)]
+    Inspect(#[from] inspect::Error),
+    /// An error during signing
+    #[error(transparent)]
+    Sign(#[from] sign::Error),
     /// An error during the simulation
     #[error(transparent)]
     Simulate(#[from] simulate::Error),
 }
 
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Sign(e) => e.exit_code(),
+            Error::Simulate(e) => e.exit_code(),
+            Error::FeeBump(fee_bump::Error::Config(e)) => e.exit_code(),
+            Error::Hash(hash::Error::Network(e)) => e.exit_code(),
+            _ => crate::exit_code::USAGE,
+        }
+    }
+}
+
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
+            Cmd::FeeBump(cmd) => cmd.run().await?,
+            Cmd::Hash(cmd) => cmd.run()?,
+            Cmd::Inspect(cmd) => cmd.run()?,
+            Cmd::Sign(cmd) => cmd.run().await?,
             Cmd::Simulate(cmd) => cmd.run(global_args).await?,
         };
         Ok(())
@@ -0,0 +1,261 @@
+use std::path::PathBuf;
+
+use ed25519_dalek::{Signer, Verifier, VerifyingKey};
+
+use crate::xdr::{
+    self, AccountEntry, DecoratedSignature, Limits, MuxedAccount, Signature, SignatureHint,
+    SignerKey, Transaction, TransactionEnvelope, TransactionV1Envelope, WriteXdr,
+};
+
+use crate::commands::{config, network};
+use crate::{signer, utils};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Config(#[from] config::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    TryFromSlice(#[from] std::array::TryFromSliceError),
+    #[error(transparent)]
+    Ledger(#[from] signer::ledger::Error),
+    #[error("--output-file already exists and you did not specify --overwrite: {0:?}")]
+    OutputFileExists(PathBuf),
+    #[error("failed to write {path:?}: {error}")]
+    CannotWriteOutputFile {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Config(e) => e.exit_code(),
+            Error::XdrArgs(_) | Error::OutputFileExists(_) => crate::exit_code::USAGE,
+            _ => crate::exit_code::FAILURE,
+        }
+    }
+}
+
+/// Command to sign a transaction envelope from stdin or a file
+/// e.g. `cat file.txt | soroban tx sign` or `soroban tx sign --input-file file.txt`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Only print the hash that would be signed, in hex, e.g. to sign on an external device
+    #[arg(long)]
+    pub hash_only: bool,
+    /// Read the base64 transaction envelope from this file instead of stdin
+    #[arg(long)]
+    pub input_file: Option<PathBuf>,
+    /// Write the signed base64 transaction envelope to this file, without a trailing newline,
+    /// instead of stdout
+    #[arg(long, conflicts_with = "hash_only")]
+    pub output_file: Option<PathBuf>,
+    /// Whether to overwrite the output file if it already exists
+    #[arg(long, requires = "output_file")]
+    pub overwrite: bool,
+    /// After signing, fetch the source account's signers from the network and warn if the
+    /// signatures on the envelope don't meet its medium threshold
+    #[arg(long, conflicts_with = "hash_only")]
+    pub verify_threshold: bool,
+    #[command(flatten)]
+    pub config: config::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let tx_env = super::xdr::tx_envelope_from_input(self.input_file.as_deref())?;
+        let network = self.config.get_network()?;
+        let hash = utils::hash_transaction_in_envelope(&tx_env, &network.network_passphrase)?;
+        if self.hash_only {
+            println!("{}", hex::encode(hash));
+            return Ok(());
+        }
+        let TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) = tx_env else {
+            return Err(super::xdr::Error::OnlyTransactionV1Supported.into());
+        };
+        let ledger = self.config.ledger_key()?;
+        let verifying_key = match &ledger {
+            Some(ledger) => ledger.verifying_key().map_err(config::Error::from)?,
+            None => self.config.key_pair()?.verifying_key(),
+        };
+        let mut signatures = signatures.to_vec();
+        if signed_by(&signatures, &verifying_key, &hash) {
+            tracing::warn!("transaction is already signed by this key, not signing again");
+        } else if let Some(ledger) = &ledger {
+            signatures
+                .push(signer::ledger::sign_tx(ledger, &tx, &network.network_passphrase).await?);
+        } else {
+            let key = self.config.key_pair()?;
+            signatures.push(DecoratedSignature {
+                hint: SignatureHint(key.verifying_key().to_bytes()[28..].try_into()?),
+                signature: Signature(key.sign(&hash).to_bytes().try_into()?),
+            });
+        }
+        if self.verify_threshold {
+            match source_account(&self.config, &network, &tx).await {
+                Ok(account) => {
+                    let weight = signature_weight(&account, &signatures, &hash);
+                    let threshold = u32::from(account.thresholds.0[2]).max(1);
+                    if weight < threshold {
+                        tracing::warn!(
+                            "signatures have a combined weight of {weight}, below the source \
+                             account's medium threshold of {threshold}"
+                        );
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("could not fetch the source account to verify threshold: {e}");
+                }
+            }
+        }
+        let tx_env = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: signatures.try_into()?,
+        })
+        .to_xdr_base64(Limits::none())?;
+        if let Some(path) = &self.output_file {
+            if path.exists() && !self.overwrite {
+                return Err(Error::OutputFileExists(path.clone()));
+            }
+            std::fs::write(path, tx_env).map_err(|error| Error::CannotWriteOutputFile {
+                path: path.clone(),
+                error,
+            })?;
+        } else {
+            println!("{tx_env}");
+        }
+        Ok(())
+    }
+}
+
+async fn source_account(
+    config: &config::Args,
+    network: &network::Network,
+    tx: &Transaction,
+) -> Result<AccountEntry, config::Error> {
+    let source = match &tx.source_account {
+        MuxedAccount::Ed25519(key) => key,
+        MuxedAccount::MuxedEd25519(muxed) => &muxed.ed25519,
+    };
+    let address = stellar_strkey::ed25519::PublicKey(source.0).to_string();
+    let client = config.rpc_client(network).await?;
+    Ok(client.get_account(&address).await?)
+}
+
+/// Whether any of `signatures` is a valid signature of `hash` by `key`.
+fn signed_by(signatures: &[DecoratedSignature], key: &VerifyingKey, hash: &[u8; 32]) -> bool {
+    let hint = &key.as_bytes()[28..];
+    signatures.iter().any(|s| {
+        s.hint.0 == hint
+            && ed25519_dalek::Signature::from_slice(s.signature.as_slice())
+                .is_ok_and(|sig| key.verify(hash, &sig).is_ok())
+    })
+}
+
+/// Sum the weights of the account's master key and ed25519 signers that have signed `hash`.
+fn signature_weight(
+    account: &AccountEntry,
+    signatures: &[DecoratedSignature],
+    hash: &[u8; 32],
+) -> u32 {
+    let xdr::PublicKey::PublicKeyTypeEd25519(master) = &account.account_id.0;
+    let master = (master.0, u32::from(account.thresholds.0[0]));
+    let signers = account.signers.iter().filter_map(|s| match &s.key {
+        SignerKey::Ed25519(key) => Some((key.0, s.weight)),
+        _ => None,
+    });
+    std::iter::once(master)
+        .chain(signers)
+        .filter(|(key, _)| {
+            VerifyingKey::from_bytes(key).is_ok_and(|key| signed_by(signatures, &key, hash))
+        })
+        .map(|(_, weight)| weight)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{
+        AccountEntryExt, AccountId, PublicKey, SequenceNumber, Signer as AccountSigner, String32,
+        Thresholds, Uint256,
+    };
+    use ed25519_dalek::SigningKey;
+
+    fn sign(key: &SigningKey, hash: &[u8; 32]) -> DecoratedSignature {
+        DecoratedSignature {
+            hint: SignatureHint(key.verifying_key().to_bytes()[28..].try_into().unwrap()),
+            signature: Signature(key.sign(hash).to_bytes().try_into().unwrap()),
+        }
+    }
+
+    fn account(master: &SigningKey, signer: &SigningKey) -> AccountEntry {
+        AccountEntry {
+            account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+                master.verifying_key().to_bytes(),
+            ))),
+            balance: 0,
+            seq_num: SequenceNumber(0),
+            num_sub_entries: 1,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: String32::default(),
+            thresholds: Thresholds([1, 0, 2, 0]),
+            signers: vec![AccountSigner {
+                key: SignerKey::Ed25519(Uint256(signer.verifying_key().to_bytes())),
+                weight: 1,
+            }]
+            .try_into()
+            .unwrap(),
+            ext: AccountEntryExt::V0,
+        }
+    }
+
+    #[test]
+    fn detects_existing_signature() {
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let other = SigningKey::from_bytes(&[2; 32]);
+        let hash = [7; 32];
+        let signatures = [sign(&other, &hash), sign(&key, &hash)];
+        assert!(signed_by(&signatures, &key.verifying_key(), &hash));
+        assert!(!signed_by(&signatures[..1], &key.verifying_key(), &hash));
+        // A signature of a different hash doesn't count
+        assert!(!signed_by(
+            &[sign(&key, &[8; 32])],
+            &key.verifying_key(),
+            &hash
+        ));
+    }
+
+    #[test]
+    fn sums_weights_of_valid_signers() {
+        let master = SigningKey::from_bytes(&[1; 32]);
+        let signer = SigningKey::from_bytes(&[2; 32]);
+        let stranger = SigningKey::from_bytes(&[3; 32]);
+        let account = account(&master, &signer);
+        let hash = [7; 32];
+        assert_eq!(
+            signature_weight(&account, &[sign(&master, &hash)], &hash),
+            1
+        );
+        assert_eq!(
+            signature_weight(
+                &account,
+                &[
+                    sign(&master, &hash),
+                    sign(&signer, &hash),
+                    sign(&stranger, &hash)
+                ],
+                &hash
+            ),
+            2
+        );
+    }
+}
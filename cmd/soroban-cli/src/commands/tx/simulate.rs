@@ -16,6 +16,18 @@ pub enum Error {
     Xdr(#[from] xdr::Error),
 }
 
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Config(e) => e.exit_code(),
+            Error::XdrArgs(_) => crate::exit_code::USAGE,
+            Error::Xdr(_) => crate::exit_code::FAILURE,
+        }
+    }
+}
+
 /// Command to simulate a transaction envelope via rpc
 /// e.g. `cat file.txt | soroban tx simulate`
 #[derive(Debug, clap::Parser, Clone, Default)]
@@ -48,7 +60,7 @@ impl NetworkRunnable for Cmd {
     ) -> Result<Self::Result, Self::Error> {
         let config = config.unwrap_or(&self.config);
         let network = config.get_network()?;
-        let client = crate::rpc::Client::new(&network.rpc_url)?;
+        let client = config.rpc_client(&network).await?;
         let tx = super::xdr::unwrap_envelope_v1(super::xdr::tx_envelope_from_stdin()?)?;
         Ok(client.simulate_and_assemble_transaction(&tx).await?)
     }
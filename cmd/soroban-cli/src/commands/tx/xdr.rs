@@ -1,6 +1,7 @@
 use std::{
+    fs::File,
     io::{stdin, Read},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use soroban_env_host::xdr::ReadXdr;
@@ -23,12 +24,23 @@ pub enum Error {
 pub fn tx_envelope_from_stdin() -> Result<TransactionEnvelope, Error> {
     from_stdin()
 }
+
+/// Read a transaction envelope from `input` if given, otherwise from stdin
+pub fn tx_envelope_from_input(input: Option<&Path>) -> Result<TransactionEnvelope, Error> {
+    match input {
+        Some(path) => from_reader(File::open(path)?).map_err(|_| Error::FileDecode(path.into())),
+        None => tx_envelope_from_stdin(),
+    }
+}
+
 pub fn from_stdin<T: ReadXdr>() -> Result<T, Error> {
+    from_reader(stdin()).map_err(|_| Error::StdinDecode)
+}
+
+pub fn from_reader<T: ReadXdr>(mut reader: impl Read) -> Result<T, Error> {
     let mut buf = String::new();
-    let _ = stdin()
-        .read_to_string(&mut buf)
-        .map_err(|_| Error::StdinDecode)?;
-    T::from_xdr_base64(buf.trim(), Limits::none()).map_err(|_| Error::StdinDecode)
+    reader.read_to_string(&mut buf)?;
+    T::from_xdr_base64(buf.trim(), Limits::none()).map_err(|_| Error::Base64Decode)
 }
 
 pub fn unwrap_envelope_v1(tx_env: TransactionEnvelope) -> Result<Transaction, Error> {
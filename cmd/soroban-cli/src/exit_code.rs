@@ -0,0 +1,71 @@
+//! Process exit codes, so that scripts can tell what kind of failure stopped a command.
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Success |
+//! | 1 | Any other error |
+//! | 2 | Usage or configuration error, e.g. a bad argument, a missing identity or network |
+//! | 3 | Network error, e.g. the RPC server is unreachable, fails, or times out |
+//! | 4 | Contract or host error, e.g. simulation failed, the transaction failed, or the contract returned an error |
+use jsonrpsee_http_client::types::error::{INVALID_PARAMS_CODE, INVALID_REQUEST_CODE};
+
+use crate::rpc;
+
+pub const FAILURE: i32 = 1;
+pub const USAGE: i32 = 2;
+pub const NETWORK: i32 = 3;
+pub const CONTRACT: i32 = 4;
+
+/// The exit code for an error from the RPC client. An error response from the server means it
+/// was reached, so it's a usage error if the server rejected the request's parameters, and a
+/// plain failure otherwise.
+pub fn rpc(e: &rpc::Error) -> i32 {
+    match e {
+        rpc::Error::JsonRpc(jsonrpsee_core::Error::Call(e)) => match e.code() {
+            INVALID_REQUEST_CODE | INVALID_PARAMS_CODE => USAGE,
+            _ => FAILURE,
+        },
+        rpc::Error::JsonRpc(_)
+        | rpc::Error::InvalidResponse
+        | rpc::Error::MissingResult
+        | rpc::Error::MissingError
+        | rpc::Error::TransactionSubmissionTimeout => NETWORK,
+        rpc::Error::TransactionFailed(_)
+        | rpc::Error::TransactionSubmissionFailed(_)
+        | rpc::Error::TransactionSimulationFailed(_) => CONTRACT,
+        rpc::Error::InvalidAddress(_)
+        | rpc::Error::InvalidNetworkPassphrase { .. }
+        | rpc::Error::InvalidRpcUrl(_)
+        | rpc::Error::InvalidRpcUrlFromUriParts(_)
+        | rpc::Error::InvalidUrl(_)
+        | rpc::Error::NotFound(..)
+        | rpc::Error::MissingSignerForAddress { .. }
+        | rpc::Error::InvalidCursor => USAGE,
+        _ => FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_rpc_errors() {
+        assert_eq!(rpc(&rpc::Error::TransactionSubmissionTimeout), NETWORK);
+        assert_eq!(
+            rpc(&rpc::Error::TransactionSimulationFailed(
+                "trapped".to_string()
+            )),
+            CONTRACT
+        );
+        assert_eq!(rpc(&rpc::Error::InvalidCursor), USAGE);
+        let call = |code| {
+            rpc::Error::JsonRpc(jsonrpsee_core::Error::Call(
+                jsonrpsee_http_client::types::ErrorObject::owned(code, "", None::<()>),
+            ))
+        };
+        assert_eq!(rpc(&call(INVALID_PARAMS_CODE)), USAGE);
+        assert_eq!(rpc(&call(-32603)), FAILURE);
+        assert_eq!(rpc(&rpc::Error::MissingOp), FAILURE);
+    }
+}
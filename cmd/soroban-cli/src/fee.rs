@@ -1,9 +1,39 @@
 use clap::arg;
 
-use soroban_env_host::xdr;
+use soroban_env_host::xdr::{
+    self, Hash, Limits, Memo, Preconditions, StringM, TimeBounds, TimePoint, TransactionEnvelope,
+    WriteXdr,
+};
 use soroban_rpc::Assembled;
 
-use crate::commands::HEADING_RPC;
+use crate::{commands::HEADING_RPC, rpc, utils::rpc::RetryingClient};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("max time {max_time} is already in the past, the latest ledger closed at {now}")]
+    MaxTimeInPast { max_time: u64, now: u64 },
+    #[error("min time {min_time} is after max time {max_time}, so the transaction is never valid")]
+    MinTimeAfterMaxTime { min_time: u64, max_time: u64 },
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::MaxTimeInPast { .. } | Error::MinTimeAfterMaxTime { .. } => {
+                crate::exit_code::USAGE
+            }
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Xdr(_) | Error::Json(_) => crate::exit_code::FAILURE,
+        }
+    }
+}
 
 #[derive(Debug, clap::Args, Clone)]
 #[group(skip)]
@@ -17,24 +47,138 @@ pub struct Args {
     /// Number of instructions to simulate
     #[arg(long, help_heading = HEADING_RPC)]
     pub instructions: Option<u32>,
+    /// Use the simulated number of instructions as-is instead of padding it
+    #[arg(long, help_heading = HEADING_RPC, conflicts_with = "instructions")]
+    pub no_auto_pad_instructions: bool,
     /// Build the transaction and only write the base64 xdr to stdout
     #[arg(long, help_heading = HEADING_RPC)]
     pub build_only: bool,
     /// Simulate the transaction and only write the base64 xdr to stdout
     #[arg(long, help_heading = HEADING_RPC, conflicts_with = "build_only")]
     pub sim_only: bool,
+    /// Build the transaction and only write it to stdout as JSON, for review before signing. The
+    /// JSON encodes back to the same xdr with `stellar xdr encode --type TransactionEnvelope`
+    #[arg(long, help_heading = HEADING_RPC, conflicts_with_all = ["build_only", "sim_only"])]
+    pub build_only_json: bool,
+    /// Attach a text memo of up to 28 bytes to the transaction
+    #[arg(
+        long,
+        value_parser = parse_memo_text,
+        help_heading = HEADING_RPC,
+        conflicts_with_all = ["memo_id", "memo_hash"]
+    )]
+    pub memo_text: Option<StringM<28>>,
+    /// Attach an ID memo, an unsigned 64-bit integer, to the transaction
+    #[arg(long, help_heading = HEADING_RPC, conflicts_with = "memo_hash")]
+    pub memo_id: Option<u64>,
+    /// Attach a hash memo, 32 bytes as hex, to the transaction
+    #[arg(long, value_parser = parse_memo_hash, help_heading = HEADING_RPC)]
+    pub memo_hash: Option<Hash>,
+    /// Earliest time the transaction is valid, as a unix timestamp in seconds
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub min_time: Option<u64>,
+    /// Latest time the transaction is valid, as a unix timestamp in seconds. It fails if it isn't
+    /// included in a ledger by then
+    #[arg(long, help_heading = HEADING_RPC, conflicts_with = "timeout")]
+    pub max_time: Option<u64>,
+    /// Number of seconds the transaction is valid for, counted from the close time of the latest
+    /// ledger. It fails if it isn't included in a ledger by then
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub timeout: Option<u64>,
 }
 
 impl Args {
+    /// Whether the transaction is only built, with either `--build-only` or `--build-only-json`
+    pub fn is_build_only(&self) -> bool {
+        self.build_only || self.build_only_json
+    }
+
+    /// The built or simulated transaction as it's written to stdout: JSON with
+    /// `--build-only-json`, otherwise base64 xdr
+    pub fn tx_output(&self, tx: &TransactionEnvelope) -> Result<String, Error> {
+        Ok(if self.build_only_json {
+            serde_json::to_string_pretty(tx)?
+        } else {
+            tx.to_xdr_base64(Limits::none())?
+        })
+    }
+
+    /// The memo to attach to the transaction, from `--memo-text`, `--memo-id`, or `--memo-hash`
+    pub fn memo(&self) -> Memo {
+        if let Some(text) = &self.memo_text {
+            Memo::Text(text.clone())
+        } else if let Some(id) = self.memo_id {
+            Memo::Id(id)
+        } else if let Some(hash) = &self.memo_hash {
+            Memo::Hash(hash.clone())
+        } else {
+            Memo::None
+        }
+    }
+
+    /// The transaction's preconditions, with time bounds from `--min-time`, `--max-time`, or
+    /// `--timeout`. The latest ledger's close time is fetched to resolve `--timeout` and to check
+    /// that the transaction isn't already expired; no call is made without time bounds.
+    pub async fn preconditions(&self, client: &RetryingClient) -> Result<Preconditions, Error> {
+        if self.min_time.is_none() && self.max_time.is_none() && self.timeout.is_none() {
+            return Ok(Preconditions::None);
+        }
+        let now = client.get_latest_ledger_close_time().await?;
+        Ok(self
+            .time_bounds(now)?
+            .map_or(Preconditions::None, Preconditions::Time))
+    }
+
+    /// The time bounds from `--min-time`, `--max-time`, or `--timeout`, for a latest ledger
+    /// closed at `now`.
+    fn time_bounds(&self, now: u64) -> Result<Option<TimeBounds>, Error> {
+        let max_time = match (self.timeout, self.max_time) {
+            (Some(timeout), _) => Some(now.saturating_add(timeout)),
+            (None, max_time) => max_time,
+        };
+        if let Some(max_time) = max_time {
+            if max_time <= now {
+                return Err(Error::MaxTimeInPast { max_time, now });
+            }
+        }
+        if let (Some(min_time), Some(max_time)) = (self.min_time, max_time) {
+            if min_time > max_time {
+                return Err(Error::MinTimeAfterMaxTime { min_time, max_time });
+            }
+        }
+        if self.min_time.is_none() && max_time.is_none() {
+            return Ok(None);
+        }
+        // A max time of zero means the transaction has no expiry
+        Ok(Some(TimeBounds {
+            min_time: TimePoint(self.min_time.unwrap_or_default()),
+            max_time: TimePoint(max_time.unwrap_or_default()),
+        }))
+    }
+
     pub fn apply_to_assembled_txn(&self, txn: Assembled) -> Assembled {
         if let Some(instructions) = self.instructions {
             txn.set_max_instructions(instructions)
+        } else if self.no_auto_pad_instructions {
+            txn
         } else {
             add_padding_to_instructions(txn)
         }
     }
 }
 
+fn parse_memo_text(s: &str) -> Result<StringM<28>, String> {
+    s.try_into()
+        .map_err(|_| format!("memo text must be at most 28 bytes, it's {} bytes", s.len()))
+}
+
+fn parse_memo_hash(s: &str) -> Result<Hash, String> {
+    let bytes = hex::decode(s).map_err(|e| format!("memo hash must be hex: {e}"))?;
+    Ok(Hash(bytes.try_into().map_err(|bytes: Vec<u8>| {
+        format!("memo hash must be 32 bytes, it's {} bytes", bytes.len())
+    })?))
+}
+
 pub fn add_padding_to_instructions(txn: Assembled) -> Assembled {
     let xdr::TransactionExt::V1(xdr::SorobanTransactionData {
         resources: xdr::SorobanResources { instructions, .. },
@@ -44,7 +188,7 @@ pub fn add_padding_to_instructions(txn: Assembled) -> Assembled {
         return txn;
     };
     // Start with 150%
-    let instructions = (instructions.checked_mul(150 / 100)).unwrap_or(instructions);
+    let instructions = instructions.saturating_add(instructions / 2);
     txn.set_max_instructions(instructions)
 }
 
@@ -54,8 +198,159 @@ impl Default for Args {
             fee: 100,
             cost: false,
             instructions: None,
+            no_auto_pad_instructions: false,
             build_only: false,
             sim_only: false,
+            build_only_json: false,
+            memo_text: None,
+            memo_id: None,
+            memo_hash: None,
+            min_time: None,
+            max_time: None,
+            timeout: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commands::contract::install, utils};
+    use soroban_rpc::{SimulateHostFunctionResultRaw, SimulateTransactionResponse};
+
+    fn instructions(txn: &Assembled) -> u32 {
+        let xdr::TransactionExt::V1(data) = &txn.transaction().ext else {
+            panic!("expected soroban transaction data")
+        };
+        data.resources.instructions
+    }
+
+    #[test]
+    fn instructions_are_padded_unless_no_auto_pad_instructions() {
+        let key =
+            utils::parse_secret_key("SBFGFF27Y64ZUGFAIG5AMJGQODZZKV2YQKAVUUN4HNE24XZXD2OEUVUP")
+                .unwrap();
+        let (tx, _) = install::build_install_contract_code_tx(b"foo", 1, 100, &key).unwrap();
+        let transaction_data = xdr::SorobanTransactionData {
+            ext: xdr::ExtensionPoint::V0,
+            resources: xdr::SorobanResources {
+                footprint: xdr::LedgerFootprint {
+                    read_only: xdr::VecM::default(),
+                    read_write: xdr::VecM::default(),
+                },
+                instructions: 1_234,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        };
+        let sim = SimulateTransactionResponse {
+            results: vec![SimulateHostFunctionResultRaw {
+                auth: vec![],
+                xdr: xdr::ScVal::Void.to_xdr_base64(Limits::none()).unwrap(),
+            }],
+            transaction_data: transaction_data.to_xdr_base64(Limits::none()).unwrap(),
+            ..Default::default()
+        };
+        let txn = Args::default().apply_to_assembled_txn(Assembled::new(&tx, sim.clone()).unwrap());
+        assert_eq!(instructions(&txn), 1_851);
+        let args = Args {
+            no_auto_pad_instructions: true,
+            ..Default::default()
+        };
+        let txn = args.apply_to_assembled_txn(Assembled::new(&tx, sim).unwrap());
+        assert_eq!(instructions(&txn), 1_234);
+    }
+
+    #[test]
+    fn build_only_json_encodes_back_to_the_same_xdr() {
+        let key =
+            utils::parse_secret_key("SBFGFF27Y64ZUGFAIG5AMJGQODZZKV2YQKAVUUN4HNE24XZXD2OEUVUP")
+                .unwrap();
+        let (tx, _) = install::build_install_contract_code_tx(b"foo", 1, 100, &key).unwrap();
+        let tx_env: TransactionEnvelope = tx.into();
+        let args = Args {
+            build_only_json: true,
+            ..Default::default()
+        };
+        let json = args.tx_output(&tx_env).unwrap();
+        let decoded: TransactionEnvelope = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, tx_env);
+        assert_eq!(
+            Args::default().tx_output(&tx_env).unwrap(),
+            tx_env.to_xdr_base64(Limits::none()).unwrap()
+        );
+    }
+
+    #[test]
+    fn memo_flags() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cmd {
+            #[command(flatten)]
+            fee: Args,
+        }
+        let memo = |args: &[&str]| {
+            Cmd::try_parse_from(std::iter::once("stellar").chain(args.iter().copied()))
+                .map(|cmd| cmd.fee.memo())
+        };
+        assert_eq!(memo(&[]).unwrap(), Memo::None);
+        assert_eq!(
+            memo(&["--memo-text", "hello"]).unwrap(),
+            Memo::Text("hello".try_into().unwrap())
+        );
+        assert_eq!(memo(&["--memo-id", "42"]).unwrap(), Memo::Id(42));
+        assert_eq!(
+            memo(&["--memo-hash", &"ab".repeat(32)]).unwrap(),
+            Memo::Hash(Hash([0xab; 32]))
+        );
+        assert!(memo(&["--memo-text", &"a".repeat(29)]).is_err());
+        assert!(memo(&["--memo-hash", "abcd"]).is_err());
+        assert!(memo(&["--memo-text", "hello", "--memo-id", "42"]).is_err());
+    }
+
+    #[test]
+    fn time_bounds() {
+        let now = 1_700_000_000;
+        let bounds = |args: Args| args.time_bounds(now);
+        assert_eq!(bounds(Args::default()).unwrap(), None);
+        assert_eq!(
+            bounds(Args {
+                timeout: Some(30),
+                ..Default::default()
+            })
+            .unwrap(),
+            Some(TimeBounds {
+                min_time: TimePoint(0),
+                max_time: TimePoint(now + 30),
+            })
+        );
+        assert_eq!(
+            bounds(Args {
+                min_time: Some(now + 10),
+                ..Default::default()
+            })
+            .unwrap(),
+            Some(TimeBounds {
+                min_time: TimePoint(now + 10),
+                max_time: TimePoint(0),
+            })
+        );
+        assert!(matches!(
+            bounds(Args {
+                max_time: Some(now - 1),
+                ..Default::default()
+            }),
+            Err(Error::MaxTimeInPast { .. })
+        ));
+        assert!(matches!(
+            bounds(Args {
+                min_time: Some(now + 60),
+                timeout: Some(30),
+                ..Default::default()
+            }),
+            Err(Error::MinTimeAfterMaxTime { .. })
+        ));
+    }
+}
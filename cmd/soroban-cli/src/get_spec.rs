@@ -32,6 +32,18 @@ pub enum Error {
     ContractSpec(#[from] contract_spec::Error),
 }
 
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Network(e) => e.exit_code(),
+            Error::Config(e) => e.exit_code(),
+            _ => crate::exit_code::FAILURE,
+        }
+    }
+}
+
 ///
 /// # Errors
 pub async fn get_remote_contract_spec(
@@ -41,12 +53,12 @@ pub async fn get_remote_contract_spec(
     global_args: Option<&global::Args>,
     config: Option<&config::Args>,
 ) -> Result<Vec<ScSpecEntry>, Error> {
-    let network = config.map_or_else(
-        || network.get(locator).map_err(Error::from),
-        |c| c.get_network().map_err(Error::from),
-    )?;
+    let (network, options) = match config {
+        Some(config) => (config.get_network()?, config.network.rpc_options()),
+        None => (network.get(locator)?, network.rpc_options()),
+    };
     tracing::trace!(?network);
-    let client = rpc::Client::new(&network.rpc_url)?;
+    let client = network.rpc_client(&options).await?;
     // Get contract data
     let r = client.get_contract_data(contract_id).await?;
     tracing::trace!("{r:?}");
@@ -62,8 +74,15 @@ pub async fn get_remote_contract_spec(
     // Get the contract spec entries based on the executable type
     Ok(match executable {
         ContractExecutable::Wasm(hash) => {
+            // The spec is cached by wasm hash, so an upgraded contract's new spec is fetched
             let hash_str = hash.to_string();
-            if let Ok(entries) = data::read_spec(&hash_str) {
+            let cached = if global_args.is_some_and(|a| a.refresh_cache) {
+                None
+            } else {
+                data::read_spec(&hash_str).ok()
+            };
+            if let Some(entries) = cached {
+                tracing::debug!("using cached spec for wasm {hash_str}");
                 entries
             } else {
                 let raw_wasm = client.get_remote_wasm_from_hash(hash).await?;
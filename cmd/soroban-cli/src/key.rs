@@ -31,7 +31,8 @@ pub struct Args {
         required_unless_present = "wasm_hash"
     )]
     pub contract_id: Option<String>,
-    /// Storage key (symbols only)
+    /// Storage key, either a symbol or the JSON form of any value, e.g.
+    /// `{"vec":[{"symbol":"Balance"},{"u32":1}]}`
     #[arg(long = "key", conflicts_with = "key_xdr")]
     pub key: Option<Vec<String>>,
     /// Storage key (base64-encoded XDR)
@@ -64,12 +65,7 @@ impl Args {
     pub fn parse_keys(&self, contract: Contract) -> Result<Vec<LedgerKey>, Error> {
         let keys = if let Some(keys) = &self.key {
             keys.iter()
-                .map(|key| {
-                    Ok(soroban_spec_tools::from_string_primitive(
-                        key,
-                        &xdr::ScSpecTypeDef::Symbol,
-                    )?)
-                })
+                .map(|key| parse_key(key))
                 .collect::<Result<Vec<_>, Error>>()?
         } else if let Some(keys) = &self.key_xdr {
             keys.iter()
@@ -100,3 +96,33 @@ impl Args {
             .collect())
     }
 }
+
+/// Parse a key given as the JSON form of a value, or else as a symbol. Symbols can't contain the
+/// braces or quotes that start the JSON form of a value, so the two can't be confused.
+fn parse_key(key: &str) -> Result<ScVal, Error> {
+    if let Ok(val) = serde_json::from_str::<ScVal>(key) {
+        return Ok(val);
+    }
+    Ok(soroban_spec_tools::from_string_primitive(
+        key,
+        &xdr::ScSpecTypeDef::Symbol,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xdr::{ScSymbol, ScVec};
+
+    #[test]
+    fn parses_symbol_and_json_keys() {
+        let symbol = |s: &str| ScVal::Symbol(ScSymbol(s.try_into().unwrap()));
+        assert_eq!(parse_key("Counter").unwrap(), symbol("Counter"));
+        assert_eq!(
+            parse_key(r#"{"vec":[{"symbol":"Balance"},{"u32":1}]}"#).unwrap(),
+            ScVal::Vec(Some(ScVec(
+                vec![symbol("Balance"), ScVal::U32(1)].try_into().unwrap()
+            )))
+        );
+    }
+}
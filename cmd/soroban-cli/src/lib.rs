@@ -11,7 +11,9 @@ pub(crate) use soroban_rpc as rpc;
 mod cli;
 pub use cli::main;
 
+pub mod client;
 pub mod commands;
+pub mod exit_code;
 pub mod fee;
 pub mod get_spec;
 pub mod key;
@@ -19,8 +21,10 @@ pub mod log;
 pub mod signer;
 pub mod toid;
 pub mod utils;
+pub mod wait;
 pub mod wasm;
 
+pub use client::SorobanClient;
 pub use commands::Root;
 
 pub fn parse_cmd<T>(s: &str) -> Result<T, clap::Error>
@@ -1,5 +1,7 @@
-use soroban_env_host::xdr::SorobanResources;
-use std::fmt::{Debug, Display};
+use soroban_env_host::xdr::{SorobanResources, SorobanTransactionData};
+use std::fmt::{Debug, Display, Write};
+
+use crate::rpc;
 
 struct Cost<'a>(&'a SorobanResources);
 
@@ -25,3 +27,70 @@ pub fn cost(resources: &SorobanResources) {
     let cost = Cost(resources);
     tracing::debug!(?cost);
 }
+
+/// Format the cost reported by simulation and the resources assembled into the transaction as a
+/// table with one aligned row per item, so that the costs of builds can be diffed.
+pub fn cost_table(cost: &rpc::Cost, data: &SorobanTransactionData) -> String {
+    let resources = &data.resources;
+    [
+        ("CPU instructions", cost.cpu_insns.to_string()),
+        ("Memory bytes", cost.mem_bytes.to_string()),
+        ("Instructions", resources.instructions.to_string()),
+        ("Read bytes", resources.read_bytes.to_string()),
+        ("Write bytes", resources.write_bytes.to_string()),
+        (
+            "Read-only entries",
+            resources.footprint.read_only.len().to_string(),
+        ),
+        (
+            "Read-write entries",
+            resources.footprint.read_write.len().to_string(),
+        ),
+        ("Resource fee", data.resource_fee.to_string()),
+    ]
+    .iter()
+    .fold(String::new(), |mut table, (name, value)| {
+        let _ = writeln!(table, "{name:<20}{value:>14}");
+        table
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_env_host::xdr::{ExtensionPoint, LedgerFootprint};
+
+    #[test]
+    fn cost_table_is_aligned() {
+        let cost = rpc::Cost {
+            cpu_insns: 1_234_567,
+            mem_bytes: 890,
+        };
+        let data = SorobanTransactionData {
+            ext: ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: vec![].try_into().unwrap(),
+                    read_write: vec![].try_into().unwrap(),
+                },
+                instructions: 2_000_000,
+                read_bytes: 100,
+                write_bytes: 0,
+            },
+            resource_fee: 12_345,
+        };
+        assert_eq!(
+            cost_table(&cost, &data),
+            "\
+CPU instructions           1234567
+Memory bytes                   890
+Instructions               2000000
+Read bytes                     100
+Write bytes                      0
+Read-only entries                0
+Read-write entries               0
+Resource fee                 12345
+"
+        );
+    }
+}
@@ -4,6 +4,8 @@ pub fn diagnostic_events(events: &[impl std::fmt::Debug], level: tracing::Level)
             tracing::trace!("{i}: {event:#?}");
         } else if level == tracing::Level::INFO {
             tracing::info!("{i}: {event:#?}");
+        } else if level == tracing::Level::WARN {
+            tracing::warn!("{i}: {event:#?}");
         } else if level == tracing::Level::ERROR {
             tracing::error!("{i}: {event:#?}");
         }
@@ -10,6 +10,8 @@ use soroban_env_host::xdr::{
     TransactionV1Envelope, Uint256, WriteXdr,
 };
 
+pub mod ledger;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("Contract addresses are not supported to sign auth entries {address}")]
@@ -194,17 +196,31 @@ pub fn sign_tx(
     tx: &Transaction,
     network_passphrase: &str,
 ) -> Result<TransactionEnvelope, Error> {
-    let tx_hash = hash(tx, network_passphrase)?;
-    let tx_signature = key.sign(&tx_hash);
+    sign_tx_with_keys(std::slice::from_ref(key), tx, network_passphrase)
+}
 
-    let decorated_signature = DecoratedSignature {
-        hint: SignatureHint(key.verifying_key().to_bytes()[28..].try_into()?),
-        signature: Signature(tx_signature.to_bytes().try_into()?),
-    };
+/// Sign `tx` with each of `keys`, e.g. when a fee source pays for a transaction whose operations
+/// have a different source account.
+pub fn sign_tx_with_keys(
+    keys: &[ed25519_dalek::SigningKey],
+    tx: &Transaction,
+    network_passphrase: &str,
+) -> Result<TransactionEnvelope, Error> {
+    let tx_hash = hash(tx, network_passphrase)?;
+    let signatures = keys
+        .iter()
+        .map(|key| {
+            let tx_signature = key.sign(&tx_hash);
+            Ok(DecoratedSignature {
+                hint: SignatureHint(key.verifying_key().to_bytes()[28..].try_into()?),
+                signature: Signature(tx_signature.to_bytes().try_into()?),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
 
     Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
         tx: tx.clone(),
-        signatures: [decorated_signature].try_into()?,
+        signatures: signatures.try_into()?,
     }))
 }
 
@@ -0,0 +1,119 @@
+#[cfg(feature = "ledger")]
+use ed25519_dalek::Verifier;
+#[cfg(feature = "ledger")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "ledger")]
+use stellar_ledger::Blob;
+
+#[cfg(feature = "ledger")]
+use crate::xdr::{Hash, Signature, SignatureHint};
+use crate::{
+    commands::config::secret::{self, LedgerKey},
+    xdr::{self, DecoratedSignature, Transaction},
+};
+
+/// Status word the Stellar app returns when the user rejects a request on the device.
+#[cfg(feature = "ledger")]
+const SW_DENY: &str = "0x6985";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("no Ledger device found, connect and unlock it, then open the Stellar app")]
+    DeviceNotFound,
+    #[error("the request was rejected on the Ledger device")]
+    Rejected,
+    #[error("the Ledger device has key {got}, not the identity's key {expected}, check that the right device is connected")]
+    WrongKey { expected: String, got: String },
+    #[cfg(feature = "ledger")]
+    #[error(transparent)]
+    Ledger(stellar_ledger::Error),
+    #[cfg(not(feature = "ledger"))]
+    #[error("Must install with \"ledger\" feature to use Ledger devices, e.g. `cargo install --locked stellar-cli --features ledger`")]
+    Install,
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+    #[error(transparent)]
+    Ed25519(#[from] ed25519_dalek::SignatureError),
+    #[error(transparent)]
+    TryFromSlice(#[from] std::array::TryFromSliceError),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+#[cfg(feature = "ledger")]
+impl From<stellar_ledger::Error> for Error {
+    fn from(e: stellar_ledger::Error) -> Self {
+        match e {
+            stellar_ledger::Error::HidApiError(_)
+            | stellar_ledger::Error::LedgerHidError(_)
+            | stellar_ledger::Error::LedgerConnectionError(_) => Error::DeviceNotFound,
+            stellar_ledger::Error::APDUExchangeError(ref code) if code.ends_with(SW_DENY) => {
+                Error::Rejected
+            }
+            e => Error::Ledger(e),
+        }
+    }
+}
+
+/// Read the key of account `hd_path` from a connected Ledger device.
+#[cfg(not(feature = "ledger"))]
+#[allow(clippy::unused_async)]
+pub async fn key(_hd_path: u32) -> Result<LedgerKey, Error> {
+    Err(Error::Install)
+}
+
+/// Read the key of account `hd_path` from a connected Ledger device.
+#[cfg(feature = "ledger")]
+pub async fn key(hd_path: u32) -> Result<LedgerKey, Error> {
+    let public_key = stellar_ledger::native()?
+        .get_public_key(&hd_path.into())
+        .await?;
+    Ok(LedgerKey {
+        hd_path,
+        public_key: public_key.to_string(),
+    })
+}
+
+/// Sign `tx` with `key` on a connected Ledger device, which shows the transaction for the user
+/// to approve. The device's public key is checked against the identity's first, so that a
+/// different device or account isn't silently used.
+#[cfg(not(feature = "ledger"))]
+#[allow(clippy::unused_async)]
+pub async fn sign_tx(
+    _key: &LedgerKey,
+    _tx: &Transaction,
+    _network_passphrase: &str,
+) -> Result<DecoratedSignature, Error> {
+    Err(Error::Install)
+}
+
+/// Sign `tx` with `key` on a connected Ledger device, which shows the transaction for the user
+/// to approve. The device's public key is checked against the identity's first, so that a
+/// different device or account isn't silently used.
+#[cfg(feature = "ledger")]
+pub async fn sign_tx(
+    key: &LedgerKey,
+    tx: &Transaction,
+    network_passphrase: &str,
+) -> Result<DecoratedSignature, Error> {
+    let signer = stellar_ledger::native()?;
+    let public_key = signer.get_public_key(&key.hd_path.into()).await?;
+    if public_key.to_string() != key.public_key {
+        return Err(Error::WrongKey {
+            expected: key.public_key.clone(),
+            got: public_key.to_string(),
+        });
+    }
+    let network_id = Hash(Sha256::digest(network_passphrase.as_bytes()).into());
+    eprintln!("Review and approve the transaction on your Ledger device");
+    let signature = signer
+        .sign_transaction(key.hd_path, tx.clone(), network_id)
+        .await?;
+    let signature = ed25519_dalek::Signature::from_slice(&signature)?;
+    key.verifying_key()?
+        .verify(&super::hash(tx, network_passphrase)?, &signature)?;
+    Ok(DecoratedSignature {
+        hint: SignatureHint(public_key.0[28..].try_into()?),
+        signature: Signature(signature.to_bytes().try_into()?),
+    })
+}
@@ -3,14 +3,18 @@ use sha2::{Digest, Sha256};
 use stellar_strkey::ed25519::PrivateKey;
 
 use soroban_env_host::xdr::{
-    Asset, ContractIdPreimage, DecoratedSignature, Error as XdrError, Hash, HashIdPreimage,
-    HashIdPreimageContractId, Limits, Signature, SignatureHint, Transaction, TransactionEnvelope,
-    TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+    Asset, ContractIdPreimage, DecoratedSignature, Error as XdrError, FeeBumpTransactionEnvelope,
+    Hash, HashIdPreimage, HashIdPreimageContractId, Limits, MuxedAccount, Preconditions, Signature,
+    SignatureHint, Transaction, TransactionEnvelope, TransactionExt, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, TransactionV0, TransactionV0Envelope,
     TransactionV1Envelope, WriteXdr,
 };
 
 pub use soroban_spec_tools::contract as contract_spec;
 
+pub mod proxy;
+pub mod rpc;
+
 /// # Errors
 ///
 /// Might return an error
@@ -22,9 +26,60 @@ pub fn contract_hash(contract: &[u8]) -> Result<Hash, XdrError> {
 ///
 /// Might return an error
 pub fn transaction_hash(tx: &Transaction, network_passphrase: &str) -> Result<[u8; 32], XdrError> {
+    signature_payload_hash(
+        TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+        network_passphrase,
+    )
+}
+
+/// Hash of the transaction in the envelope that signers of the envelope sign. V0 envelopes are
+/// hashed as the equivalent V1 transaction, as the network does.
+///
+/// # Errors
+///
+/// Might return an error
+pub fn hash_transaction_in_envelope(
+    tx_env: &TransactionEnvelope,
+    network_passphrase: &str,
+) -> Result<[u8; 32], XdrError> {
+    let tagged_transaction = match tx_env {
+        TransactionEnvelope::TxV0(TransactionV0Envelope { tx, .. }) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(transaction_from_v0(tx))
+        }
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone())
+        }
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(tx.clone())
+        }
+    };
+    signature_payload_hash(tagged_transaction, network_passphrase)
+}
+
+/// Convert a legacy v0 transaction to the equivalent v1 transaction, as is done when hashing it
+#[must_use]
+pub fn transaction_from_v0(tx: &TransactionV0) -> Transaction {
+    Transaction {
+        source_account: MuxedAccount::Ed25519(tx.source_account_ed25519.clone()),
+        fee: tx.fee,
+        seq_num: tx.seq_num.clone(),
+        cond: tx
+            .time_bounds
+            .clone()
+            .map_or(Preconditions::None, Preconditions::Time),
+        memo: tx.memo.clone(),
+        operations: tx.operations.clone(),
+        ext: TransactionExt::V0,
+    }
+}
+
+fn signature_payload_hash(
+    tagged_transaction: TransactionSignaturePayloadTaggedTransaction,
+    network_passphrase: &str,
+) -> Result<[u8; 32], XdrError> {
     let signature_payload = TransactionSignaturePayload {
         network_id: Hash(Sha256::digest(network_passphrase).into()),
-        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+        tagged_transaction,
     };
     Ok(Sha256::digest(signature_payload.to_xdr(Limits::none())?).into())
 }
@@ -104,8 +159,6 @@ pub(crate) fn into_signing_key(key: &PrivateKey) -> ed25519_dalek::SigningKey {
     ed25519_dalek::SigningKey::from_bytes(&secret)
 }
 
-/// Used in tests
-#[allow(unused)]
 pub(crate) fn parse_secret_key(
     s: &str,
 ) -> Result<ed25519_dalek::SigningKey, stellar_strkey::DecodeError> {
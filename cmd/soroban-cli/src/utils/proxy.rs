@@ -0,0 +1,189 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Once,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use http::Uri;
+use hyper::{client::HttpConnector, service::Service};
+use hyper_tls::{native_tls, HttpsConnector};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Environment variables a proxy is read from when none is given, in order of precedence.
+const PROXY_ENV_VARS: [&str; 4] = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"];
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("proxy {0} must be an http:// URL, SOCKS and https proxies aren't supported")]
+    UnsupportedProxy(String),
+}
+
+/// Connects through an HTTP proxy, tunnelling each connection with `CONNECT` so that TLS is
+/// still negotiated with the destination, or connects directly when there is no proxy. Wrap it
+/// in a [`hyper_tls::HttpsConnector`] for HTTPS.
+#[derive(Clone, Debug)]
+pub struct ProxyConnector {
+    http: HttpConnector,
+    proxy: Option<Uri>,
+    no_proxy: Vec<String>,
+}
+
+impl ProxyConnector {
+    /// Connect through `proxy`, or the proxy from the `HTTPS_PROXY` or `ALL_PROXY` environment
+    /// variables if it's not given. Hosts in `NO_PROXY`, and localhost, are connected to
+    /// directly. A proxy from the environment that isn't supported is ignored with a warning.
+    pub fn new(proxy: Option<Uri>) -> Self {
+        let proxy = proxy.or_else(|| {
+            PROXY_ENV_VARS
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+                .and_then(|proxy| {
+                    parse_proxy(&proxy)
+                        .map_err(|e| tracing::warn!("ignoring proxy from environment: {e}"))
+                        .ok()
+                })
+        });
+        let no_proxy = ["NO_PROXY", "no_proxy"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok())
+            .unwrap_or_default()
+            .split(',')
+            .map(|host| host.trim().trim_start_matches('.').to_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect();
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        Self {
+            http,
+            proxy,
+            no_proxy,
+        }
+    }
+
+    /// Give up on connecting, to the proxy or directly, after `timeout`.
+    #[must_use]
+    pub fn with_connect_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.http.set_connect_timeout(timeout);
+        self
+    }
+
+    /// The proxy to connect to `host` through, if any.
+    fn proxy_for(&self, host: &str) -> Option<Uri> {
+        let host = host.trim_matches(|c| c == '[' || c == ']').to_lowercase();
+        let bypass = matches!(host.as_str(), "localhost" | "127.0.0.1" | "::1")
+            || self.no_proxy.iter().any(|no_proxy| {
+                no_proxy == "*" || host == *no_proxy || host.ends_with(&format!(".{no_proxy}"))
+            });
+        self.proxy.clone().filter(|_| !bypass)
+    }
+}
+
+/// Parse a proxy URL, e.g. `http://proxy.example:3128`, refusing proxies that aren't supported.
+pub fn parse_proxy(proxy: &str) -> Result<Uri, Error> {
+    match proxy.parse::<Uri>() {
+        Ok(uri) if uri.scheme_str() == Some("http") && uri.host().is_some() => Ok(uri),
+        _ => Err(Error::UnsupportedProxy(proxy.to_string())),
+    }
+}
+
+impl Service<Uri> for ProxyConnector {
+    type Response = TcpStream;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<TcpStream, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let host = dst.host().unwrap_or_default().to_string();
+        let Some(proxy) = self.proxy_for(&host) else {
+            let connecting = self.http.call(dst);
+            return Box::pin(async move { Ok(connecting.await?) });
+        };
+        let port = dst
+            .port_u16()
+            .unwrap_or(if dst.scheme_str() == Some("https") {
+                443
+            } else {
+                80
+            });
+        let connecting = self.http.call(proxy);
+        Box::pin(async move {
+            let mut stream = connecting.await?;
+            let target = format!("{host}:{port}");
+            stream
+                .write_all(
+                    format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n\r\n").as_bytes(),
+                )
+                .await?;
+            // Read the proxy's response a byte at a time so that nothing after its headers,
+            // which belongs to the tunnelled connection, is consumed
+            let mut response = Vec::new();
+            while !response.ends_with(b"\r\n\r\n") {
+                let byte = stream.read_u8().await?;
+                response.push(byte);
+            }
+            let response = String::from_utf8_lossy(&response);
+            let status_line = response.lines().next().unwrap_or_default();
+            if status_line.split_whitespace().nth(1) != Some("200") {
+                return Err(format!("proxy refused to connect to {target}: {status_line}").into());
+            }
+            Ok(stream)
+        })
+    }
+}
+
+/// An HTTPS, or plain HTTP, connector through [`ProxyConnector`]. With `insecure`, invalid TLS
+/// certificates are accepted and a warning is printed to stderr, once per process.
+pub fn https_connector(
+    proxy: Option<Uri>,
+    insecure: bool,
+    connect_timeout: Option<Duration>,
+) -> Result<HttpsConnector<ProxyConnector>, native_tls::Error> {
+    static WARN_INSECURE: Once = Once::new();
+    let connector = ProxyConnector::new(proxy).with_connect_timeout(connect_timeout);
+    let mut tls = native_tls::TlsConnector::builder();
+    if insecure {
+        WARN_INSECURE.call_once(|| {
+            eprintln!("⚠️ Warning: --insecure is set, TLS certificates are not verified");
+        });
+        tls.danger_accept_invalid_certs(true)
+            .danger_accept_invalid_hostnames(true);
+    }
+    Ok(HttpsConnector::from((connector, tls.build()?.into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_http_proxies_are_supported() {
+        assert!(parse_proxy("http://proxy.example:3128").is_ok());
+        assert!(parse_proxy("socks5://proxy.example:1080").is_err());
+        assert!(parse_proxy("https://proxy.example").is_err());
+    }
+
+    #[test]
+    fn bypasses_localhost_and_no_proxy_hosts() {
+        let connector = ProxyConnector {
+            http: HttpConnector::new(),
+            proxy: Some("http://proxy.example:3128".parse().unwrap()),
+            no_proxy: vec!["internal.example".to_string()],
+        };
+        assert!(connector.proxy_for("friendbot.stellar.org").is_some());
+        assert!(connector.proxy_for("localhost").is_none());
+        assert!(connector.proxy_for("[::1]").is_none());
+        assert!(connector.proxy_for("rpc.internal.example").is_none());
+        assert!(connector.proxy_for("internal.example").is_none());
+        assert!(connector.proxy_for("notinternal.example").is_some());
+    }
+}
@@ -0,0 +1,1016 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    str::FromStr,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use http::Uri;
+use hyper::Body;
+use hyper_tls::HttpsConnector;
+use jsonrpsee_core::{
+    client::ClientT,
+    params::{BatchRequestBuilder, ObjectParams},
+};
+use jsonrpsee_http_client::{
+    transport::{self, HttpBackend},
+    HeaderMap, HeaderValue, HttpClient, HttpClientBuilder,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use tower::Layer;
+
+use super::proxy::{https_connector, ProxyConnector};
+use crate::rpc::{
+    Assembled, Client, Error, EventStart, EventType, FullLedgerEntries, FullLedgerEntry,
+    GetEventsResponse, GetLatestLedgerResponse, GetLedgerEntriesResponse, GetNetworkResponse,
+    GetTransactionResponse, GetTransactionResponseRaw, SendTransactionResponse,
+    SimulateTransactionResponse,
+};
+use crate::xdr::{
+    AccountEntry, AccountId, ContractCodeEntry, ContractDataDurability, ContractDataEntry,
+    ContractExecutable, Hash, LedgerEntryData, LedgerKey, LedgerKeyAccount, LedgerKeyContractCode,
+    LedgerKeyContractData, Limits, PublicKey, ReadXdr, ScAddress, ScContractInstance, ScVal,
+    Transaction, TransactionEnvelope, TransactionResult, TransactionV1Envelope, Uint256, VecM,
+    WriteXdr,
+};
+
+/// Extends [`Client`] with JSON-RPC batch requests, sending several calls in a single POST.
+#[async_trait::async_trait]
+pub trait Batch {
+    /// Send every call in `batch` as one request, returning each call's result in the order the
+    /// calls were inserted. Responses are matched to calls by id, and a call that failed on the
+    /// server is returned as an error in its position without failing the rest of the batch.
+    async fn batch<R>(
+        &self,
+        batch: BatchRequestBuilder<'_>,
+    ) -> Result<Vec<Result<R, Error>>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + Send + 'static;
+}
+
+#[async_trait::async_trait]
+impl Batch for Client {
+    async fn batch<R>(&self, batch: BatchRequestBuilder<'_>) -> Result<Vec<Result<R, Error>>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + Send + 'static,
+    {
+        send_batch(self.client(), batch).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Batch for RetryingClient {
+    async fn batch<R>(&self, batch: BatchRequestBuilder<'_>) -> Result<Vec<Result<R, Error>>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + Send + 'static,
+    {
+        send_batch(&self.client, batch).await
+    }
+}
+
+async fn send_batch<R>(
+    client: &impl ClientT,
+    batch: BatchRequestBuilder<'_>,
+) -> Result<Vec<Result<R, Error>>, Error>
+where
+    R: DeserializeOwned + std::fmt::Debug + Send + 'static,
+{
+    tracing::trace!(?batch);
+    let response = client.batch_request::<R>(batch).await?;
+    Ok(response
+        .into_iter()
+        .map(|res| res.map_err(|e| Error::JsonRpc(jsonrpsee_core::Error::Call(e.into_owned()))))
+        .collect())
+}
+
+/// Response of the `getHealth` RPC method.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHealthResponse {
+    pub status: String,
+    /// Oldest ledger the server keeps events and transactions for. Not reported by older servers
+    #[serde(default)]
+    pub oldest_ledger: Option<u32>,
+}
+
+/// Delay before the first retry of an RPC call; each further retry waits twice as long.
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// How long to poll for a transaction's result when no timeout is given.
+pub const DEFAULT_POLLING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A policy for retrying RPC calls that failed with a transient error, such as a dropped
+/// connection or a 5xx response, with exponential backoff.
+#[derive(Debug, Clone, Copy)]
+pub struct Retry {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+}
+
+impl Retry {
+    /// Call `f` until it succeeds, fails with an error that isn't transient, or has been
+    /// retried `max_retries` times.
+    pub async fn run<T, F, Fut>(&self, mut f: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match f().await {
+                Err(e) if attempt < self.max_retries && is_transient(&e) => {
+                    let delay = self.base_delay * 2u32.saturating_pow(attempt);
+                    tracing::debug!("retrying RPC call in {delay:?} after error: {e}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+/// Whether an error may go away by trying again: networking failures, timeouts, and 5xx or 429
+/// responses. Errors reported by the server about the request itself are not transient.
+pub fn is_transient(e: &Error) -> bool {
+    use jsonrpsee_http_client::transport::Error as Transport;
+    match e {
+        Error::JsonRpc(jsonrpsee_core::Error::Transport(e)) => {
+            match e.downcast_ref::<Transport>() {
+                Some(Transport::RequestFailure { status_code }) => {
+                    *status_code >= 500 || *status_code == 429
+                }
+                Some(Transport::Http(_)) | None => true,
+                Some(_) => false,
+            }
+        }
+        Error::JsonRpc(
+            jsonrpsee_core::Error::RequestTimeout | jsonrpsee_core::Error::RestartNeeded(_),
+        ) => true,
+        _ => false,
+    }
+}
+
+/// Default time to wait for each RPC request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time to wait for a connection to the RPC server.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How a [`RetryingClient`] connects to the RPC server and retries its calls.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// Number of times to retry a call that failed with a transient error
+    pub retries: u32,
+    /// Time to wait for each attempt at a call, or `None` to wait indefinitely
+    pub timeout: Option<Duration>,
+    /// Time to wait for a connection to the server, or `None` to wait indefinitely
+    pub connect_timeout: Option<Duration>,
+    /// Headers to send with every request
+    pub headers: HeaderMap,
+    /// HTTP proxy to connect through, see [`ProxyConnector::new`]
+    pub proxy: Option<Uri>,
+    /// Whether to accept invalid TLS certificates from the server
+    pub insecure: bool,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            retries: 0,
+            timeout: Some(DEFAULT_TIMEOUT),
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            headers: HeaderMap::new(),
+            proxy: None,
+            insecure: false,
+        }
+    }
+}
+
+/// Sends the RPC client's HTTP requests with a hyper client on [`https_connector`], in place of
+/// jsonrpsee's own backend, which can't be given a proxy, TLS settings or a connect timeout.
+#[derive(Clone, Debug)]
+struct Backend(hyper::Client<HttpsConnector<ProxyConnector>>);
+
+impl Layer<HttpBackend> for Backend {
+    type Service = Self;
+
+    fn layer(&self, _: HttpBackend) -> Self {
+        self.clone()
+    }
+}
+
+impl tower::Service<hyper::Request<Body>> for Backend {
+    type Response = hyper::Response<Body>;
+    type Error = transport::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: hyper::Request<Body>) -> Self::Future {
+        let response = self.0.request(req);
+        Box::pin(async move {
+            response
+                .await
+                .map_err(|e| transport::Error::Http(Box::new(e)))
+        })
+    }
+}
+
+/// Add the scheme's default port to `url` if it has none, as jsonrpsee requires one. A URL that
+/// can't be parsed is returned as is, for jsonrpsee to report.
+fn with_default_port(url: &str) -> String {
+    let Ok(uri) = url.parse::<Uri>() else {
+        return url.to_string();
+    };
+    let mut parts = uri.into_parts();
+    if let (Some(scheme), Some(authority)) = (&parts.scheme, &parts.authority) {
+        let port = match scheme.as_str() {
+            "http" => Some(80),
+            "https" => Some(443),
+            _ => None,
+        };
+        if let (None, Some(port)) = (authority.port(), port) {
+            parts.authority = format!("{}:{port}", authority.host()).parse().ok();
+        }
+    }
+    Uri::from_parts(parts).map_or_else(|_| url.to_string(), |uri| uri.to_string())
+}
+
+/// A client for an RPC server whose idempotent calls are retried on transient errors. Its
+/// requests go through the proxy and TLS settings, headers and timeouts given in [`ClientOptions`].
+pub struct RetryingClient {
+    base_url: String,
+    client: HttpClient<Backend>,
+    retry: Retry,
+}
+
+impl RetryingClient {
+    /// Create a client for the RPC server at `base_url`.
+    pub fn new(base_url: &str, options: &ClientOptions) -> Result<Self, Error> {
+        let base_url = with_default_port(base_url);
+        let connector = https_connector(
+            options.proxy.clone(),
+            options.insecure,
+            options.connect_timeout,
+        )
+        .map_err(|e| Error::JsonRpc(jsonrpsee_core::Error::Transport(e.into())))?;
+        let backend = Backend(hyper::Client::builder().build(connector));
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Client-Name", HeaderValue::from_static("soroban-cli"));
+        headers.insert(
+            "X-Client-Version",
+            HeaderValue::from_static(env!("CARGO_PKG_VERSION")),
+        );
+        for (name, value) in &options.headers {
+            headers.insert(name, value.clone());
+        }
+        let client = HttpClientBuilder::default()
+            .set_headers(headers)
+            .request_timeout(options.timeout.unwrap_or(Duration::MAX))
+            .set_middleware(tower::ServiceBuilder::new().layer(backend))
+            .build(&base_url)?;
+        Ok(Self {
+            base_url,
+            client,
+            retry: Retry {
+                max_retries: options.retries,
+                base_delay: DEFAULT_RETRY_DELAY,
+            },
+        })
+    }
+
+    /// Call `f` with the retry policy.
+    async fn run<T, F, Fut>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        self.retry.run(f).await
+    }
+
+    /// Make a single call, retrying it on transient errors.
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: ObjectParams,
+    ) -> Result<R, Error> {
+        self.run(|| async { Ok(self.client.request(method, params.clone()).await?) })
+            .await
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub async fn friendbot_url(&self) -> Result<String, Error> {
+        self.get_network().await?.friendbot_url.ok_or_else(|| {
+            Error::NotFound(
+                "Friendbot".to_string(),
+                "Friendbot is not available on this network".to_string(),
+            )
+        })
+    }
+
+    pub async fn get_network(&self) -> Result<GetNetworkResponse, Error> {
+        self.request("getNetwork", ObjectParams::new()).await
+    }
+
+    pub async fn verify_network_passphrase(&self, expected: Option<&str>) -> Result<String, Error> {
+        let server = self.get_network().await?.passphrase;
+        match expected {
+            Some(expected) if expected != server => Err(Error::InvalidNetworkPassphrase {
+                expected: expected.to_string(),
+                server,
+            }),
+            _ => Ok(server),
+        }
+    }
+
+    pub async fn get_health(&self) -> Result<GetHealthResponse, Error> {
+        self.request("getHealth", ObjectParams::new()).await
+    }
+
+    pub async fn get_latest_ledger(&self) -> Result<GetLatestLedgerResponse, Error> {
+        self.request("getLatestLedger", ObjectParams::new()).await
+    }
+
+    /// Close time of the latest ledger, as a unix timestamp in seconds, read with `getLedgers`
+    /// for the ledger that `getLatestLedger` reports.
+    pub async fn get_latest_ledger_close_time(&self) -> Result<u64, Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Ledger {
+            ledger_close_time: serde_json::Value,
+        }
+        #[derive(Deserialize)]
+        struct Response {
+            ledgers: Vec<Ledger>,
+        }
+        let latest = self.get_latest_ledger().await?;
+        let mut params = ObjectParams::new();
+        params.insert("startLedger", latest.sequence)?;
+        params.insert("pagination", serde_json::json!({ "limit": 1 }))?;
+        let response: Response = self.request("getLedgers", params).await?;
+        let ledger = response.ledgers.first().ok_or(Error::InvalidResponse)?;
+        // The close time is sent as a string, but accept a number too
+        let close_time = &ledger.ledger_close_time;
+        close_time
+            .as_u64()
+            .or_else(|| close_time.as_str()?.parse().ok())
+            .ok_or(Error::InvalidResponse)
+    }
+
+    pub async fn get_account(&self, address: &str) -> Result<AccountEntry, Error> {
+        let key = LedgerKey::Account(LedgerKeyAccount {
+            account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+                stellar_strkey::ed25519::PublicKey::from_string(address)?.0,
+            ))),
+        });
+        let entries = self.get_ledger_entries(&[key]).await?.entries;
+        let Some(entry) = entries.unwrap_or_default().into_iter().next() else {
+            return Err(Error::NotFound("Account".to_string(), address.to_owned()));
+        };
+        match LedgerEntryData::from_xdr_base64(entry.xdr, Limits::none())? {
+            LedgerEntryData::Account(entry) => Ok(entry),
+            _ => Err(Error::InvalidResponse),
+        }
+    }
+
+    pub async fn simulate_and_assemble_transaction(
+        &self,
+        tx: &Transaction,
+    ) -> Result<Assembled, Error> {
+        let sim_res = self
+            .simulate_transaction_envelope(&TransactionEnvelope::Tx(TransactionV1Envelope {
+                tx: tx.clone(),
+                signatures: VecM::default(),
+            }))
+            .await?;
+        match sim_res.error {
+            None => Assembled::new(tx, sim_res),
+            Some(e) => {
+                crate::log::diagnostic_events(&sim_res.events, tracing::Level::ERROR);
+                Err(Error::TransactionSimulationFailed(e))
+            }
+        }
+    }
+
+    pub async fn simulate_transaction_envelope(
+        &self,
+        tx: &TransactionEnvelope,
+    ) -> Result<SimulateTransactionResponse, Error> {
+        tracing::trace!("Simulating:\n{tx:#?}");
+        let mut params = ObjectParams::new();
+        params.insert("transaction", tx.to_xdr_base64(Limits::none())?)?;
+        let sim_res = self.request("simulateTransaction", params).await?;
+        tracing::trace!("Simulation response:\n {sim_res:#?}");
+        Ok(sim_res)
+    }
+
+    pub async fn get_transaction(&self, hash: &Hash) -> Result<GetTransactionResponse, Error> {
+        let mut params = ObjectParams::new();
+        params.insert("hash", hash)?;
+        let response: GetTransactionResponseRaw = self.request("getTransaction", params).await?;
+        Ok(response.try_into()?)
+    }
+
+    pub async fn get_ledger_entries(
+        &self,
+        keys: &[LedgerKey],
+    ) -> Result<GetLedgerEntriesResponse, Error> {
+        let keys = keys
+            .iter()
+            .map(|k| k.to_xdr_base64(Limits::none()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut params = ObjectParams::new();
+        params.insert("keys", keys)?;
+        self.request("getLedgerEntries", params).await
+    }
+
+    pub async fn get_full_ledger_entries(
+        &self,
+        keys: &[LedgerKey],
+    ) -> Result<FullLedgerEntries, Error> {
+        let keys = keys
+            .iter()
+            .filter(|key| !matches!(key, LedgerKey::Ttl(_)))
+            .cloned()
+            .collect::<Vec<_>>();
+        let GetLedgerEntriesResponse {
+            entries,
+            latest_ledger,
+        } = self.get_ledger_entries(&keys).await?;
+        let entries = entries
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| {
+                Ok(FullLedgerEntry {
+                    key: LedgerKey::from_xdr_base64(entry.key, Limits::none())?,
+                    val: LedgerEntryData::from_xdr_base64(entry.xdr, Limits::none())?,
+                    live_until_ledger_seq: entry
+                        .live_until_ledger_seq_ledger_seq
+                        .unwrap_or_default(),
+                    last_modified_ledger: entry.last_modified_ledger,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(FullLedgerEntries {
+            entries,
+            latest_ledger,
+        })
+    }
+
+    pub async fn get_events(
+        &self,
+        start: EventStart,
+        event_type: Option<EventType>,
+        contract_ids: &[String],
+        topics: &[String],
+        limit: Option<usize>,
+    ) -> Result<GetEventsResponse, Error> {
+        let mut filters = serde_json::Map::new();
+        // All types is the default, so the type is left out for it
+        let event_type = match event_type {
+            Some(EventType::Contract) => Some("contract"),
+            Some(EventType::System) => Some("system"),
+            Some(EventType::All) | None => None,
+        };
+        if let Some(event_type) = event_type {
+            filters.insert("type".to_string(), event_type.into());
+        }
+        filters.insert("topics".to_string(), topics.into());
+        filters.insert("contractIds".to_string(), contract_ids.into());
+        let mut pagination = serde_json::Map::new();
+        if let Some(limit) = limit {
+            pagination.insert("limit".to_string(), limit.into());
+        }
+        let mut params = ObjectParams::new();
+        match start {
+            EventStart::Ledger(ledger) => params.insert("startLedger", ledger)?,
+            EventStart::Cursor(cursor) => {
+                pagination.insert("cursor".to_string(), cursor.into());
+            }
+        }
+        params.insert("filters", vec![filters])?;
+        params.insert("pagination", pagination)?;
+        self.request("getEvents", params).await
+    }
+
+    pub async fn get_contract_data(
+        &self,
+        contract_id: &[u8; 32],
+    ) -> Result<ContractDataEntry, Error> {
+        let key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(Hash(*contract_id)),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        });
+        let entries = self.get_ledger_entries(&[key]).await?.entries;
+        let Some(entry) = entries.unwrap_or_default().into_iter().next() else {
+            let contract_address = stellar_strkey::Contract(*contract_id).to_string();
+            return Err(Error::NotFound("Contract".to_string(), contract_address));
+        };
+        match LedgerEntryData::from_xdr_base64(entry.xdr, Limits::none())? {
+            LedgerEntryData::ContractData(contract_data) => Ok(contract_data),
+            data => Err(Error::UnexpectedContractCodeDataType(data)),
+        }
+    }
+
+    pub async fn get_remote_wasm(&self, contract_id: &[u8; 32]) -> Result<Vec<u8>, Error> {
+        match self.get_contract_data(contract_id).await? {
+            ContractDataEntry {
+                val:
+                    ScVal::ContractInstance(ScContractInstance {
+                        executable: ContractExecutable::Wasm(hash),
+                        ..
+                    }),
+                ..
+            } => self.get_remote_wasm_from_hash(hash).await,
+            entry => Err(Error::UnexpectedToken(entry)),
+        }
+    }
+
+    pub async fn get_remote_wasm_from_hash(&self, hash: Hash) -> Result<Vec<u8>, Error> {
+        let key = LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() });
+        let entries = self.get_ledger_entries(&[key]).await?.entries;
+        let Some(entry) = entries.unwrap_or_default().into_iter().next() else {
+            return Err(Error::NotFound(
+                "Contract Code".to_string(),
+                hex::encode(hash),
+            ));
+        };
+        match LedgerEntryData::from_xdr_base64(entry.xdr, Limits::none())? {
+            LedgerEntryData::ContractCode(ContractCodeEntry { code, .. }) => Ok(code.into()),
+            data => Err(Error::UnexpectedContractCodeDataType(data)),
+        }
+    }
+
+    pub async fn get_contract_instance(
+        &self,
+        contract_id: &[u8; 32],
+    ) -> Result<ScContractInstance, Error> {
+        match self.get_contract_data(contract_id).await?.val {
+            ScVal::ContractInstance(instance) => Ok(instance),
+            val => Err(Error::UnexpectedContractInstance(val)),
+        }
+    }
+
+    /// Submit the transaction once, then poll for its result, retrying the polling on
+    /// transient errors. Submission itself is not retried as its errors can't be told apart.
+    pub async fn send_transaction_polling(
+        &self,
+        tx: &TransactionEnvelope,
+    ) -> Result<GetTransactionResponse, Error> {
+        let hash = self.send_transaction(tx).await?;
+        self.get_transaction_polling(&hash, None).await
+    }
+
+    /// Submit the transaction once. Submission is not retried as its errors can't be told apart.
+    pub async fn send_transaction(&self, tx: &TransactionEnvelope) -> Result<Hash, Error> {
+        tracing::trace!("Sending:\n{tx:#?}");
+        let mut params = ObjectParams::new();
+        params.insert("transaction", tx.to_xdr_base64(Limits::none())?)?;
+        let SendTransactionResponse {
+            hash,
+            error_result_xdr,
+            status,
+            ..
+        } = self
+            .client
+            .request("sendTransaction", params)
+            .await
+            .map_err(|e| Error::TransactionSubmissionFailed(format!("No status yet:\n {e:#?}")))?;
+        if status == "ERROR" {
+            let error = error_result_xdr
+                .ok_or(Error::MissingError)
+                .and_then(|xdr| {
+                    TransactionResult::from_xdr_base64(xdr, Limits::none())
+                        .map_err(|_| Error::InvalidResponse)
+                })?
+                .result;
+            tracing::error!("TXN {hash} failed:\n {error:#?}");
+            return Err(Error::TransactionSubmissionFailed(format!("{error:#?}")));
+        }
+        Ok(Hash::from_str(&hash)?)
+    }
+
+    /// Poll for the result of a submitted transaction until it completes or `timeout` passes,
+    /// retrying each poll on transient errors. While the transaction is pending, the attempt
+    /// count and elapsed time are logged as a warning, which goes to stderr and is silenced by
+    /// `--quiet`.
+    pub async fn get_transaction_polling(
+        &self,
+        hash: &Hash,
+        timeout: Option<Duration>,
+    ) -> Result<GetTransactionResponse, Error> {
+        let start = Instant::now();
+        let timeout = timeout.unwrap_or(DEFAULT_POLLING_TIMEOUT);
+        // Same exponential backoff as the RPC client's own polling
+        let backoff = 1.0 / (1.0 - (-1.0f64).exp());
+        let mut sleep_time = Duration::from_secs(1);
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let response = self.get_transaction(hash).await?;
+            match response.status.as_str() {
+                "SUCCESS" => {
+                    tracing::trace!("{response:#?}");
+                    return Ok(response);
+                }
+                "FAILED" => {
+                    tracing::error!("{response:#?}");
+                    return Err(Error::TransactionSubmissionFailed(format!(
+                        "{:#?}",
+                        response.result
+                    )));
+                }
+                "NOT_FOUND" => (),
+                _ => return Err(Error::UnexpectedTransactionStatus(response.status)),
+            }
+            let elapsed = start.elapsed();
+            if elapsed > timeout {
+                return Err(Error::TransactionSubmissionTimeout);
+            }
+            tracing::warn!(
+                "Waiting for transaction {hash}: attempt {attempt}, {}s elapsed",
+                elapsed.as_secs()
+            );
+            tokio::time::sleep(sleep_time).await;
+            sleep_time = sleep_time.mul_f64(backoff);
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use jsonrpsee_core::rpc_params;
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Where the request at the start of `buf` ends, as the length of its head and of its
+    /// body, if all of it has been read.
+    fn request_len(buf: &[u8]) -> Option<(usize, usize)> {
+        let head_len = buf.windows(4).position(|w| w == b"\r\n\r\n")? + 4;
+        let head = String::from_utf8_lossy(&buf[..head_len]);
+        let body_len = head
+            .lines()
+            .find_map(|l| {
+                let (k, v) = l.split_once(':')?;
+                k.eq_ignore_ascii_case("content-length")
+                    .then(|| v.trim().parse::<usize>().unwrap())
+            })
+            .unwrap_or_default();
+        (buf.len() >= head_len + body_len).then_some((head_len, body_len))
+    }
+
+    /// Read the next request on `stream` into `buf`, returning its head and body, or `None`
+    /// once the connection is closed.
+    async fn read_request(
+        stream: &mut tokio::net::TcpStream,
+        buf: &mut Vec<u8>,
+    ) -> Option<(String, Vec<u8>)> {
+        loop {
+            if let Some((head_len, body_len)) = request_len(buf) {
+                let request = buf.drain(..head_len + body_len).collect::<Vec<_>>();
+                let head = String::from_utf8_lossy(&request[..head_len]).into_owned();
+                return Some((head, request[head_len..].to_vec()));
+            }
+            let mut chunk = [0; 4096];
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return None,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            }
+        }
+    }
+
+    /// An HTTP response with a JSON body.
+    fn json_response(body: &Value) -> String {
+        let body = body.to_string();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    /// Answer a call, or each call in a batch, with the result of `respond`. A batch's
+    /// responses are in reverse order so that they must be matched by id.
+    fn answer(body: &[u8], respond: fn(&Value) -> Value) -> Value {
+        let answer = |call: &Value| {
+            let mut res = respond(call);
+            res["jsonrpc"] = json!("2.0");
+            res["id"] = call["id"].clone();
+            res
+        };
+        match serde_json::from_slice(body).unwrap() {
+            Value::Array(calls) => Value::Array(calls.iter().rev().map(answer).collect()),
+            call => answer(&call),
+        }
+    }
+
+    /// Serve HTTP requests to an RPC server whose calls are answered with `respond`, returning
+    /// the server's URL.
+    pub(crate) async fn mock_server(respond: fn(&Value) -> Value) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    while let Some((_, body)) = read_request(&mut stream, &mut buf).await {
+                        let response = json_response(&answer(&body, respond));
+                        stream.write_all(response.as_bytes()).await.unwrap();
+                    }
+                });
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn batch_returns_results_in_order() {
+        let url = mock_server(|call| json!({ "result": call["method"] })).await;
+        let client = Client::new(&url).unwrap();
+        let mut batch = BatchRequestBuilder::new();
+        batch.insert("getHealth", rpc_params![]).unwrap();
+        batch.insert("getLatestLedger", rpc_params![]).unwrap();
+        let results = client.batch::<String>(batch).await.unwrap();
+        let results = results.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(results, ["getHealth", "getLatestLedger"]);
+    }
+
+    #[tokio::test]
+    async fn batch_maps_errors_to_their_call() {
+        let url = mock_server(|call| {
+            if call["method"] == "getHealth" {
+                json!({ "result": "healthy" })
+            } else {
+                json!({ "error": { "code": -32601, "message": "method not found" } })
+            }
+        })
+        .await;
+        let client = Client::new(&url).unwrap();
+        let mut batch = BatchRequestBuilder::new();
+        batch.insert("getHealth", rpc_params![]).unwrap();
+        batch.insert("getUnknown", rpc_params![]).unwrap();
+        let mut results = client.batch::<String>(batch).await.unwrap().into_iter();
+        assert_eq!(results.next().unwrap().unwrap(), "healthy");
+        assert!(matches!(
+            results.next().unwrap(),
+            Err(Error::JsonRpc(jsonrpsee_core::Error::Call(e))) if e.code() == -32601
+        ));
+    }
+
+    fn transport_error(status_code: u16) -> Error {
+        Error::JsonRpc(jsonrpsee_core::Error::Transport(
+            jsonrpsee_http_client::transport::Error::RequestFailure { status_code }.into(),
+        ))
+    }
+
+    #[test]
+    fn classifies_transient_errors() {
+        assert!(is_transient(&transport_error(503)));
+        assert!(is_transient(&transport_error(429)));
+        assert!(!is_transient(&transport_error(400)));
+        assert!(is_transient(&Error::JsonRpc(
+            jsonrpsee_core::Error::RequestTimeout
+        )));
+        assert!(!is_transient(&Error::TransactionSubmissionFailed(
+            String::new()
+        )));
+        assert!(!is_transient(&Error::InvalidResponse));
+    }
+
+    fn health(_: &Value) -> Value {
+        json!({ "result": { "status": "healthy" } })
+    }
+
+    #[test]
+    fn adds_the_default_port() {
+        assert_eq!(
+            with_default_port("https://rpc.example"),
+            "https://rpc.example:443/"
+        );
+        assert_eq!(
+            with_default_port("http://rpc.example/soroban"),
+            "http://rpc.example:80/soroban"
+        );
+        assert_eq!(
+            with_default_port("https://rpc.example:8000"),
+            "https://rpc.example:8000/"
+        );
+    }
+
+    #[tokio::test]
+    async fn calls_the_server() {
+        let url = mock_server(health).await;
+        let client = RetryingClient::new(&url, &ClientOptions::default()).unwrap();
+        assert_eq!(client.get_health().await.unwrap().status, "healthy");
+    }
+
+    #[tokio::test]
+    async fn sends_headers_with_every_request() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let (head, body) = read_request(&mut stream, &mut buf).await.unwrap();
+            let response = json_response(&answer(&body, health));
+            stream.write_all(response.as_bytes()).await.unwrap();
+            head.to_lowercase()
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("secret"));
+        let options = ClientOptions {
+            headers,
+            ..ClientOptions::default()
+        };
+        let client = RetryingClient::new(&url, &options).unwrap();
+        client.get_health().await.unwrap();
+        let head = server.await.unwrap();
+        assert!(head.contains("x-api-key: secret"));
+        assert!(head.contains("x-client-name: soroban-cli"));
+    }
+
+    #[tokio::test]
+    async fn times_out_slow_requests() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        // Accept the connection but never respond
+        tokio::spawn(async move {
+            let (_stream, _) = listener.accept().await.unwrap();
+            std::future::pending::<()>().await;
+        });
+        let options = ClientOptions {
+            timeout: Some(Duration::from_millis(50)),
+            ..ClientOptions::default()
+        };
+        let client = RetryingClient::new(&url, &options).unwrap();
+        assert!(matches!(
+            client.get_health().await,
+            Err(Error::JsonRpc(jsonrpsee_core::Error::RequestTimeout))
+        ));
+    }
+
+    #[tokio::test]
+    async fn sends_requests_through_the_proxy() {
+        let proxy = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let options = ClientOptions {
+            proxy: Some(
+                format!("http://{}", proxy.local_addr().unwrap())
+                    .parse()
+                    .unwrap(),
+            ),
+            ..ClientOptions::default()
+        };
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = proxy.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let (connect, _) = read_request(&mut stream, &mut buf).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                .await
+                .unwrap();
+            let (_, body) = read_request(&mut stream, &mut buf).await.unwrap();
+            let response = json_response(&answer(&body, health));
+            stream.write_all(response.as_bytes()).await.unwrap();
+            connect
+        });
+        let client = RetryingClient::new("http://rpc.example", &options).unwrap();
+        client.get_health().await.unwrap();
+        assert!(server
+            .await
+            .unwrap()
+            .starts_with("CONNECT rpc.example:80 HTTP/1.1"));
+    }
+
+    /// Serve one HTTPS request with a self-signed certificate for `localhost`, for each of
+    /// `connections` connections, returning the server's URL.
+    #[cfg(unix)]
+    fn self_signed_server(connections: usize) -> String {
+        use hyper_tls::native_tls::{Identity, TlsAcceptor};
+        use openssl::{asn1::Asn1Time, ec, hash::MessageDigest, nid::Nid, pkey::PKey, x509};
+        use std::io::{Read, Write};
+
+        let group = ec::EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(ec::EcKey::generate(&group).unwrap()).unwrap();
+        let mut name = x509::X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "localhost").unwrap();
+        let name = name.build();
+        let mut cert = x509::X509Builder::new().unwrap();
+        cert.set_version(2).unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_issuer_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        cert.set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        cert.sign(&key, MessageDigest::sha256()).unwrap();
+        let identity = Identity::from_pkcs8(
+            &cert.build().to_pem().unwrap(),
+            &key.private_key_to_pem_pkcs8().unwrap(),
+        )
+        .unwrap();
+        let acceptor = TlsAcceptor::new(identity).unwrap();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let Ok(mut stream) = acceptor.accept(stream.unwrap()) else {
+                    continue;
+                };
+                let mut buf = Vec::new();
+                let (head_len, body_len) = loop {
+                    if let Some(len) = request_len(&buf) {
+                        break len;
+                    }
+                    let mut chunk = [0; 4096];
+                    let n = stream.read(&mut chunk).unwrap();
+                    buf.extend_from_slice(&chunk[..n]);
+                };
+                let body = &buf[head_len..head_len + body_len];
+                let response = json_response(&answer(body, health));
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        format!("https://localhost:{port}")
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn accepts_invalid_certificates_only_when_insecure() {
+        let url = self_signed_server(2);
+        let client = RetryingClient::new(&url, &ClientOptions::default()).unwrap();
+        assert!(client.get_health().await.is_err());
+        let options = ClientOptions {
+            insecure: true,
+            ..ClientOptions::default()
+        };
+        let client = RetryingClient::new(&url, &options).unwrap();
+        assert_eq!(client.get_health().await.unwrap().status, "healthy");
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_success() {
+        let retry = Retry {
+            max_retries: 3,
+            base_delay: Duration::ZERO,
+        };
+        let mut calls = 0;
+        let res = retry
+            .run(|| {
+                calls += 1;
+                let res = if calls < 3 {
+                    Err(transport_error(502))
+                } else {
+                    Ok(calls)
+                };
+                async move { res }
+            })
+            .await;
+        assert_eq!(res.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn stops_on_permanent_error_or_when_out_of_retries() {
+        let retry = Retry {
+            max_retries: 2,
+            base_delay: Duration::ZERO,
+        };
+        let mut calls = 0;
+        let res = retry
+            .run(|| {
+                calls += 1;
+                async { Err::<(), _>(transport_error(400)) }
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(calls, 1);
+
+        let mut calls = 0;
+        let res = retry
+            .run(|| {
+                calls += 1;
+                async { Err::<(), _>(transport_error(500)) }
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(calls, 3);
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use clap::arg;
+
+use crate::{
+    commands::HEADING_RPC,
+    rpc::{self, GetTransactionResponse},
+    utils::{
+        self,
+        rpc::{RetryingClient, DEFAULT_POLLING_TIMEOUT},
+    },
+    xdr::{self, Hash, TransactionEnvelope},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("transaction {hash} is still pending after {timeout}s")]
+    Timeout { hash: Hash, timeout: u64 },
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+impl Error {
+    /// The process exit code for this error, see [`crate::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Timeout { .. } => crate::exit_code::NETWORK,
+            Error::Rpc(e) => crate::exit_code::rpc(e),
+            Error::Xdr(_) => crate::exit_code::FAILURE,
+        }
+    }
+}
+
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Submit the transaction and print its hash without waiting for its result
+    #[arg(long, help_heading = HEADING_RPC)]
+    pub no_wait: bool,
+    /// Number of seconds to wait for the transaction's result before failing with its hash.
+    /// Defaults to the RPC client's timeout of 30 seconds
+    #[arg(long, help_heading = HEADING_RPC, conflicts_with = "no_wait")]
+    pub wait_timeout: Option<u64>,
+}
+
+/// A submitted transaction, with its result unless `--no-wait` was given.
+pub enum Submitted {
+    Pending(Hash),
+    Done(Box<GetTransactionResponse>),
+}
+
+impl Args {
+    /// Submit the transaction, then poll for its result unless `--no-wait` was given. Unless
+    /// `quiet`, the transaction's hash is printed to stderr before it's sent, so that it can
+    /// still be looked up if the CLI is stopped while waiting.
+    pub async fn submit(
+        &self,
+        client: &RetryingClient,
+        tx: &TransactionEnvelope,
+        network_passphrase: &str,
+        quiet: bool,
+    ) -> Result<Submitted, Error> {
+        if !quiet {
+            let hash = utils::hash_transaction_in_envelope(tx, network_passphrase)?;
+            eprintln!("Transaction hash is {}", hex::encode(hash));
+        }
+        let hash = client.send_transaction(tx).await?;
+        if self.no_wait {
+            return Ok(Submitted::Pending(hash));
+        }
+        let timeout = self.wait_timeout.map(Duration::from_secs);
+        match client.get_transaction_polling(&hash, timeout).await {
+            Ok(res) => Ok(Submitted::Done(Box::new(res))),
+            Err(rpc::Error::TransactionSubmissionTimeout) => Err(Error::Timeout {
+                hash,
+                timeout: self
+                    .wait_timeout
+                    .unwrap_or(DEFAULT_POLLING_TIMEOUT.as_secs()),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
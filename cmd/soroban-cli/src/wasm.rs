@@ -3,7 +3,8 @@ use sha2::{Digest, Sha256};
 use soroban_env_host::xdr::{self, Hash, LedgerKey, LedgerKeyContractCode};
 use soroban_spec_tools::contract::{self, Spec};
 use std::{
-    fs, io,
+    fs,
+    io::{self, Read},
     path::{Path, PathBuf},
 };
 
@@ -39,13 +40,34 @@ pub struct Args {
 }
 
 impl Args {
+    /// Whether the wasm is read from stdin, which a path of `-` selects. Stdin can only be read
+    /// once, so commands supporting it must only read the wasm once.
+    pub fn is_stdin(&self) -> bool {
+        self.wasm.as_os_str() == "-"
+    }
+
+    /// The wasm's path, or `<stdin>` when reading from stdin
+    pub fn name(&self) -> String {
+        if self.is_stdin() {
+            "<stdin>".to_string()
+        } else {
+            self.wasm.display().to_string()
+        }
+    }
+
     /// # Errors
     /// May fail to read wasm file
     pub fn read(&self) -> Result<Vec<u8>, Error> {
-        fs::read(&self.wasm).map_err(|e| Error::CannotReadContractFile {
+        let map_err = |e| Error::CannotReadContractFile {
             filepath: self.wasm.clone(),
             error: e,
-        })
+        };
+        if self.is_stdin() {
+            let mut wasm = Vec::new();
+            io::stdin().read_to_end(&mut wasm).map_err(map_err)?;
+            return Ok(wasm);
+        }
+        fs::read(&self.wasm).map_err(map_err)
     }
 
     /// # Errors
@@ -97,3 +119,57 @@ pub fn len(p: &Path) -> Result<u64, Error> {
         })?
         .len())
 }
+
+/// Custom sections that contracts carry their interface and metadata in, which must survive any
+/// stripping of custom sections.
+pub const CONTRACT_SECTIONS: [&str; 3] = ["contractspecv0", "contractmetav0", "contractenvmetav0"];
+
+/// Remove every custom section from a wasm module except those named in `keep`, leaving all
+/// other sections untouched.
+///
+/// # Errors
+/// May fail to parse the wasm module's sections
+pub fn strip_custom_sections(wasm: &[u8], keep: &[&str]) -> Result<Vec<u8>, Error> {
+    let mut reader = wasmparser::BinaryReader::new(wasm);
+    // Magic number and version
+    let mut stripped = reader.read_bytes(8)?.to_vec();
+    while !reader.eof() {
+        let start = reader.current_position();
+        let id = reader.read_u8()?;
+        let size = reader.read_var_u32()? as usize;
+        let contents = reader.read_bytes(size)?;
+        if id == 0 && !keep.contains(&wasmparser::BinaryReader::new(contents).read_string()?) {
+            continue;
+        }
+        stripped.extend_from_slice(&wasm[start..reader.current_position()]);
+    }
+    Ok(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_section(name: &str, data: &[u8]) -> Vec<u8> {
+        let len = u8::try_from(1 + name.len() + data.len()).unwrap();
+        let mut section = vec![0, len, u8::try_from(name.len()).unwrap()];
+        section.extend_from_slice(name.as_bytes());
+        section.extend_from_slice(data);
+        section
+    }
+
+    #[test]
+    fn strips_all_but_contract_sections() {
+        let header = b"\0asm\x01\0\0\0";
+        // An empty type section
+        let types = [1, 1, 0];
+        let spec = custom_section("contractspecv0", &[1, 2, 3]);
+        let meta = custom_section("contractmetav0", &[4]);
+        let names = custom_section("name", &[5, 6]);
+        let wasm = [&header[..], &names, &types, &spec, &names, &meta].concat();
+        assert_eq!(
+            strip_custom_sections(&wasm, &CONTRACT_SECTIONS).unwrap(),
+            [&header[..], &types, &spec, &meta].concat()
+        );
+    }
+}